@@ -87,8 +87,26 @@ pub fn run() -> Result<()> {
             "http://{}:{}",
             config.tendermint_host, config.tendermint_port
         );
-        web3_rpc =
-            fc_rpc::start_web3_service(evm_http, evm_ws, tendermint_rpc, base_app);
+        let debug_api = if CFG.enable_eth_debug_api {
+            let la = Arc::clone(&app.la);
+            let asset_of: baseapp::AssetLookup = Arc::new(move |code| {
+                la.read()
+                    .get_committed_state()
+                    .read()
+                    .get_asset_type(code)
+                    .map(|asset| asset.properties)
+            });
+            Some(asset_of)
+        } else {
+            None
+        };
+        web3_rpc = fc_rpc::start_web3_service(
+            evm_http,
+            evm_ws,
+            tendermint_rpc,
+            base_app,
+            debug_api,
+        );
     }
 
     let addr_str = format!("{}:{}", config.abci_host, config.abci_port);
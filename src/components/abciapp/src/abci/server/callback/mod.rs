@@ -6,29 +6,39 @@ mod utils;
 
 use {
     crate::{
-        abci::{server::ABCISubmissionServer, staking, IN_SAFE_ITV, POOL},
+        abci::{
+            server::{state_sync, ABCISubmissionServer},
+            staking, IN_SAFE_ITV, POOL,
+        },
         api::{
             query_server::BLOCK_CREATED,
             submission_server::{convert_tx, try_tx_catalog, TxCatalog},
         },
     },
     abci::{
-        Application, CheckTxType, RequestBeginBlock, RequestCheckTx, RequestCommit,
-        RequestDeliverTx, RequestEndBlock, RequestInfo, RequestInitChain, RequestQuery,
+        Application, CheckTxType, RequestApplySnapshotChunk, RequestBeginBlock,
+        RequestCheckTx, RequestCommit, RequestDeliverTx, RequestEndBlock, RequestInfo,
+        RequestInitChain, RequestListSnapshots, RequestLoadSnapshotChunk,
+        RequestOfferSnapshot, RequestQuery, ResponseApplySnapshotChunk,
         ResponseBeginBlock, ResponseCheckTx, ResponseCommit, ResponseDeliverTx,
-        ResponseEndBlock, ResponseInfo, ResponseInitChain, ResponseQuery,
+        ResponseEndBlock, ResponseInfo, ResponseInitChain, ResponseListSnapshots,
+        ResponseLoadSnapshotChunk, ResponseOfferSnapshot, ResponseQuery,
     },
     config::abci::global_cfg::CFG,
     fp_storage::hash::{Sha256, StorageHasher},
     lazy_static::lazy_static,
     ledger::{
-        converter::is_convert_account,
+        converter::{get_convert_account_asset, is_convert_account},
         staking::KEEP_HIST,
         store::{
             api_cache,
             fbnc::{new_mapx, Mapx},
         },
     },
+    nix::{
+        sys::signal::{kill, Signal},
+        unistd::Pid,
+    },
     parking_lot::{Mutex, RwLock},
     protobuf::RepeatedField,
     ruc::*,
@@ -39,6 +49,7 @@ use {
             atomic::{AtomicI64, Ordering},
             Arc,
         },
+        thread,
     },
 };
 
@@ -68,6 +79,14 @@ lazy_static! {
 pub fn info(s: &mut ABCISubmissionServer, req: &RequestInfo) -> ResponseInfo {
     let mut resp = ResponseInfo::new();
 
+    // report the restored snapshot to tendermint,
+    // it will be loaded on the next start
+    if let Some((h, hash)) = state_sync::pending_restore() {
+        resp.set_last_block_height(h as i64);
+        resp.set_last_block_app_hash(hash);
+        return resp;
+    }
+
     let mut la = s.la.write();
     let state = la.get_committed_state().write();
 
@@ -158,6 +177,20 @@ pub fn begin_block(
     s: &mut ABCISubmissionServer,
     req: &RequestBeginBlock,
 ) -> ResponseBeginBlock {
+    // the restored state can only be loaded on the next start,
+    // so shut down through the signal handler instead of going on with the empty state
+    if state_sync::pending_restore().is_some() {
+        log::warn!(target: "abciapp", "state sync finished, shutting down to load the restored state");
+        IN_SAFE_ITV.swap(true, Ordering::Relaxed);
+        info_omit!(kill(Pid::this(), Signal::SIGTERM));
+        loop {
+            thread::park();
+        }
+    }
+
+    // the ledger files of the last state sync snapshot may still be copied
+    state_sync::wait_for_checkpoint();
+
     #[cfg(target_os = "linux")]
     {
         // snapshot the last block
@@ -205,7 +238,16 @@ pub fn begin_block(
     {
         ResponseBeginBlock::default()
     } else {
-        s.account_base_app.write().begin_block(req)
+        let resp = s.account_base_app.write().begin_block(req);
+        if CFG.checkpoint.evm_fee_to_proposer_height <= header.height {
+            if let Ok(pk) = ruc::info!(staking::get_proposer(
+                &la.get_committed_state().read(),
+                header
+            )) {
+                s.account_base_app.write().set_block_proposer(&pk);
+            }
+        }
+        resp
     }
 }
 
@@ -256,8 +298,17 @@ pub fn deliver_tx(
                             resp.log = e.to_string();
                         }
                     } else if is_convert_account(&tx) {
-                        if let Err(err) =
-                            s.account_base_app.write().deliver_findora_tx(&tx)
+                        let asset = get_convert_account_asset(&tx).and_then(|code| {
+                            s.la.read()
+                                .get_committed_state()
+                                .read()
+                                .get_asset_type(&code)
+                                .map(|asset| asset.properties)
+                        });
+                        if let Err(err) = s
+                            .account_base_app
+                            .write()
+                            .deliver_findora_tx(&tx, asset.as_ref())
                         {
                             log::info!(target: "abciapp", "deliver convert account tx failed: {:?}", err);
 
@@ -268,33 +319,31 @@ pub fn deliver_tx(
                         }
 
                         if s.la.write().cache_transaction(tx).is_ok() {
-                            s.account_base_app
-                                .read()
-                                .deliver_state
-                                .state
-                                .write()
-                                .commit_session();
-                            s.account_base_app
-                                .read()
-                                .deliver_state
-                                .db
-                                .write()
-                                .commit_session();
+                            end_evm_session(s, true);
                             return resp;
                         }
 
-                        s.account_base_app
-                            .read()
-                            .deliver_state
-                            .state
-                            .write()
-                            .discard_session();
-                        s.account_base_app
-                            .read()
-                            .deliver_state
-                            .db
-                            .write()
-                            .discard_session();
+                        end_evm_session(s, false);
+                    } else if CFG.checkpoint.evm_custom_assets_height <= td_height
+                        && utils::is_asset_definition(&tx)
+                    {
+                        if let Err(err) =
+                            s.account_base_app.write().deliver_asset_definitions(&tx)
+                        {
+                            log::info!(target: "abciapp", "deliver asset definition tx failed: {:?}", err);
+
+                            resp.code = 1;
+                            resp.log =
+                                format!("deliver asset definition tx failed: {:?}", err);
+                            return resp;
+                        }
+
+                        let cached = s.la.write().cache_transaction(tx);
+                        end_evm_session(s, cached.is_ok());
+                        if let Err(e) = cached {
+                            resp.code = 1;
+                            resp.log = e.to_string();
+                        }
                     } else if let Err(e) = s.la.write().cache_transaction(tx) {
                         resp.code = 1;
                         resp.log = e.to_string();
@@ -401,6 +450,12 @@ pub fn commit(s: &mut ABCISubmissionServer, req: &RequestCommit) -> ResponseComm
     // cache last block for QueryServer
     pnk!(api_cache::update_api_cache(&mut state));
 
+    // discard the old data in pruning mode,
+    // an error is logged rather than halting the node
+    if let Some(keep_blocks) = CFG.pruning {
+        info_omit!(state.prune(keep_blocks));
+    }
+
     // snapshot them finally
     let path = format!("{}/{}", &CFG.ledger_dir, &state.get_status().snapshot_file);
     pnk!(serde_json::to_vec(&state.get_status())
@@ -419,12 +474,67 @@ pub fn commit(s: &mut ABCISubmissionServer, req: &RequestCommit) -> ResponseComm
         r.set_data(app_hash("commit", td_height, la_hash, cs_hash));
     }
 
+    // the ledger lock is still held, nothing will be written during this process
+    if 0 < CFG.state_sync_itv && 0 == td_height as u64 % CFG.state_sync_itv {
+        ledger::store::fbnc::flush_data();
+        info_omit!(state_sync::take_snapshot(
+            td_height as u64,
+            r.get_data(),
+            &s.account_base_app.read()
+        ));
+    }
+
     r
 }
 
+pub fn list_snapshots(
+    _s: &mut ABCISubmissionServer,
+    _req: &RequestListSnapshots,
+) -> ResponseListSnapshots {
+    state_sync::list_snapshots()
+}
+
+pub fn offer_snapshot(
+    s: &mut ABCISubmissionServer,
+    req: &RequestOfferSnapshot,
+) -> ResponseOfferSnapshot {
+    let h =
+        s.la.read()
+            .get_committed_state()
+            .read()
+            .get_tendermint_height();
+    state_sync::offer_snapshot(req, h)
+}
+
+pub fn load_snapshot_chunk(
+    _s: &mut ABCISubmissionServer,
+    req: &RequestLoadSnapshotChunk,
+) -> ResponseLoadSnapshotChunk {
+    state_sync::load_snapshot_chunk(req)
+}
+
+pub fn apply_snapshot_chunk(
+    _s: &mut ABCISubmissionServer,
+    req: &RequestApplySnapshotChunk,
+) -> ResponseApplySnapshotChunk {
+    state_sync::apply_snapshot_chunk(req)
+}
+
+/// Keep or drop the changes made to the EVM state by a findora transaction.
+fn end_evm_session(s: &ABCISubmissionServer, commit: bool) {
+    let app = s.account_base_app.read();
+    if commit {
+        app.deliver_state.state.write().commit_session();
+        app.deliver_state.db.write().commit_session();
+    } else {
+        app.deliver_state.state.write().discard_session();
+        app.deliver_state.db.write().discard_session();
+    }
+}
+
 /// Combines ledger state hash and EVM chain state hash
 /// and print app hashes for debugging
-fn app_hash(
+pub(super) fn app_hash(
     when: &str,
     height: i64,
     mut la_hash: Vec<u8>,
@@ -81,6 +81,15 @@ pub fn gen_tendermint_attr(tx: &Transaction) -> RepeatedField<Event> {
     RepeatedField::from_vec(res)
 }
 
+/// Whether the transaction defines a custom asset or updates the memo of one,
+/// which changes the attributes of its FRC20 precompile.
+pub fn is_asset_definition(tx: &Transaction) -> bool {
+    tx.body
+        .operations
+        .iter()
+        .any(|op| matches!(op, Operation::DefineAsset(_) | Operation::UpdateMemo(_)))
+}
+
 // collect informations of inputs and outputs
 // # return: ([from ...], [to ...])
 fn gen_tendermint_attr_addr(tx: &Transaction) -> (Vec<TagAttr>, Vec<TagAttr>) {
@@ -0,0 +1,701 @@
+//!
+//! # State Sync
+//!
+//! Snapshots served through the state sync callbacks of tendermint.
+//!
+//! A snapshot is an archive of all files under the ledger directory,
+//! that is the `LedgerStatus`, the Merkle logs, the utxo bitmap and
+//! the EVM `ChainState`, split into chunks whose hashes are
+//! recorded in the metadata of the snapshot.
+//!
+//! A snapshot is taken from a checkpoint of the ledger directory,
+//! the EVM databases are checkpointed by hard links on commit,
+//! the other files are copied in the background before the next block begins,
+//! then the chunks are built in the background.
+//!
+//! The restored files are verified against the trusted app hash,
+//! but they can not be swapped under the opened databases,
+//! so they are staged on disk and moved into place on the next start.
+//!
+
+use {
+    super::callback,
+    abci::{
+        RequestApplySnapshotChunk, RequestLoadSnapshotChunk, RequestOfferSnapshot,
+        ResponseApplySnapshotChunk, ResponseApplySnapshotChunk_Result,
+        ResponseListSnapshots, ResponseLoadSnapshotChunk, ResponseOfferSnapshot,
+        ResponseOfferSnapshot_Result, Snapshot,
+    },
+    baseapp::{BaseApp, CHAIN_HISTORY_DATA_PATH, CHAIN_STATE_PATH},
+    config::abci::global_cfg::CFG,
+    fp_storage::hash::{Sha256, StorageHasher},
+    lazy_static::lazy_static,
+    ledger::store::LedgerState,
+    parking_lot::{Condvar, Mutex},
+    protobuf::RepeatedField,
+    ruc::*,
+    serde::{Deserialize, Serialize},
+    std::{
+        cmp,
+        fs::{self, File},
+        io::{self, BufReader, BufWriter, Read, Write},
+        path::{Path, PathBuf},
+        sync::atomic::{AtomicBool, Ordering},
+        thread,
+    },
+};
+
+/// The version of the snapshot format.
+pub const SNAPSHOT_FORMAT: u32 = 1;
+
+const CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+const STATE_SYNC_DIR: &str = "__state_sync__";
+const METADATA_FILE: &str = "metadata";
+const ARCHIVE_FILE: &str = "archive";
+const PENDING_FILE: &str = "pending";
+
+lazy_static! {
+    // the snapshot being restored
+    static ref RESTORE: Mutex<Option<Restore>> = Mutex::new(None);
+    // the ledger files are being copied into a checkpoint
+    static ref COPYING: (Mutex<bool>, Condvar) = (Mutex::new(false), Condvar::new());
+}
+
+// a snapshot is being built in the background
+static BUILDING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Metadata {
+    height: u64,
+    app_hash: Vec<u8>,
+    chunk_hashes: Vec<Vec<u8>>,
+}
+
+impl Metadata {
+    // the hash of a snapshot is the hash of all its chunk hashes
+    fn hash(&self) -> Vec<u8> {
+        Sha256::hash(&self.chunk_hashes.concat()).to_vec()
+    }
+
+    fn to_snapshot(&self) -> Result<Snapshot> {
+        let mut snapshot = Snapshot::new();
+        snapshot.set_height(self.height);
+        snapshot.set_format(SNAPSHOT_FORMAT);
+        snapshot.set_chunks(self.chunk_hashes.len() as u32);
+        snapshot.set_hash(self.hash());
+        snapshot.set_metadata(serde_json::to_vec(self).c(d!())?);
+        Ok(snapshot)
+    }
+}
+
+struct Restore {
+    meta: Metadata,
+    next_chunk: u32,
+    archive: BufWriter<File>,
+}
+
+#[inline(always)]
+fn snapshots_dir() -> PathBuf {
+    Path::new(&CFG.ledger_dir)
+        .join(STATE_SYNC_DIR)
+        .join("snapshots")
+}
+
+#[inline(always)]
+fn restore_dir() -> PathBuf {
+    Path::new(&CFG.ledger_dir)
+        .join(STATE_SYNC_DIR)
+        .join("restore")
+}
+
+/// Take a snapshot after the block at `height` has been committed,
+/// the caller must ensure that no data will be written during the checkpoint,
+/// and call [`wait_for_checkpoint`] before writing the ledger again.
+///
+/// Only the EVM databases are checkpointed in place,
+/// the ledger files are copied and the chunks are built in the background,
+/// and it is skipped if the previous snapshot has not been built.
+pub fn take_snapshot(height: u64, app_hash: &[u8], app: &BaseApp) -> Result<()> {
+    if BUILDING.swap(true, Ordering::AcqRel) {
+        return Err(eg!(format!(
+            "the previous snapshot is still being built, skip height {}",
+            height
+        )));
+    }
+
+    let tmp_dir = snapshots_dir().join(format!("{}.tmp", height));
+    let data_dir = tmp_dir.join("data");
+    if let Err(e) = checkpoint_evm(&data_dir, app).c(d!()) {
+        BUILDING.store(false, Ordering::Release);
+        return Err(e);
+    }
+
+    *COPYING.0.lock() = true;
+    let app_hash = app_hash.to_vec();
+    thread::spawn(move || {
+        let copied = copy_ledger(&data_dir).c(d!());
+        *COPYING.0.lock() = false;
+        COPYING.1.notify_all();
+
+        info_omit!(copied.and_then(|_| build_snapshot(height, app_hash, tmp_dir)));
+        BUILDING.store(false, Ordering::Release);
+    });
+
+    Ok(())
+}
+
+/// Block until the ledger files of the last snapshot have been copied.
+pub fn wait_for_checkpoint() {
+    let (copying, cvar) = &*COPYING;
+    let mut copying = copying.lock();
+    while *copying {
+        cvar.wait(&mut copying);
+    }
+}
+
+// Create checkpoints of the EVM databases in `dir`.
+fn checkpoint_evm(dir: &Path, app: &BaseApp) -> Result<()> {
+    omit!(fs::remove_dir_all(dir));
+    fs::create_dir_all(dir).c(d!())?;
+    app.create_checkpoint(dir).c(d!())
+}
+
+// Copy the ledger files into `dir`.
+fn copy_ledger(dir: &Path) -> Result<()> {
+    // the EVM databases are checkpointed instead
+    let skip = [STATE_SYNC_DIR, CHAIN_STATE_PATH, CHAIN_HISTORY_DATA_PATH];
+    let mut files = vec![];
+    collect_files(Path::new(&CFG.ledger_dir), "", &skip, &mut files).c(d!())?;
+    for (name, path) in files {
+        let target = dir.join(&name);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).c(d!())?;
+        }
+        fs::copy(&path, &target).c(d!(name))?;
+    }
+
+    Ok(())
+}
+
+// Build the chunks from the checkpoint in `tmp_dir`,
+// and move them into place along with the metadata.
+fn build_snapshot(height: u64, app_hash: Vec<u8>, tmp_dir: PathBuf) -> Result<()> {
+    let data_dir = tmp_dir.join("data");
+
+    let meta = Metadata {
+        height,
+        app_hash,
+        chunk_hashes: pack(&data_dir, tmp_dir.clone()).c(d!())?,
+    };
+    serde_json::to_vec(&meta)
+        .c(d!())
+        .and_then(|m| fs::write(tmp_dir.join(METADATA_FILE), m).c(d!()))?;
+    fs::remove_dir_all(&data_dir).c(d!())?;
+
+    let dir = snapshots_dir().join(height.to_string());
+    omit!(fs::remove_dir_all(&dir));
+    fs::rename(&tmp_dir, &dir).c(d!())?;
+
+    prune_snapshots().c(d!())
+}
+
+// Write all files in `src` as an archive into the chunks in `dest`,
+// returns the hashes of the chunks.
+fn pack(src: &Path, dest: PathBuf) -> Result<Vec<Vec<u8>>> {
+    let mut files = vec![];
+    collect_files(src, "", &[], &mut files).c(d!())?;
+
+    let mut writer = ChunkWriter::new(dest);
+    for (name, path) in files {
+        let size = fs::metadata(&path).c(d!())?.len();
+        writer
+            .write_all(&(name.len() as u32).to_le_bytes())
+            .and_then(|_| writer.write_all(name.as_bytes()))
+            .and_then(|_| writer.write_all(&size.to_le_bytes()))
+            .c(d!())?;
+        let mut f = File::open(&path).c(d!())?.take(size);
+        if size != io::copy(&mut f, &mut writer).c(d!())? {
+            return Err(eg!(format!("{} was truncated", name)));
+        }
+    }
+
+    writer.finish().c(d!())
+}
+
+// Keep only the latest `CFG.state_sync_cap` snapshots.
+fn prune_snapshots() -> Result<()> {
+    get_sorted_heights()
+        .c(d!())?
+        .into_iter()
+        .skip(CFG.state_sync_cap)
+        .try_for_each(|h| {
+            fs::remove_dir_all(snapshots_dir().join(h.to_string())).c(d!())
+        })
+}
+
+// In descending order.
+fn get_sorted_heights() -> Result<Vec<u64>> {
+    let dir = snapshots_dir();
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut heights = fs::read_dir(dir)
+        .c(d!())?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().to_str().and_then(|n| n.parse::<u64>().ok()))
+        .collect::<Vec<_>>();
+    heights.sort_unstable_by(|a, b| b.cmp(a));
+
+    Ok(heights)
+}
+
+fn get_metadata(height: u64) -> Result<Metadata> {
+    fs::read(snapshots_dir().join(height.to_string()).join(METADATA_FILE))
+        .c(d!())
+        .and_then(|m| serde_json::from_slice(&m).c(d!()))
+}
+
+// Collect all regular files in `dir` except the top-level entries in `skip`,
+// sorted by their paths relative to `dir`.
+fn collect_files(
+    dir: &Path,
+    prefix: &str,
+    skip: &[&str],
+    files: &mut Vec<(String, PathBuf)>,
+) -> Result<()> {
+    let mut entries = fs::read_dir(dir)
+        .c(d!())?
+        .collect::<io::Result<Vec<_>>>()
+        .c(d!())?;
+    entries.sort_by_key(|e| e.file_name());
+
+    for e in entries {
+        let name = e.file_name().to_str().c(d!())?.to_owned();
+        if prefix.is_empty() && skip.contains(&name.as_str()) {
+            continue;
+        }
+
+        let name = prefix.to_owned() + &name;
+        let ft = e.file_type().c(d!())?;
+        if ft.is_dir() {
+            collect_files(&e.path(), &(name + "/"), skip, files).c(d!())?;
+        } else if ft.is_file() {
+            files.push((name, e.path()));
+        }
+    }
+
+    Ok(())
+}
+
+// Split the archive into chunk files of `CHUNK_SIZE`.
+struct ChunkWriter {
+    dir: PathBuf,
+    buf: Vec<u8>,
+    hashes: Vec<Vec<u8>>,
+}
+
+impl ChunkWriter {
+    fn new(dir: PathBuf) -> Self {
+        ChunkWriter {
+            dir,
+            buf: Vec::with_capacity(CHUNK_SIZE),
+            hashes: vec![],
+        }
+    }
+
+    fn write_chunk(&mut self) -> io::Result<()> {
+        let path = self.dir.join(format!("chunk.{}", self.hashes.len()));
+        fs::write(path, &self.buf)?;
+        self.hashes.push(Sha256::hash(&self.buf).to_vec());
+        self.buf.clear();
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Vec<Vec<u8>>> {
+        if !self.buf.is_empty() || self.hashes.is_empty() {
+            self.write_chunk().c(d!())?;
+        }
+        Ok(self.hashes)
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        let n = cmp::min(data.len(), CHUNK_SIZE - self.buf.len());
+        self.buf.extend_from_slice(&data[..n]);
+        if CHUNK_SIZE == self.buf.len() {
+            self.write_chunk()?;
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// List the local snapshots.
+pub fn list_snapshots() -> ResponseListSnapshots {
+    let snapshots = get_sorted_heights()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|h| info!(get_metadata(h).and_then(|m| m.to_snapshot())).ok())
+        .collect();
+
+    let mut resp = ResponseListSnapshots::new();
+    resp.set_snapshots(RepeatedField::from_vec(snapshots));
+    resp
+}
+
+/// Load a chunk of a local snapshot,
+/// an empty chunk is returned if it does not exist.
+pub fn load_snapshot_chunk(req: &RequestLoadSnapshotChunk) -> ResponseLoadSnapshotChunk {
+    let mut resp = ResponseLoadSnapshotChunk::new();
+
+    if SNAPSHOT_FORMAT == req.format {
+        let path = snapshots_dir()
+            .join(req.height.to_string())
+            .join(format!("chunk.{}", req.chunk));
+        if let Ok(chunk) = info!(fs::read(path)) {
+            resp.set_chunk(chunk);
+        }
+    }
+
+    resp
+}
+
+/// Start restoring a snapshot offered by a peer,
+/// only a node without any committed block will accept it.
+pub fn offer_snapshot(
+    req: &RequestOfferSnapshot,
+    cur_height: u64,
+) -> ResponseOfferSnapshot {
+    let mut resp = ResponseOfferSnapshot::new();
+
+    let snapshot = req.get_snapshot();
+    if SNAPSHOT_FORMAT != snapshot.format {
+        resp.set_result(ResponseOfferSnapshot_Result::REJECT_FORMAT);
+        return resp;
+    }
+
+    if 0 < cur_height {
+        resp.set_result(ResponseOfferSnapshot_Result::ABORT);
+        return resp;
+    }
+
+    let meta = match info!(serde_json::from_slice::<Metadata>(snapshot.get_metadata())) {
+        Ok(m) => m,
+        Err(_) => {
+            resp.set_result(ResponseOfferSnapshot_Result::REJECT);
+            return resp;
+        }
+    };
+
+    if meta.height != snapshot.height
+        || meta.chunk_hashes.len() != snapshot.chunks as usize
+        || meta.hash() != snapshot.hash
+        || meta.app_hash != req.app_hash
+    {
+        resp.set_result(ResponseOfferSnapshot_Result::REJECT);
+        return resp;
+    }
+
+    omit!(fs::remove_dir_all(restore_dir()));
+    let archive = fs::create_dir_all(restore_dir())
+        .c(d!())
+        .and_then(|_| File::create(restore_dir().join(ARCHIVE_FILE)).c(d!()));
+
+    match info!(archive) {
+        Ok(f) => {
+            *RESTORE.lock() = Some(Restore {
+                meta,
+                next_chunk: 0,
+                archive: BufWriter::new(f),
+            });
+            resp.set_result(ResponseOfferSnapshot_Result::ACCEPT);
+        }
+        Err(_) => {
+            resp.set_result(ResponseOfferSnapshot_Result::ABORT);
+        }
+    }
+
+    resp
+}
+
+/// Verify and save a chunk of the snapshot being restored,
+/// the whole snapshot will be unpacked and verified after the last chunk is applied.
+pub fn apply_snapshot_chunk(
+    req: &RequestApplySnapshotChunk,
+) -> ResponseApplySnapshotChunk {
+    let mut resp = ResponseApplySnapshotChunk::new();
+
+    let mut restore = RESTORE.lock();
+    let r = if let Some(r) = restore.as_mut() {
+        r
+    } else {
+        resp.set_result(ResponseApplySnapshotChunk_Result::ABORT);
+        return resp;
+    };
+
+    if req.index != r.next_chunk {
+        resp.set_result(ResponseApplySnapshotChunk_Result::RETRY);
+        resp.set_refetch_chunks(vec![r.next_chunk]);
+        return resp;
+    }
+
+    if Some(&Sha256::hash(req.get_chunk()).to_vec())
+        != r.meta.chunk_hashes.get(req.index as usize)
+    {
+        resp.set_result(ResponseApplySnapshotChunk_Result::RETRY);
+        resp.set_refetch_chunks(vec![req.index]);
+        resp.set_reject_senders(RepeatedField::from_vec(vec![req.sender.clone()]));
+        return resp;
+    }
+
+    if info!(r.archive.write_all(req.get_chunk())).is_err() {
+        resp.set_result(ResponseApplySnapshotChunk_Result::ABORT);
+        return resp;
+    }
+    r.next_chunk += 1;
+
+    if r.next_chunk as usize == r.meta.chunk_hashes.len() {
+        let data_dir = restore_dir().join("data");
+        let res = r
+            .archive
+            .flush()
+            .c(d!())
+            .and_then(|_| unpack(&restore_dir().join(ARCHIVE_FILE), &data_dir).c(d!()))
+            .and_then(|_| check_app_hash(&data_dir, &r.meta).c(d!()))
+            .and_then(|_| {
+                let pending = (r.meta.height, r.meta.app_hash.clone());
+                serde_json::to_vec(&pending)
+                    .c(d!())
+                    .and_then(|p| fs::write(restore_dir().join(PENDING_FILE), p).c(d!()))
+            });
+
+        if info!(res).is_err() {
+            resp.set_result(ResponseApplySnapshotChunk_Result::REJECT_SNAPSHOT);
+            omit!(fs::remove_dir_all(restore_dir()));
+            *restore = None;
+            return resp;
+        }
+
+        omit!(fs::remove_file(restore_dir().join(ARCHIVE_FILE)));
+        *restore = None;
+    }
+
+    resp.set_result(ResponseApplySnapshotChunk_Result::ACCEPT);
+    resp
+}
+
+// Recompute the app hash of the restored data like `commit` does,
+// it must be the trusted one offered by tendermint.
+fn check_app_hash(dir: &Path, meta: &Metadata) -> Result<()> {
+    let la_hash = LedgerState::check_ledger_files(dir.to_str().c(d!())?)
+        .c(d!())?
+        .as_ref()
+        .to_vec();
+
+    let h = meta.height as i64;
+    let app_hash = if CFG.checkpoint.disable_evm_block_height < h
+        && h < CFG.checkpoint.enable_frc20_height
+    {
+        la_hash
+    } else {
+        let cs_hash = BaseApp::read_root_hash(dir).c(d!())?;
+        callback::app_hash("state_sync", h, la_hash, cs_hash)
+    };
+
+    if app_hash != meta.app_hash {
+        return Err(eg!("app hash mismatch"));
+    }
+
+    Ok(())
+}
+
+fn unpack(archive: &Path, dest: &Path) -> Result<()> {
+    omit!(fs::remove_dir_all(dest));
+    fs::create_dir_all(dest).c(d!())?;
+
+    let mut r = BufReader::new(File::open(archive).c(d!())?);
+    loop {
+        let mut len = [0u8; 4];
+        match r.read_exact(&mut len) {
+            Ok(_) => {}
+            Err(e) if io::ErrorKind::UnexpectedEof == e.kind() => break,
+            Err(e) => return Err(e).c(d!()),
+        }
+
+        let mut name = vec![0u8; u32::from_le_bytes(len) as usize];
+        r.read_exact(&mut name).c(d!())?;
+        let name = String::from_utf8(name).c(d!())?;
+
+        // never write outside of `dest`
+        if name
+            .split('/')
+            .any(|c| c.is_empty() || "." == c || ".." == c)
+        {
+            return Err(eg!(format!("invalid path: {}", name)));
+        }
+
+        let mut size = [0u8; 8];
+        r.read_exact(&mut size).c(d!())?;
+        let size = u64::from_le_bytes(size);
+
+        let path = dest.join(&name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).c(d!())?;
+        }
+        let mut f = File::create(&path).c(d!())?;
+        if size != io::copy(&mut (&mut r).take(size), &mut f).c(d!())? {
+            return Err(eg!(format!("{} was truncated", name)));
+        }
+    }
+
+    Ok(())
+}
+
+/// The height and app hash of a restored snapshot waiting to be loaded.
+pub fn pending_restore() -> Option<(u64, Vec<u8>)> {
+    fs::read(restore_dir().join(PENDING_FILE))
+        .ok()
+        .and_then(|p| serde_json::from_slice(&p).ok())
+}
+
+/// Move the restored data into the ledger directory,
+/// must be called before any data is opened.
+pub fn finish_restore() -> Result<()> {
+    if pending_restore().is_none() {
+        return Ok(());
+    }
+
+    for e in fs::read_dir(restore_dir().join("data")).c(d!())? {
+        let e = e.c(d!())?;
+        let target = Path::new(&CFG.ledger_dir).join(e.file_name());
+        if target.is_dir() {
+            fs::remove_dir_all(&target).c(d!())?;
+        } else if target.exists() {
+            fs::remove_file(&target).c(d!())?;
+        }
+        fs::rename(e.path(), &target).c(d!())?;
+    }
+
+    fs::remove_dir_all(restore_dir()).c(d!())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn write_entry(archive: &mut Vec<u8>, name: &str, data: &[u8]) {
+        archive.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        archive.extend_from_slice(name.as_bytes());
+        archive.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        archive.extend_from_slice(data);
+    }
+
+    #[test]
+    fn test_pack_and_unpack() {
+        let src = tempfile::tempdir().unwrap();
+        let chunks = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+
+        let big = (0..CHUNK_SIZE + 100).map(|i| i as u8).collect::<Vec<_>>();
+        fs::create_dir_all(src.path().join("a/b")).unwrap();
+        fs::write(src.path().join("ledger_status"), b"status").unwrap();
+        fs::write(src.path().join("a/b/big"), &big).unwrap();
+        fs::write(src.path().join("a/empty"), b"").unwrap();
+
+        let hashes = pack(src.path(), chunks.path().to_path_buf()).unwrap();
+        assert_eq!(2, hashes.len());
+
+        let archive = chunks.path().join(ARCHIVE_FILE);
+        let mut f = File::create(&archive).unwrap();
+        for (i, h) in hashes.iter().enumerate() {
+            let chunk = fs::read(chunks.path().join(format!("chunk.{}", i))).unwrap();
+            assert_eq!(h, &Sha256::hash(&chunk).to_vec());
+            f.write_all(&chunk).unwrap();
+        }
+        drop(f);
+
+        unpack(&archive, dest.path()).unwrap();
+        assert_eq!(
+            b"status",
+            &fs::read(dest.path().join("ledger_status")).unwrap()[..]
+        );
+        assert_eq!(big, fs::read(dest.path().join("a/b/big")).unwrap());
+        assert!(fs::read(dest.path().join("a/empty")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unpack_rejects_invalid_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join(ARCHIVE_FILE);
+        let dest = dir.path().join("data");
+
+        for name in ["../escaped", "a/../../escaped", "/escaped", "a//b", "./a"] {
+            let mut data = vec![];
+            write_entry(&mut data, name, b"x");
+            fs::write(&archive, data).unwrap();
+
+            assert!(unpack(&archive, &dest).is_err());
+            assert!(!dir.path().join("escaped").exists());
+            assert!(!Path::new("/escaped").exists());
+        }
+
+        // a truncated entry
+        let mut data = vec![];
+        write_entry(&mut data, "a", b"xyz");
+        data.pop();
+        fs::write(&archive, data).unwrap();
+        assert!(unpack(&archive, &dest).is_err());
+    }
+
+    #[test]
+    fn test_apply_snapshot_chunk() {
+        let dir = tempfile::tempdir().unwrap();
+        let chunks = vec![b"chunk.0".to_vec(), b"chunk.1".to_vec()];
+
+        *RESTORE.lock() = Some(Restore {
+            meta: Metadata {
+                height: 1,
+                app_hash: vec![],
+                chunk_hashes: chunks.iter().map(|c| Sha256::hash(c).to_vec()).collect(),
+            },
+            next_chunk: 0,
+            archive: BufWriter::new(
+                File::create(dir.path().join(ARCHIVE_FILE)).unwrap(),
+            ),
+        });
+
+        let gen_req = |index: u32, chunk: &[u8]| {
+            let mut req = RequestApplySnapshotChunk::new();
+            req.set_index(index);
+            req.set_chunk(chunk.to_vec());
+            req.set_sender("peer".to_owned());
+            req
+        };
+
+        // out of order
+        let resp = apply_snapshot_chunk(&gen_req(1, &chunks[1]));
+        assert_eq!(ResponseApplySnapshotChunk_Result::RETRY, resp.result);
+        assert_eq!(vec![0], resp.refetch_chunks);
+        assert!(resp.reject_senders.is_empty());
+
+        // hash mismatch
+        let resp = apply_snapshot_chunk(&gen_req(0, &chunks[1]));
+        assert_eq!(ResponseApplySnapshotChunk_Result::RETRY, resp.result);
+        assert_eq!(vec![0], resp.refetch_chunks);
+        assert_eq!(&["peer".to_owned()][..], resp.get_reject_senders());
+
+        let resp = apply_snapshot_chunk(&gen_req(0, &chunks[0]));
+        assert_eq!(ResponseApplySnapshotChunk_Result::ACCEPT, resp.result);
+        assert_eq!(1, RESTORE.lock().as_ref().unwrap().next_chunk);
+
+        *RESTORE.lock() = None;
+        let resp = apply_snapshot_chunk(&gen_req(1, &chunks[1]));
+        assert_eq!(ResponseApplySnapshotChunk_Result::ABORT, resp.result);
+    }
+}
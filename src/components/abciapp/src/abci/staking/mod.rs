@@ -13,6 +13,7 @@ use {
     crate::abci::server::callback::TENDERMINT_BLOCK_HEIGHT,
     abci::{Evidence, Header, LastCommitInfo, PubKey, ValidatorUpdate},
     baseapp::BaseApp as AccountBaseApp,
+    config::abci::global_cfg::CFG,
     lazy_static::lazy_static,
     ledger::{
         data_model::{Operation, Transaction, ASSET_TYPE_FRA},
@@ -21,7 +22,7 @@ use {
                 governance::{governance_penalty_tendermint_auto, ByzantineKind},
                 mint_fra::{MintEntry, MintFraOps, MintKind},
             },
-            td_addr_to_string, Staking, VALIDATOR_UPDATE_BLOCK_ITV,
+            td_addr_to_string, BlockHeight, Staking, VALIDATOR_UPDATE_BLOCK_ITV,
         },
         store::LedgerState,
     },
@@ -32,6 +33,7 @@ use {
         ops::{Deref, DerefMut},
         sync::atomic::Ordering,
     },
+    zei::xfr::sig::XfrPublicKey,
 };
 
 // The top 50~ candidate validators
@@ -103,16 +105,17 @@ pub fn get_validators(
                 // - new power = 0: remove existing entries
                 // - the power returned by `LastCommitInfo` is impossible
                 // to be zero in the context of tendermint
-                *power as u64 != v.td_power
+                // - jailed validators are removed
+                *power as u64 != v.consensus_power()
             } else {
                 // add new validator
                 //
                 // try to remove non-existing entries is not allowed
-                0 < v.td_power
+                0 < v.consensus_power()
             }
         })
         // this conversion is safe in the context of tendermint
-        .map(|v| (&v.td_pubkey, v.td_power as i64))
+        .map(|v| (&v.td_pubkey, v.consensus_power() as i64))
         .collect::<Vec<_>>();
 
     if vs.is_empty() {
@@ -144,10 +147,20 @@ pub fn get_validators(
     ))
 }
 
+/// Look up the staking key of the block proposer,
+/// who is paid with the EVM transaction fees of the block.
+pub fn get_proposer(la: &LedgerState, header: &Header) -> Result<XfrPublicKey> {
+    la.get_staking()
+        .validator_td_addr_to_app_pk(&td_addr_to_string(&header.proposer_address))
+        .c(d!())
+}
+
 /// Call this function in `EndBlock`,
 /// - pay delegation rewards
 /// - pay proposer rewards(traditional block rewards)
 /// - do governance operations
+/// - tally and execute governance proposals
+/// - apply the scheduled commission rate changes
 pub fn system_ops(
     la: &mut LedgerState,
     header: &Header,
@@ -157,6 +170,8 @@ pub fn system_ops(
     // trigger system staking process
     la.get_staking_mut().delegation_process();
     la.get_staking_mut().validator_apply_current();
+    la.get_staking_mut().validator_apply_commission_changes();
+    la.get_staking_mut().proposal_process();
 
     ruc::info_omit!(set_rewards(
         la,
@@ -165,6 +180,8 @@ pub fn system_ops(
     ));
 
     // tendermint primary governances
+    let evidence_tombstone = CFG.checkpoint.enable_evidence_tombstone_height
+        <= la.get_staking().cur_height() as i64;
     evs.iter()
         .filter(|ev| ev.validator.is_some())
         .for_each(|ev| {
@@ -174,7 +191,18 @@ pub fn system_ops(
                 kind: ev.field_type.as_str(),
             };
 
-            ruc::info_omit!(system_governance(la.get_staking_mut().deref_mut(), &bz));
+            if evidence_tombstone {
+                ruc::info_omit!(system_evidence(
+                    la.get_staking_mut().deref_mut(),
+                    &bz,
+                    ev.height as BlockHeight
+                ));
+            } else {
+                ruc::info_omit!(system_governance(
+                    la.get_staking_mut().deref_mut(),
+                    &bz
+                ));
+            }
         });
 
     // application custom governances
@@ -198,6 +226,21 @@ pub fn system_ops(
             });
         }
 
+        // jail the validators who missed too many blocks
+        if CFG.checkpoint.enable_validator_jail_height
+            <= la.get_staking().cur_height() as i64
+        {
+            let offline_list = lci
+                .votes
+                .iter()
+                .filter(|v| !v.signed_last_block)
+                .flat_map(|info| info.validator.as_ref().map(|v| &v.address))
+                .collect::<BTreeSet<_>>();
+            ruc::info_omit!(la
+                .get_staking_mut()
+                .validator_handle_missed_blocks(&offline_list));
+        }
+
         if online_list.len() != lci.votes.len() {
             if let Ok(pl) = ruc::info!(gen_offline_punish_list(
                 la.get_staking().deref(),
@@ -256,19 +299,38 @@ struct ByzantineInfo<'a> {
     kind: &'a str,
 }
 
+impl ByzantineInfo<'_> {
+    fn byzantine_kind(&self) -> Result<ByzantineKind> {
+        match self.kind {
+            "DUPLICATE_VOTE" => Ok(ByzantineKind::DuplicateVote),
+            "LIGHT_CLIENT_ATTACK" => Ok(ByzantineKind::LightClientAttack),
+            "OFF_LINE" => Ok(ByzantineKind::OffLine),
+            "UNKNOWN" => Ok(ByzantineKind::Unknown),
+            _ => Err(eg!()),
+        }
+    }
+}
+
 /// Auto governance.
 fn system_governance(staking: &mut Staking, bz: &ByzantineInfo) -> Result<()> {
     // ruc::pd!(serde_json::to_string(&bz).unwrap());
-    let kind = match bz.kind {
-        "DUPLICATE_VOTE" => ByzantineKind::DuplicateVote,
-        "LIGHT_CLIENT_ATTACK" => ByzantineKind::LightClientAttack,
-        "OFF_LINE" => ByzantineKind::OffLine,
-        "UNKNOWN" => ByzantineKind::Unknown,
-        _ => return Err(eg!()),
-    };
+    let kind = bz.byzantine_kind().c(d!())?;
     governance_penalty_tendermint_auto(staking, bz.addr, &kind).c(d!())
 }
 
+/// Auto governance based on the evidences of tendermint,
+/// each evidence will be handled only once.
+fn system_evidence(
+    staking: &mut Staking,
+    bz: &ByzantineInfo,
+    height: BlockHeight,
+) -> Result<()> {
+    let kind = bz.byzantine_kind().c(d!())?;
+    staking
+        .governance_handle_evidence(bz.addr, height, kind)
+        .c(d!())
+}
+
 /// Pay for freed 'Delegations' and 'FraDistributions'.
 pub fn system_mint_pay(
     la: &LedgerState,
@@ -301,7 +363,9 @@ pub fn system_mint_pay(
                     limit >= 0
                 })
                 .map(|(k, n)| {
-                    MintEntry::new(MintKind::Claim, k, None, n, ASSET_TYPE_FRA)
+                    // pay to the withdrawal address of a validator if set
+                    let receiver_pk = staking.delegation_get_reward_receiver(&k);
+                    MintEntry::new(MintKind::Claim, k, receiver_pk, n, ASSET_TYPE_FRA)
                 }),
         )
         .take(NUM_TO_PAY)
@@ -351,10 +415,11 @@ fn gen_offline_punish_list(
         .validator_get_effective_at_height(last_height as u64)
         .c(d!())?;
 
+    // jailed validators have been removed from tendermint
     let mut vs = validators
         .body
         .values()
-        .map(|v| (&v.td_addr, v.td_power))
+        .map(|v| (&v.td_addr, v.consensus_power()))
         .collect::<Vec<_>>();
     vs.sort_by(|a, b| b.1.cmp(&a.1));
     vs.iter_mut().skip(VALIDATOR_LIMIT).for_each(|(_, power)| {
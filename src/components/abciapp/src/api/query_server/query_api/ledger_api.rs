@@ -5,10 +5,9 @@
 use {
     super::server::QueryServer,
     actix_web::{error, web},
-    config::abci::global_cfg::CFG,
     finutils::api::{
-        DelegationInfo, DelegatorInfo, DelegatorList, NetworkRoute, Validator,
-        ValidatorDetail, ValidatorList,
+        DelegationInfo, DelegatorInfo, DelegatorList, NetworkRoute, ProposalDetail,
+        UnbondingEntry, Validator, ValidatorDetail, ValidatorList,
     },
     globutils::HashOf,
     ledger::{
@@ -17,7 +16,9 @@ use {
             TxoSID, UnAuthenticatedUtxo, Utxo,
         },
         staking::{
-            DelegationRwdDetail, DelegationState, Staking, TendermintAddr,
+            params::StakingParams,
+            proposal::{Proposal, ProposalId, ProposalStatus},
+            BlockHeight, DelegationRwdDetail, DelegationState, Staking, TendermintAddr,
             TendermintAddrRef,
         },
     },
@@ -253,12 +254,39 @@ pub async fn query_validators(
             })
             .collect();
         return Ok(web::Json(ValidatorList::new(
+            staking.params().max_power_percent_per_validator,
             staking.cur_height() as u64,
             validators_list,
         )));
     };
 
-    Ok(web::Json(ValidatorList::new(0, vec![])))
+    Ok(web::Json(ValidatorList::new(
+        staking.params().max_power_percent_per_validator,
+        0,
+        vec![],
+    )))
+}
+
+/// The staking parameters in use, and the versions waiting to take effect.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StakingParamsInfo {
+    cur_height: BlockHeight,
+    current: StakingParams,
+    pending: BTreeMap<BlockHeight, StakingParams>,
+}
+
+/// Query the current staking parameters and the scheduled updates.
+pub async fn query_staking_params(
+    data: web::Data<Arc<RwLock<QueryServer>>>,
+) -> actix_web::Result<web::Json<StakingParamsInfo>> {
+    let qs = data.read();
+    let staking = qs.ledger_cloned.get_staking();
+
+    Ok(web::Json(StakingParamsInfo {
+        cur_height: staking.cur_height(),
+        current: staking.params().clone(),
+        pending: staking.params_get_pending(),
+    }))
 }
 
 #[allow(missing_docs)]
@@ -577,6 +605,15 @@ pub async fn query_validator_detail(
                 validator_realtime_apy,
                 kind: v.kind(),
                 delegator_cnt: v.delegators.len() as u64,
+                missed_block_cnt: v.missed_blocks.count(),
+                jailed_until: v.jailed_until,
+                tombstoned_at: v.tombstoned_at,
+                commission_policy: v.commission_policy,
+                pending_commission_rate: v.pending_commission_rate,
+                operators: v.operators.clone(),
+                reward_receiver: v_self_delegation
+                    .reward_receiver
+                    .map(|pk| globutils::wallet::public_key_to_base64(&pk)),
             };
             return Ok(web::Json(resp));
         }
@@ -636,7 +673,7 @@ pub async fn query_delegation_info(
                 DelegationState::Bond => {
                     if staking.cur_height()
                         > d.end_height()
-                            .saturating_sub(CFG.checkpoint.unbond_block_cnt)
+                            .saturating_sub(staking.params().unbond_block_cnt)
                     {
                         mem::swap(&mut bond_amount, &mut unbond_amount);
                     }
@@ -672,10 +709,71 @@ pub async fn query_delegation_info(
     resp.end_height = end_height;
     resp.delegation_rwd_cnt = delegation_rwd_cnt;
     resp.proposer_rwd_cnt = proposer_rwd_cnt;
+    resp.auto_compound = staking.delegation_get(&pk).and_then(|d| d.auto_compound);
+    resp.unbonding_entries = staking
+        .delegation_get_unbonding_entries(&pk)
+        .into_iter()
+        .map(|(id, tmp)| UnbondingEntry {
+            id: globutils::wallet::public_key_to_base64(&id),
+            validator: tmp
+                .delegations
+                .keys()
+                .next()
+                .and_then(|v| staking.validator_app_pk_to_td_addr(v).ok())
+                .unwrap_or_default(),
+            amount: tmp.amount(),
+            release_height: tmp.end_height(),
+        })
+        .collect();
 
     Ok(web::Json(resp))
 }
 
+/// Query all the governance proposals, the votes are omitted.
+pub async fn query_proposal_list(
+    data: web::Data<Arc<RwLock<QueryServer>>>,
+) -> actix_web::Result<web::Json<Vec<Proposal>>> {
+    let qs = data.read();
+    let staking = qs.ledger_cloned.get_staking();
+
+    let proposals = staking
+        .proposal_get_all()
+        .values()
+        .map(|p| {
+            let mut p = p.clone();
+            p.votes.clear();
+            p
+        })
+        .collect();
+
+    Ok(web::Json(proposals))
+}
+
+/// Query a governance proposal according to its id.
+pub async fn query_proposal(
+    data: web::Data<Arc<RwLock<QueryServer>>>,
+    id: web::Path<ProposalId>,
+) -> actix_web::Result<web::Json<ProposalDetail>> {
+    let qs = data.read();
+    let staking = qs.ledger_cloned.get_staking();
+
+    let proposal = staking
+        .proposal_get(*id)
+        .cloned()
+        .ok_or_else(|| error::ErrorNotFound("proposal not found"))?;
+    let cur_tally = alt!(
+        ProposalStatus::Voting == proposal.status,
+        Some(staking.proposal_tally(&proposal)),
+        None
+    );
+
+    Ok(web::Json(ProposalDetail {
+        proposal,
+        cur_height: staking.cur_height(),
+        cur_tally,
+    }))
+}
+
 /// query utxos according `public_key`
 pub async fn query_owned_utxos(
     data: web::Data<Arc<RwLock<QueryServer>>>,
@@ -702,6 +800,9 @@ pub enum ApiRoutes {
     GlobalStateVersion,
     OwnedUtxos,
     ValidatorList,
+    StakingParams,
+    ProposalList,
+    Proposal,
     DelegationInfo,
     DelegatorList,
     ValidatorDetail,
@@ -721,6 +822,9 @@ impl NetworkRoute for ApiRoutes {
             ApiRoutes::GlobalStateVersion => "global_state_version",
             ApiRoutes::OwnedUtxos => "owned_utxos",
             ApiRoutes::ValidatorList => "validator_list",
+            ApiRoutes::StakingParams => "staking_params",
+            ApiRoutes::ProposalList => "proposal_list",
+            ApiRoutes::Proposal => "proposal",
             ApiRoutes::DelegationInfo => "delegation_info",
             ApiRoutes::DelegatorList => "delegator_list",
             ApiRoutes::ValidatorDetail => "validator_detail",
@@ -27,7 +27,7 @@ use {
     parking_lot::RwLock,
     ruc::*,
     serde::{Deserialize, Serialize},
-    server::QueryServer,
+    server::{HistoryFilter, HistoryPage, QueryServer, TxnRecord},
     std::{
         collections::{BTreeMap, HashSet},
         sync::Arc,
@@ -118,6 +118,10 @@ pub enum QueryServerRoutes {
     GetIssuedRecordsByCode,
     GetRelatedTxns,
     GetRelatedXfrs,
+    GetOwnedUtxosPage,
+    GetRelatedTxnsPage,
+    GetRelatedXfrsPage,
+    GetClaimTxnsPage,
     GetAuthencatedTxnIDHash,
     GetTransactionHash,
     GetTransactionSid,
@@ -131,6 +135,10 @@ impl NetworkRoute for QueryServerRoutes {
             QueryServerRoutes::GetRelatedTxns => "get_related_txns",
             QueryServerRoutes::GetRelatedXfrs => "get_related_xfrs",
             QueryServerRoutes::GetOwnedUtxos => "get_owned_utxos",
+            QueryServerRoutes::GetOwnedUtxosPage => "get_owned_utxos_page",
+            QueryServerRoutes::GetRelatedTxnsPage => "get_related_txns_page",
+            QueryServerRoutes::GetRelatedXfrsPage => "get_related_xfrs_page",
+            QueryServerRoutes::GetClaimTxnsPage => "get_claim_txns_page",
             QueryServerRoutes::GetOwnerMemo => "get_owner_memo",
             QueryServerRoutes::GetOwnerMemoBatch => "get_owner_memo_batch",
             QueryServerRoutes::GetCreatedAssets => "get_created_assets",
@@ -412,6 +420,140 @@ pub async fn get_related_xfrs(
     }
 }
 
+/// Default number of records in a page of the paginated history
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Max number of records in a page of the paginated history
+pub const MAX_PAGE_SIZE: usize = 1000;
+
+/// Query parameters of the paginated history,
+/// records are ordered by `TxnSID`(or `TxoSID` for utxos), ascending by default.
+#[derive(Debug, Deserialize)]
+pub struct HistoryQueryParams {
+    /// `next_cursor` of the previous page, the first page if missing
+    cursor: Option<u64>,
+    limit: Option<usize>,
+    order: Option<OrderOption>,
+    from_height: Option<u64>,
+    to_height: Option<u64>,
+    /// asset code in base64
+    asset: Option<String>,
+    /// operation kind, such as `TransferAsset`
+    op: Option<String>,
+}
+
+impl HistoryQueryParams {
+    #[inline(always)]
+    fn limit(&self) -> usize {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+
+    #[inline(always)]
+    fn order_desc(&self) -> bool {
+        Some(&OrderOption::Desc) == self.order.as_ref()
+    }
+
+    fn asset(&self) -> actix_web::Result<Option<AssetTypeCode>> {
+        self.asset
+            .as_ref()
+            .map(|a| {
+                AssetTypeCode::new_from_base64(a)
+                    .c(d!())
+                    .map_err(|e| error::ErrorBadRequest(e.to_string()))
+            })
+            .transpose()
+    }
+
+    fn filter(&self) -> actix_web::Result<HistoryFilter> {
+        Ok(HistoryFilter {
+            from_height: self.from_height,
+            to_height: self.to_height,
+            asset: self.asset()?,
+            op_kind: self.op.clone(),
+        })
+    }
+}
+
+/// Paginated version of `get_owned_utxos`, can be filtered by the asset code
+pub async fn get_owned_utxos_page(
+    data: web::Data<Arc<RwLock<QueryServer>>>,
+    owner: web::Path<String>,
+    web::Query(params): web::Query<HistoryQueryParams>,
+) -> actix_web::Result<web::Json<HistoryPage<TxoSID>>> {
+    let key = wallet::public_key_from_base64(owner.as_str())
+        .c(d!())
+        .map_err(|e| error::ErrorBadRequest(e.to_string()))?;
+    let server = data.read();
+    server
+        .get_owned_utxos_page(
+            &key,
+            params.cursor,
+            params.limit(),
+            params.order_desc(),
+            params.asset()?,
+        )
+        .map(web::Json)
+        .map_err(|e| error::ErrorInternalServerError(e.to_string()))
+}
+
+/// Paginated version of `get_related_txns`
+pub async fn get_related_txns_page(
+    data: web::Data<Arc<RwLock<QueryServer>>>,
+    info: web::Path<String>,
+    web::Query(params): web::Query<HistoryQueryParams>,
+) -> actix_web::Result<web::Json<HistoryPage<TxnRecord>>> {
+    let key = wallet::public_key_from_base64(info.as_str())
+        .c(d!())
+        .map_err(|e| error::ErrorBadRequest(e.to_string()))?;
+    let server = data.read();
+    Ok(web::Json(server.get_related_transactions_page(
+        &XfrAddress { key },
+        params.cursor,
+        params.limit(),
+        params.order_desc(),
+        &params.filter()?,
+    )))
+}
+
+/// Paginated version of `get_related_xfrs`
+pub async fn get_related_xfrs_page(
+    data: web::Data<Arc<RwLock<QueryServer>>>,
+    info: web::Path<String>,
+    web::Query(params): web::Query<HistoryQueryParams>,
+) -> actix_web::Result<web::Json<HistoryPage<TxnRecord>>> {
+    let token_code = AssetTypeCode::new_from_base64(&*info)
+        .map_err(|_| error::ErrorBadRequest("Invalid asset definition encoding."))?;
+    let server = data.read();
+    Ok(web::Json(server.get_related_transfers_page(
+        &token_code,
+        params.cursor,
+        params.limit(),
+        params.order_desc(),
+        &params.filter()?,
+    )))
+}
+
+/// Paginated version of `claim_history`
+pub async fn get_claim_txns_page(
+    data: web::Data<Arc<RwLock<QueryServer>>>,
+    info: web::Path<String>,
+    web::Query(params): web::Query<HistoryQueryParams>,
+) -> actix_web::Result<web::Json<HistoryPage<TxnRecord>>> {
+    let key = wallet::public_key_from_base64(info.as_str())
+        .c(d!())
+        .map_err(|e| error::ErrorBadRequest(e.to_string()))?;
+    let server = data.read();
+    Ok(web::Json(server.get_claim_transactions_page(
+        &XfrAddress { key },
+        params.cursor,
+        params.limit(),
+        params.order_desc(),
+        &params.filter()?,
+    )))
+}
+
 #[allow(missing_docs)]
 #[allow(clippy::unnecessary_wraps)]
 
@@ -530,6 +672,23 @@ impl QueryApi {
                     &QueryServerRoutes::GetRelatedXfrs.with_arg_template("asset_token"),
                     web::get().to(get_related_xfrs),
                 )
+                .route(
+                    &QueryServerRoutes::GetOwnedUtxosPage.with_arg_template("address"),
+                    web::get().to(get_owned_utxos_page),
+                )
+                .route(
+                    &QueryServerRoutes::GetRelatedTxnsPage.with_arg_template("address"),
+                    web::get().to(get_related_txns_page),
+                )
+                .route(
+                    &QueryServerRoutes::GetRelatedXfrsPage
+                        .with_arg_template("asset_token"),
+                    web::get().to(get_related_xfrs_page),
+                )
+                .route(
+                    &QueryServerRoutes::GetClaimTxnsPage.with_arg_template("address"),
+                    web::get().to(get_claim_txns_page),
+                )
                 .route(
                     &QueryServerRoutes::GetCreatedAssets.with_arg_template("address"),
                     web::get().to(get_created_assets),
@@ -604,6 +763,18 @@ impl QueryApi {
                     &ApiRoutes::ValidatorList.route(),
                     web::get().to(query_validators),
                 )
+                .route(
+                    &ApiRoutes::StakingParams.route(),
+                    web::get().to(query_staking_params),
+                )
+                .route(
+                    &ApiRoutes::ProposalList.route(),
+                    web::get().to(query_proposal_list),
+                )
+                .route(
+                    &ApiRoutes::Proposal.with_arg_template("id"),
+                    web::get().to(query_proposal),
+                )
                 .route(
                     &ApiRoutes::DelegationInfo.with_arg_template("XfrPublicKey"),
                     web::get().to(query_delegation_info),
@@ -3,6 +3,7 @@
 //!
 
 use {
+    fbnc::Mapxnk,
     lazy_static::lazy_static,
     ledger::{
         data_model::{
@@ -10,12 +11,16 @@ use {
             TxnIDHash, TxnSID, TxoSID, XfrAddress,
         },
         staking::{ops::mint_fra::MintEntry, BlockHeight},
-        store::LedgerState,
+        store::{
+            api_cache::{self, TxnTags},
+            LedgerState,
+        },
     },
     parking_lot::{Condvar, Mutex, RwLock},
     ruc::*,
+    serde::Serialize,
     std::{collections::HashSet, sync::Arc},
-    zei::xfr::structs::OwnerMemo,
+    zei::xfr::{sig::XfrPublicKey, structs::OwnerMemo},
 };
 
 lazy_static! {
@@ -25,6 +30,48 @@ lazy_static! {
         Arc::new((Mutex::new(false), Condvar::new()));
 }
 
+/// Filters of the paginated history
+#[derive(Debug, Default)]
+pub struct HistoryFilter {
+    /// the lowest block height, inclusive
+    pub from_height: Option<BlockHeight>,
+    /// the highest block height, inclusive
+    pub to_height: Option<BlockHeight>,
+    /// transactions involving this nonconfidential asset
+    pub asset: Option<AssetTypeCode>,
+    /// transactions containing this kind of operation, such as `TransferAsset`
+    pub op_kind: Option<String>,
+}
+
+/// A page of the paginated history,
+/// pass `next_cursor` back to get the next page,
+/// it is `None` if there are no more records.
+#[derive(Debug, Serialize)]
+pub struct HistoryPage<T> {
+    #[allow(missing_docs)]
+    pub items: Vec<T>,
+    #[allow(missing_docs)]
+    pub next_cursor: Option<u64>,
+}
+
+impl<T> Default for HistoryPage<T> {
+    fn default() -> Self {
+        HistoryPage {
+            items: vec![],
+            next_cursor: None,
+        }
+    }
+}
+
+/// A transaction in the paginated history
+#[derive(Debug, Serialize)]
+pub struct TxnRecord {
+    #[allow(missing_docs)]
+    pub txn_sid: TxnSID,
+    #[allow(missing_docs)]
+    pub height: BlockHeight,
+}
+
 /// A data container for API
 pub struct QueryServer {
     pub(crate) ledger: Arc<RwLock<LedgerState>>,
@@ -239,6 +286,194 @@ impl QueryServer {
             .map(|d| d.iter().map(|(k, _)| k).collect())
     }
 
+    /// Paginated version of `get_related_transactions`, ordered by `TxnSID`.
+    #[inline(always)]
+    pub fn get_related_transactions_page(
+        &self,
+        address: &XfrAddress,
+        cursor: Option<u64>,
+        limit: usize,
+        order_desc: bool,
+        filter: &HistoryFilter,
+    ) -> HistoryPage<TxnRecord> {
+        let index = self
+            .ledger_cloned
+            .api_cache
+            .as_ref()
+            .unwrap()
+            .related_transactions
+            .get(&address);
+        self.page_transactions(index, cursor, limit, order_desc, filter)
+    }
+
+    /// Paginated version of `get_related_transfers`, ordered by `TxnSID`.
+    #[inline(always)]
+    pub fn get_related_transfers_page(
+        &self,
+        code: &AssetTypeCode,
+        cursor: Option<u64>,
+        limit: usize,
+        order_desc: bool,
+        filter: &HistoryFilter,
+    ) -> HistoryPage<TxnRecord> {
+        let index = self
+            .ledger_cloned
+            .api_cache
+            .as_ref()
+            .unwrap()
+            .related_transfers
+            .get(&code);
+        self.page_transactions(index, cursor, limit, order_desc, filter)
+    }
+
+    /// Paginated version of `get_claim_transactions`, ordered by `TxnSID`.
+    #[inline(always)]
+    pub fn get_claim_transactions_page(
+        &self,
+        address: &XfrAddress,
+        cursor: Option<u64>,
+        limit: usize,
+        order_desc: bool,
+        filter: &HistoryFilter,
+    ) -> HistoryPage<TxnRecord> {
+        let index = self
+            .ledger_cloned
+            .api_cache
+            .as_ref()
+            .unwrap()
+            .claim_hist_txns
+            .get(&address);
+        self.page_transactions(index, cursor, limit, order_desc, filter)
+    }
+
+    /// Returns a page of the unspent utxos owned by a given public key, ordered by `TxoSID`.
+    pub fn get_owned_utxos_page(
+        &self,
+        owner: &XfrPublicKey,
+        cursor: Option<u64>,
+        limit: usize,
+        order_desc: bool,
+        asset: Option<AssetTypeCode>,
+    ) -> Result<HistoryPage<TxoSID>> {
+        let mut page = HistoryPage::default();
+
+        let index = match self
+            .ledger_cloned
+            .api_cache
+            .as_ref()
+            .c(d!("api cache is not enabled"))?
+            .owned_utxos
+            .get(&XfrAddress { key: *owner })
+        {
+            Some(index) => index,
+            None => return Ok(page),
+        };
+
+        // only the utxos of the page are fetched
+        let (items, next_cursor) = api_cache::page_index(
+            &index,
+            (0, u64::MAX),
+            cursor,
+            limit,
+            order_desc,
+            |sid| {
+                self.ledger_cloned
+                    .get_utxo_light(TxoSID(sid))
+                    .map(|utxo| match asset {
+                        Some(code) => {
+                            utxo.utxo.0.record.asset_type.get_asset_type()
+                                == Some(code.val)
+                        }
+                        None => true,
+                    })
+                    .unwrap_or(false)
+            },
+        );
+
+        page.items = items.into_iter().map(TxoSID).collect();
+        page.next_cursor = next_cursor;
+
+        Ok(page)
+    }
+
+    /// Returns the height of the block containing a given transaction.
+    #[inline(always)]
+    pub fn get_transaction_height(&self, txn_sid: TxnSID) -> Option<BlockHeight> {
+        self.ledger_cloned.get_transaction_height(txn_sid)
+    }
+
+    /// Returns the operation kinds and assets of a given transaction,
+    /// those not in the cache are collected from the ledger.
+    pub fn get_transaction_tags(&self, txn_sid: TxnSID) -> Option<TxnTags> {
+        self.ledger_cloned
+            .api_cache
+            .as_ref()
+            .unwrap()
+            .txn_tags
+            .get(&txn_sid)
+            .or_else(|| {
+                self.ledger_cloned
+                    .get_transaction_light(txn_sid)
+                    .ok()
+                    .map(|tx| api_cache::get_txn_tags(&tx.txn))
+            })
+    }
+
+    // Walk through an ordered index of transactions from the cursor,
+    // the cursor itself is excluded.
+    fn page_transactions(
+        &self,
+        index: Option<Mapxnk<TxnSID, bool>>,
+        cursor: Option<u64>,
+        limit: usize,
+        order_desc: bool,
+        filter: &HistoryFilter,
+    ) -> HistoryPage<TxnRecord> {
+        let mut page = HistoryPage::default();
+
+        let (index, (lo, hi)) = match (
+            index,
+            self.ledger_cloned
+                .get_txn_sid_range(filter.from_height, filter.to_height),
+        ) {
+            (Some(index), Some(range)) => (index, range),
+            _ => return page,
+        };
+
+        let (items, next_cursor) = api_cache::page_index(
+            &index,
+            (lo as u64, hi as u64),
+            cursor,
+            limit,
+            order_desc,
+            |sid| self.txn_matches(TxnSID(sid as usize), filter),
+        );
+
+        page.items = items
+            .into_iter()
+            .map(|sid| {
+                let txn_sid = TxnSID(sid as usize);
+                TxnRecord {
+                    txn_sid,
+                    height: self.get_transaction_height(txn_sid).unwrap_or_default(),
+                }
+            })
+            .collect();
+        page.next_cursor = next_cursor;
+
+        page
+    }
+
+    fn txn_matches(&self, txn_sid: TxnSID, filter: &HistoryFilter) -> bool {
+        if filter.asset.is_none() && filter.op_kind.is_none() {
+            return true;
+        }
+
+        self.get_transaction_tags(txn_sid)
+            .map(|tags| tags.matches(filter.asset.as_ref(), filter.op_kind.as_deref()))
+            .unwrap_or(false)
+    }
+
     /// Returns the owner of a given txo_sid.
     #[inline(always)]
     pub fn get_address_of_sid(&self, txo_sid: TxoSID) -> Option<XfrAddress> {
@@ -65,6 +65,7 @@ fn node_command() -> Result<()> {
     for (condition, action) in [
         (CFG.enable_query_service, "--enable-query-service"),
         (CFG.enable_eth_api_service, "--enable-eth-api-service"),
+        (CFG.enable_eth_debug_api, "--enable-eth-debug-api"),
         (CFG.disable_eth_empty_blocks, "--disable-eth-empty-blocks"),
         (CFG.enable_snapshot, "--enable-snapshot"),
         (CFG.snapshot_list, "--snapshot-list"),
@@ -77,6 +78,9 @@ fn node_command() -> Result<()> {
 
     convert_arg!(tendermint_node_self_addr);
     convert_arg!(tendermint_node_key_config_path);
+    convert_arg!(state_sync_itv);
+    convert_arg!(state_sync_cap);
+    convert_arg!(pruning);
     convert_arg!(snapshot_target);
     convert_arg!(snapshot_itv);
     convert_arg!(snapshot_cap);
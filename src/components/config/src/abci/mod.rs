@@ -27,6 +27,50 @@ pub struct CheckPointConfig {
     pub ff_addr_extra_fix_height: u64,
     pub nonconfidential_balance_fix_height: u64,
     pub unbond_block_cnt: u64,
+    // EVM fees are paid to the block proposer instead of being burnt
+    #[serde(default = "i64::max_value")]
+    pub evm_fee_to_proposer_height: i64,
+    // EIP-2930 and EIP-1559 typed transactions are accepted
+    #[serde(default = "i64::max_value")]
+    pub enable_eip1559_height: i64,
+    // The bn128 and blake2f precompiles take the addresses 0x06 to 0x09,
+    // BREAKING: ECRecoverPublicKey, Sha3FIPS256 and Sha3FIPS512 move from
+    // 0x06, 0x07 and 0x08 to 0x402, 0x400 and 0x401 at this height
+    #[serde(default = "i64::max_value")]
+    pub evm_istanbul_precompiles_height: i64,
+    // The custom assets get a FRC20 precompile each
+    #[serde(default = "i64::max_value")]
+    pub evm_custom_assets_height: i64,
+    // Delegators can move their delegations between validators
+    #[serde(default = "i64::max_value")]
+    pub enable_redelegation_height: i64,
+    // The staking parameters can be updated by co-signed transactions
+    #[serde(default = "i64::max_value")]
+    pub enable_staking_params_height: i64,
+    // Stake-weighted governance proposals and votes
+    #[serde(default = "i64::max_value")]
+    pub enable_proposal_height: i64,
+    // Jail the validators who missed too many blocks
+    #[serde(default = "i64::max_value")]
+    pub enable_validator_jail_height: i64,
+    // Commission rate limits and delayed commission rate changes
+    #[serde(default = "i64::max_value")]
+    pub enable_commission_policy_height: i64,
+    // Deduplicate evidences and tombstone the double-signers
+    #[serde(default = "i64::max_value")]
+    pub enable_evidence_tombstone_height: i64,
+    // Delegators can compound their rewards automatically
+    #[serde(default = "i64::max_value")]
+    pub enable_auto_compound_height: i64,
+    // Delegators can re-bond their pending partial undelegations
+    #[serde(default = "i64::max_value")]
+    pub enable_cancel_unbonding_height: i64,
+    // Validators can be controlled by the threshold signatures of their operators
+    #[serde(default = "i64::max_value")]
+    pub enable_validator_multisig_height: i64,
+    // Validators can pay their rewards and self-delegation to a withdrawal address
+    #[serde(default = "i64::max_value")]
+    pub enable_reward_receiver_height: i64,
 }
 
 impl CheckPointConfig {
@@ -52,6 +96,20 @@ impl CheckPointConfig {
                                 ff_addr_extra_fix_height: 0,
                                 nonconfidential_balance_fix_height: 0,
                                 unbond_block_cnt: 3600 * 24 * 21 / 16,
+                                evm_fee_to_proposer_height: 0,
+                                enable_eip1559_height: 0,
+                                evm_istanbul_precompiles_height: 0,
+                                evm_custom_assets_height: 0,
+                                enable_redelegation_height: 0,
+                                enable_staking_params_height: 0,
+                                enable_proposal_height: 0,
+                                enable_validator_jail_height: 0,
+                                enable_commission_policy_height: 0,
+                                enable_evidence_tombstone_height: 0,
+                                enable_auto_compound_height: 0,
+                                enable_cancel_unbonding_height: 0,
+                                enable_validator_multisig_height: 0,
+                                enable_reward_receiver_height: 0,
                             };
                             #[cfg(not(feature = "debug_env"))]
                             let config = CheckPointConfig {
@@ -67,6 +125,20 @@ impl CheckPointConfig {
                                 ff_addr_extra_fix_height: 1200000,
                                 nonconfidential_balance_fix_height: 1210000,
                                 unbond_block_cnt: 3600 * 24 * 21 / 16,
+                                evm_fee_to_proposer_height: i64::MAX,
+                                enable_eip1559_height: i64::MAX,
+                                evm_istanbul_precompiles_height: i64::MAX,
+                                evm_custom_assets_height: i64::MAX,
+                                enable_redelegation_height: i64::MAX,
+                                enable_staking_params_height: i64::MAX,
+                                enable_proposal_height: i64::MAX,
+                                enable_validator_jail_height: i64::MAX,
+                                enable_commission_policy_height: i64::MAX,
+                                enable_evidence_tombstone_height: i64::MAX,
+                                enable_auto_compound_height: i64::MAX,
+                                enable_cancel_unbonding_height: i64::MAX,
+                                enable_validator_multisig_height: i64::MAX,
+                                enable_reward_receiver_height: i64::MAX,
                             };
                             let content = toml::to_string(&config).unwrap();
                             file.write_all(content.as_bytes()).unwrap();
@@ -208,11 +280,15 @@ pub mod global_cfg {
         pub enable_query_service: bool,
         pub disable_eth_empty_blocks: bool,
         pub enable_eth_api_service: bool,
+        pub enable_eth_debug_api: bool,
         pub evm_http_port: u16,
         pub evm_ws_port: u16,
         pub tendermint_node_self_addr: Option<String>,
         pub tendermint_node_key_config_path: Option<String>,
         pub ledger_dir: String,
+        pub state_sync_itv: u64,
+        pub state_sync_cap: usize,
+        pub pruning: Option<usize>,
         #[cfg(target_os = "linux")]
         pub btmcfg: BtmCfg,
         pub checkpoint: CheckPointConfig,
@@ -241,12 +317,16 @@ pub mod global_cfg {
             .arg_from_usage("-q, --enable-query-service")
             .arg_from_usage("--disable-eth-empty-blocks 'not generate empty ethereum blocks when no evm transaction'")
             .arg_from_usage("--enable-eth-api-service")
+            .arg_from_usage("--enable-eth-debug-api 'serve the debug namespace of the web3 service, it replays historical blocks'")
             .arg_from_usage("--evm-http-port=[EVM Web3 Http Port]")
             .arg_from_usage("--evm-ws-port=[EVM Web3 WS Port]")
             .arg_from_usage("--tendermint-node-self-addr=[Address] 'the address of your tendermint node, in upper-hex format'")
             .arg_from_usage("--tendermint-node-key-config-path=[Path] 'such as: ${HOME}/.tendermint/config/priv_validator_key.json'")
             .arg_from_usage("-d, --ledger-dir=[Path]")
             .arg_from_usage("--checkpoint-file=[Path]")
+            .arg_from_usage("--state-sync-itv=[Interval] 'interval between adjacent state sync snapshots, default to 0, aka disabled'")
+            .arg_from_usage("--state-sync-cap=[Capacity] 'the maximum number of state sync snapshots that will be stored, default to 2'")
+            .arg_from_usage("--pruning=[KeepBlocks] 'discard spent utxos and transactions of the blocks older than the latest <KeepBlocks> ones'")
             .arg_from_usage("--enable-snapshot 'global switch for enabling snapshot functions'")
             .arg_from_usage("--snapshot-list 'list all available snapshots in the form of block height'")
             .arg_from_usage("--snapshot-target=[TargetPath] 'a data volume containing both ledger data and tendermint data'")
@@ -325,6 +405,8 @@ pub mod global_cfg {
             || env::var("DISABLE_ETH_EMPTY_BLOCKS").is_ok();
         let eas = m.is_present("enable-eth-api-service")
             || env::var("ENABLE_ETH_API_SERVICE").is_ok();
+        let eda = m.is_present("enable-eth-debug-api")
+            || env::var("ENABLE_ETH_DEBUG_API").is_ok();
         let ehp = m
             .value_of("evm-http-port")
             .map(|v| v.to_owned())
@@ -339,6 +421,29 @@ pub mod global_cfg {
             .unwrap_or_else(|| "8546".to_owned())
             .parse::<u16>()
             .c(d!())?;
+        let ssi = m
+            .value_of("state-sync-itv")
+            .map(|v| v.to_owned())
+            .or_else(|| env::var("STATE_SYNC_ITV").ok())
+            .unwrap_or_else(|| "0".to_owned())
+            .parse::<u64>()
+            .c(d!())?;
+        let ssc = m
+            .value_of("state-sync-cap")
+            .map(|v| v.to_owned())
+            .or_else(|| env::var("STATE_SYNC_CAP").ok())
+            .unwrap_or_else(|| "2".to_owned())
+            .parse::<usize>()
+            .c(d!())?;
+        let pruning = m
+            .value_of("pruning")
+            .map(|v| v.to_owned())
+            .or_else(|| env::var("PRUNING").ok())
+            .map(|v| v.parse::<usize>().c(d!()))
+            .transpose()?;
+        if pruning.is_some() && eqs {
+            return Err(eg!("pruning mode can not serve historical queries"));
+        }
         let checkpoint_path = m
             .value_of("checkpoint-file")
             .map(|v| v.to_owned())
@@ -354,11 +459,15 @@ pub mod global_cfg {
             enable_query_service: eqs,
             disable_eth_empty_blocks: eeb,
             enable_eth_api_service: eas,
+            enable_eth_debug_api: eda,
             evm_http_port: ehp,
             evm_ws_port: ewp,
             tendermint_node_self_addr: tnsa,
             tendermint_node_key_config_path: tnkcp,
             ledger_dir: ld,
+            state_sync_itv: ssi,
+            state_sync_cap: ssc,
+            pruning,
             #[cfg(target_os = "linux")]
             btmcfg: parse_btmcfg(&m).c(d!())?,
             checkpoint: CheckPointConfig::from_file(&checkpoint_path).unwrap(),
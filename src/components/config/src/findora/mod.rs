@@ -146,6 +146,7 @@ pub mod config {
         pub ledger_service_port: u16,
         pub enable_query_service: bool,
         pub enable_eth_api_service: bool,
+        pub enable_eth_debug_api: bool,
         pub disable_eth_empty_blocks: bool,
         pub no_fast_sync: bool,
         pub tendermint_node_self_addr: Option<String>,
@@ -158,6 +159,9 @@ pub mod config {
         pub enable_snapshot: bool,
         pub snapshot_list: bool,
         pub checkpoint_file: Option<String>,
+        pub state_sync_itv: Option<String>,
+        pub state_sync_cap: Option<String>,
+        pub pruning: Option<String>,
         pub snapshot_target: Option<String>,
         pub snapshot_itv: Option<String>,
         pub snapshot_cap: Option<String>,
@@ -179,6 +183,7 @@ pub mod config {
                 .arg_from_usage("--ledger-service-port=[Ledger Service Port]")
                 .arg_from_usage("-q, --enable-query-service")
                 .arg_from_usage("--enable-eth-api-service")
+                .arg_from_usage("--enable-eth-debug-api 'serve the debug namespace of the web3 service, it replays historical blocks'")
                 .arg_from_usage("--disable-eth-empty-blocks")
                 .arg_from_usage("-N, --no-fast-sync")
                 .arg_from_usage("--tendermint-node-self-addr=[Address] 'the address of your tendermint node, in upper-hex format'")
@@ -187,6 +192,9 @@ pub mod config {
                 .arg_from_usage(
                     "-b, --base-dir=[DIR] 'The root directory for tendermint config, aka $TENDERMINT_HOME'",
                 )
+                .arg_from_usage("--state-sync-itv=[Interval] 'interval between adjacent state sync snapshots, default to 0, aka disabled'")
+                .arg_from_usage("--state-sync-cap=[Capacity] 'the maximum number of state sync snapshots that will be stored, default to 2'")
+                .arg_from_usage("--pruning=[KeepBlocks] 'discard spent utxos and transactions of the blocks older than the latest <KeepBlocks> ones'")
                 .arg_from_usage("--enable-snapshot 'global switch for enabling snapshot functions'")
                 .arg_from_usage("--snapshot-list 'list all available snapshots in the form of block height'")
                 .arg_from_usage("--snapshot-target=[TargetPath] 'a data volume containing both ledger data and tendermint data'")
@@ -284,6 +292,8 @@ pub mod config {
             || env::var("ENABLE_QUERY_SERVICE").is_ok();
         let eeas = m.is_present("enable-eth-api-service")
             || env::var("ENABLE_ETH_API_SERVICE").is_ok();
+        let eeda = m.is_present("enable-eth-debug-api")
+            || env::var("ENABLE_ETH_DEBUG_API").is_ok();
         let deeb = m.is_present("disable-eth-empty-blocks")
             || env::var("DISABLE_ETH_EMPTY_BLOCKS").is_ok();
         let nfs = m.is_present("no-fast-sync") || env::var("NO_FAST_SYNC").is_ok();
@@ -328,6 +338,7 @@ pub mod config {
             ledger_service_port: lsp,
             enable_query_service: eqs,
             enable_eth_api_service: eeas,
+            enable_eth_debug_api: eeda,
             disable_eth_empty_blocks: deeb,
             no_fast_sync: nfs,
             tendermint_node_self_addr: tnsa,
@@ -338,6 +349,9 @@ pub mod config {
             tendermint_home: tdir,
             checkpoint_file: cpf,
             init_mode,
+            state_sync_itv: m.value_of("state-sync-itv").map(|v| v.to_owned()),
+            state_sync_cap: m.value_of("state-sync-cap").map(|v| v.to_owned()),
+            pruning: m.value_of("pruning").map(|v| v.to_owned()),
             enable_snapshot: m.is_present("enable-snapshot")
                 || env::var("ENABLE_BTM_SNAPSHOT").is_ok(),
             snapshot_list: m.is_present("snapshot-list"),
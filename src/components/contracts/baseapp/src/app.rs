@@ -1,12 +1,17 @@
 use crate::extensions::SignedExtra;
+use crate::modules::ModuleManager;
 use abci::*;
 use fp_core::context::RunTxMode;
 use fp_evm::BlockId;
-use fp_types::assemble::convert_unchecked_transaction;
+use fp_types::{
+    actions::{ethereum::Action as EtherAction, Action},
+    assemble::{convert_unchecked_transaction, UncheckedTransaction},
+};
 use fp_utils::tx::EvmRawTxWrapper;
 use log::{debug, error, info};
-use primitive_types::U256;
+use primitive_types::{H256, U256};
 use ruc::*;
+use sha3::{Digest, Keccak256};
 
 impl abci::Application for crate::BaseApp {
     /// info implements the ABCI interface.
@@ -74,9 +79,10 @@ impl abci::Application for crate::BaseApp {
         };
 
         if let Ok(tx) = convert_unchecked_transaction::<SignedExtra>(raw_tx) {
+            let tx_hash = ethereum_tx_hash(&tx);
             let check_fn = |mode: RunTxMode| {
                 let ctx = self.retrieve_context(mode).clone();
-                let result = self.modules.process_tx::<SignedExtra>(ctx, tx);
+                let result = ModuleManager::process_tx::<SignedExtra>(ctx, tx);
                 match result {
                     Ok(ar) => {
                         resp.code = ar.code;
@@ -96,6 +102,10 @@ impl abci::Application for crate::BaseApp {
                 CheckTxType::New => check_fn(RunTxMode::Check),
                 CheckTxType::Recheck => check_fn(RunTxMode::ReCheck),
             }
+
+            if let Some(hash) = tx_hash {
+                self.update_pending_tx(hash, req.get_field_type(), resp.code == 0);
+            }
         } else {
             info!(target: "baseapp", "Could not unpack transaction");
         }
@@ -146,9 +156,12 @@ impl abci::Application for crate::BaseApp {
         };
 
         if let Ok(tx) = convert_unchecked_transaction::<SignedExtra>(raw_tx) {
+            if let Some(hash) = ethereum_tx_hash(&tx) {
+                self.pending_txs.write().remove(&hash);
+            }
             let ctx = self.retrieve_context(RunTxMode::Deliver).clone();
 
-            let ret = self.modules.process_tx::<SignedExtra>(ctx, tx);
+            let ret = ModuleManager::process_tx::<SignedExtra>(ctx, tx);
             match ret {
                 Ok(ar) => {
                     if ar.code != 0 {
@@ -217,6 +230,8 @@ impl abci::Application for crate::BaseApp {
         // Reset the deliver state
         Self::update_state(&mut self.deliver_state, Default::default(), vec![]);
 
+        self.pending_txs.write().prune(block_height);
+
         pnk!(self
             .event_notify
             .notify(BlockId::Number(U256::from(block_height))));
@@ -227,3 +242,35 @@ impl abci::Application for crate::BaseApp {
         res
     }
 }
+
+impl crate::BaseApp {
+    /// Track the ethereum transactions entering or leaving the mempool.
+    fn update_pending_tx(&self, hash: H256, check_type: CheckTxType, passed: bool) {
+        match (check_type, passed) {
+            (CheckTxType::New, true) => {
+                let height = self.check_state.block_header().height as u64;
+                if self.pending_txs.write().insert(hash, height) {
+                    pnk!(self.pending_tx_notify.notify(hash));
+                }
+            }
+            // Tendermint drops the transactions failing on recheck.
+            (CheckTxType::Recheck, false) => {
+                self.pending_txs.write().remove(&hash);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Hash of the ethereum transaction wrapped in `tx`, if there is one.
+pub(crate) fn ethereum_tx_hash(tx: &UncheckedTransaction<SignedExtra>) -> Option<H256> {
+    match &tx.function {
+        Action::Ethereum(EtherAction::Transact(transaction)) => Some(H256::from_slice(
+            Keccak256::digest(&rlp::encode(transaction)).as_slice(),
+        )),
+        Action::Ethereum(EtherAction::TransactTyped(transaction)) => {
+            Some(transaction.hash())
+        }
+        _ => None,
+    }
+}
@@ -9,10 +9,13 @@ mod app;
 pub mod extensions;
 mod modules;
 mod notify;
+mod pending;
 
+use crate::extensions::SignedExtra;
 use crate::modules::ModuleManager;
 use abci::Header;
-use ethereum::BlockV0 as Block;
+use config::abci::global_cfg::CFG;
+use ethereum::BlockV2 as Block;
 use fp_core::{
     account::SmartAccount,
     context::{Context, RunTxMode},
@@ -25,11 +28,20 @@ use fp_traits::{
     base::BaseProvider,
     evm::{EthereumAddressMapping, EthereumDecimalsMapping},
 };
-use fp_types::{actions::xhub::NonConfidentialOutput, actions::Action, crypto::Address};
+use fp_types::{
+    actions::xhub::NonConfidentialOutput, actions::Action,
+    assemble::convert_unchecked_transaction, crypto::Address,
+};
+use fp_utils::tx::EvmRawTxWrapper;
 use lazy_static::lazy_static;
-use ledger::data_model::Transaction as FindoraTransaction;
+use ledger::{
+    converter::{get_convert_account_asset, is_convert_account},
+    data_model::{Asset, AssetTypeCode, Transaction as FindoraTransaction},
+};
+use module_evm::precompile::{ActivatedAt, Relocated};
 use notify::*;
 use parking_lot::RwLock;
+pub use pending::PendingTxPool;
 use primitive_types::{H160, H256, U256};
 use ruc::{eg, Result};
 use std::borrow::BorrowMut;
@@ -39,6 +51,7 @@ use storage::{
     db::{FinDB, RocksDB},
     state::ChainState,
 };
+use zei::xfr::sig::XfrPublicKey;
 
 lazy_static! {
     /// An identifier that distinguishes different EVM chains.
@@ -47,10 +60,14 @@ lazy_static! {
 }
 
 const APP_NAME: &str = "findora";
-const CHAIN_STATE_PATH: &str = "state.db";
-const CHAIN_HISTORY_DATA_PATH: &str = "history.db";
+pub const CHAIN_STATE_PATH: &str = "state.db";
+pub const CHAIN_HISTORY_DATA_PATH: &str = "history.db";
 const CHAIN_STATE_MIN_VERSIONS: u64 = 4 * 60 * 24 * 90;
 
+/// Looks up the definition of a findora asset,
+/// needed to replay the conversions of custom assets.
+pub type AssetLookup = Arc<dyn Fn(&AssetTypeCode) -> Option<Asset> + Send + Sync>;
+
 pub struct BaseApp {
     /// application name from abci.Info
     pub name: String,
@@ -73,6 +90,10 @@ pub struct BaseApp {
     pub modules: ModuleManager,
     /// New Block event notify
     pub event_notify: Arc<Notifications<BlockId>>,
+    /// Ethereum transactions in the mempool
+    pub pending_txs: Arc<RwLock<PendingTxPool>>,
+    /// New pending transaction notify
+    pub pending_tx_notify: Arc<Notifications<H256>>,
 }
 
 impl module_template::Config for BaseApp {}
@@ -94,6 +115,11 @@ parameter_types! {
     pub ChainId: u64 = *EVM_CAHIN_ID;
     pub BlockGasLimit: U256 = U256::from(u32::max_value());
     pub const BlockHashCount: u32 = 256;
+    pub IstanbulPrecompilesHeight: i64 = CFG.checkpoint.evm_istanbul_precompiles_height;
+    pub CustomAssetsHeight: i64 = CFG.checkpoint.evm_custom_assets_height;
+    pub const Sha3FIPS256Id: u64 = 0x400;
+    pub const Sha3FIPS512Id: u64 = 0x401;
+    pub const ECRecoverPublicKeyId: u64 = 0x402;
 }
 
 impl module_ethereum::Config for BaseApp {
@@ -113,23 +139,46 @@ impl module_evm::Config for BaseApp {
     type BlockGasLimit = BlockGasLimit;
     type BlockHashMapping = module_ethereum::App<Self>;
     type ChainId = ChainId;
+    type CustomAsset = module_account::App<Self>;
     type DecimalsMapping = EthereumDecimalsMapping;
     type FeeCalculator = ();
+    // Precompiles added after genesis must be registered with
+    // `ActivatedAt<_, Height>`, the height being a `CheckPointConfig` fork height.
     type Precompiles = (
         evm_precompile_basic::ECRecover,
         evm_precompile_basic::Sha256,
         evm_precompile_basic::Ripemd160,
         evm_precompile_basic::Identity,
         evm_precompile_modexp::Modexp,
+        ActivatedAt<evm_precompile_bn128::Bn128Add, IstanbulPrecompilesHeight>,
+        ActivatedAt<evm_precompile_bn128::Bn128Mul, IstanbulPrecompilesHeight>,
+        ActivatedAt<evm_precompile_bn128::Bn128Pairing, IstanbulPrecompilesHeight>,
+        ActivatedAt<evm_precompile_blake2::Blake2F, IstanbulPrecompilesHeight>,
         evm_precompile_basic::ECRecoverPublicKey,
         evm_precompile_sha3fips::Sha3FIPS256,
         evm_precompile_sha3fips::Sha3FIPS512,
         evm_precompile_frc20::FRC20<Self>,
+        // moved out of the standard addresses taken by bn128, this breaks the
+        // contracts calling them at 0x06 to 0x08 since the checkpoint height
+        ActivatedAt<
+            Relocated<evm_precompile_sha3fips::Sha3FIPS256, Sha3FIPS256Id>,
+            IstanbulPrecompilesHeight,
+        >,
+        ActivatedAt<
+            Relocated<evm_precompile_sha3fips::Sha3FIPS512, Sha3FIPS512Id>,
+            IstanbulPrecompilesHeight,
+        >,
+        ActivatedAt<
+            Relocated<evm_precompile_basic::ECRecoverPublicKey, ECRecoverPublicKeyId>,
+            IstanbulPrecompilesHeight,
+        >,
+        ActivatedAt<evm_precompile_frc20::FRC20Assets<Self>, CustomAssetsHeight>,
     );
 }
 
 impl module_xhub::Config for BaseApp {
     type AccountAsset = module_account::App<Self>;
+    type CustomAsset = module_account::App<Self>;
     type DecimalsMapping = EthereumDecimalsMapping;
 }
 
@@ -165,9 +214,32 @@ impl BaseApp {
                 ..Default::default()
             },
             event_notify: Arc::new(Notifications::new()),
+            pending_txs: Arc::new(RwLock::new(PendingTxPool::new())),
+            pending_tx_notify: Arc::new(Notifications::new()),
         })
     }
 
+    /// Create checkpoints of the chain state db and the history db in `dir`,
+    /// the table files are hard linked, so it is cheap and consistent
+    /// even if the databases are compacting in the background.
+    pub fn create_checkpoint(&self, dir: &Path) -> Result<()> {
+        self.chain_state
+            .read()
+            .snapshot(dir.join(CHAIN_STATE_PATH))?;
+        self.chain_db
+            .read()
+            .snapshot(dir.join(CHAIN_HISTORY_DATA_PATH))
+    }
+
+    /// Read the root hash of the chain state db in `basedir`,
+    /// such as the one restored from a snapshot.
+    pub fn read_root_hash(basedir: &Path) -> Result<Vec<u8>> {
+        let fdb = FinDB::open(basedir.join(CHAIN_STATE_PATH).as_path())?;
+        let chain_state =
+            ChainState::new(fdb, "findora_db".to_owned(), CHAIN_STATE_MIN_VERSIONS);
+        Ok(chain_state.root_hash())
+    }
+
     pub fn derive_app(&self) -> Self {
         let chain_state = self.chain_state.clone();
         let chain_db = self.chain_db.clone();
@@ -182,6 +254,8 @@ impl BaseApp {
             deliver_state: Context::new(chain_state, chain_db),
             modules: ModuleManager::default(),
             event_notify: self.event_notify.clone(),
+            pending_txs: self.pending_txs.clone(),
+            pending_tx_notify: self.pending_tx_notify.clone(),
         }
     }
 
@@ -264,6 +338,29 @@ impl BaseApp {
         }
     }
 
+    /// Create a context that reads the committed state at the given block height,
+    /// writes made through it are kept in its own session and never committed.
+    pub fn create_context_at(&self, height: u64) -> Result<Context> {
+        let latest = self.chain_state.read().height()?;
+        ensure!(
+            height <= latest,
+            format!("height: {} exceeds latest height: {}", height, latest)
+        );
+        let range = self.chain_state.read().get_ver_range()?;
+        ensure!(
+            height >= range.start,
+            format!(
+                "state at height: {} has been pruned, only the latest {} versions are kept",
+                height, CHAIN_STATE_MIN_VERSIONS
+            )
+        );
+
+        let mut ctx = self.check_state.copy_with_new_state();
+        ctx.version = Some(height);
+        ctx.header.height = height as i64;
+        Ok(ctx)
+    }
+
     /// retrieve the context for the txBytes and other memoized values.
     pub fn retrieve_context(&mut self, mode: RunTxMode) -> &mut Context {
         let ctx = if mode == RunTxMode::Deliver {
@@ -295,15 +392,81 @@ impl BaseApp {
         ctx.run_mode = RunTxMode::None;
         ctx.header_hash = header_hash;
         ctx.header = header;
+        ctx.block_proposer = None;
     }
 
-    pub fn deliver_findora_tx(&mut self, tx: &FindoraTransaction) -> Result<()> {
-        self.modules.process_findora_tx(&self.deliver_state, tx)
+    /// Set the staking key of the current block proposer, which receives
+    /// the EVM transaction fees of the block.
+    pub fn set_block_proposer(&mut self, proposer: &XfrPublicKey) {
+        self.deliver_state.block_proposer = Some(Address::from(*proposer));
+    }
+
+    /// Mints the converted UTXOs of `tx` on the EVM side, `asset` is the definition
+    /// of the converted asset unless it is FRA.
+    pub fn deliver_findora_tx(
+        &mut self,
+        tx: &FindoraTransaction,
+        asset: Option<&Asset>,
+    ) -> Result<()> {
+        ModuleManager::process_findora_tx(&self.deliver_state, tx, asset)
+    }
+
+    /// Registers the FRC20 attributes of the custom assets defined or updated by `tx`.
+    pub fn deliver_asset_definitions(&mut self, tx: &FindoraTransaction) -> Result<()> {
+        ModuleManager::process_asset_definitions(&self.deliver_state, tx)
     }
 
     pub fn consume_mint(&mut self) -> Option<Vec<NonConfidentialOutput>> {
         module_xhub::App::<BaseApp>::consume_mint(&self.deliver_state)
     }
+
+    /// Replays the transactions of a committed block on `ctx` in the order
+    /// they were delivered, until the ethereum transaction `hash` is reached,
+    /// which is not executed. `txs` are the raw transactions of the tendermint block.
+    ///
+    /// Like `DeliverTx`, the failures of the replayed transactions are ignored.
+    pub fn replay_block_until(
+        ctx: &Context,
+        txs: &[Vec<u8>],
+        hash: H256,
+        asset_of: &AssetLookup,
+    ) -> Result<()> {
+        // all the EVM related transactions were rejected in this interval
+        let h = ctx.header.height;
+        if CFG.checkpoint.disable_evm_block_height < h
+            && h < CFG.checkpoint.enable_frc20_height
+        {
+            return Err(eg!("EVM is disabled at this height"));
+        }
+
+        // the transactions are only dispatched in the running modes of the abci
+        let mut ctx = ctx.clone();
+        ctx.run_mode = RunTxMode::Deliver;
+
+        for raw_tx in txs {
+            if let Ok(tx) = EvmRawTxWrapper::unwrap(raw_tx) {
+                if let Ok(tx) = convert_unchecked_transaction::<SignedExtra>(tx) {
+                    if Some(hash) == app::ethereum_tx_hash(&tx) {
+                        return Ok(());
+                    }
+                    let _ = ModuleManager::process_tx::<SignedExtra>(ctx.clone(), tx);
+                }
+            } else if let Ok(tx) = serde_json::from_slice::<FindoraTransaction>(raw_tx) {
+                if !tx.valid_in_abci() {
+                    continue;
+                }
+                if is_convert_account(&tx) {
+                    let asset =
+                        get_convert_account_asset(&tx).and_then(|c| asset_of(&c));
+                    let _ = ModuleManager::process_findora_tx(&ctx, &tx, asset.as_ref());
+                } else if CFG.checkpoint.evm_custom_assets_height <= h {
+                    let _ = ModuleManager::process_asset_definitions(&ctx, &tx);
+                }
+            }
+        }
+
+        Err(eg!("transaction not found in block"))
+    }
 }
 
 impl BaseProvider for BaseApp {
@@ -7,14 +7,19 @@ use fp_core::{
         ActionResult, Applyable, Executable, SignedExtension, ValidateUnsigned,
     },
 };
-use fp_traits::evm::DecimalsMapping;
+use fp_traits::{account::CustomAsset, evm::DecimalsMapping};
 use fp_types::{
     actions,
     assemble::{convert_unsigned_transaction, CheckedTransaction, UncheckedTransaction},
+    assets::AssetMetadata,
     crypto::Address,
 };
 use ledger::{
-    converter::check_convert_account, data_model::Transaction as FindoraTransaction,
+    converter::check_convert_account,
+    data_model::{
+        Asset, AssetTypeCode, Memo, Operation, Transaction as FindoraTransaction,
+        ASSET_TYPE_FRA,
+    },
 };
 use ruc::*;
 use serde::Serialize;
@@ -92,7 +97,6 @@ impl ModuleManager {
     pub fn process_tx<
         Extra: Clone + Serialize + SignedExtension<AccountId = Address>,
     >(
-        &mut self,
         ctx: Context,
         tx: UncheckedTransaction<Extra>,
     ) -> Result<ActionResult> {
@@ -112,14 +116,128 @@ impl ModuleManager {
     }
 
     pub fn process_findora_tx(
-        &mut self,
         ctx: &Context,
         tx: &FindoraTransaction,
+        definition: Option<&Asset>,
     ) -> Result<()> {
-        let (owner, amount) = check_convert_account(tx)?;
-        let balance = EthereumDecimalsMapping::from_native_token(U256::from(amount))
-            .ok_or_else(|| eg!("The transfer to account amount is too large"))?;
-        module_account::App::<BaseApp>::mint(ctx, &Address::from(owner), balance)
+        let (owner, asset, amount) = check_convert_account(tx)?;
+        if asset == ASSET_TYPE_FRA {
+            let balance = EthereumDecimalsMapping::from_native_token(U256::from(amount))
+                .ok_or_else(|| eg!("The transfer to account amount is too large"))?;
+            return module_account::App::<BaseApp>::mint(
+                ctx,
+                &Address::from(owner),
+                balance,
+            );
+        }
+
+        if ctx.header.height < CFG.checkpoint.evm_custom_assets_height {
+            return Err(eg!("Invalid asset type only support FRA"));
+        }
+        let definition = definition.c(d!("asset is not defined"))?;
+        if !definition.asset_rules.transferable {
+            return Err(eg!("non-transferable asset can not be bridged"));
+        }
+
+        let asset = H256::from(asset.0);
+        if module_account::App::<BaseApp>::asset_metadata(ctx, &asset).is_none() {
+            let metadata = asset_metadata(
+                &definition.code,
+                &definition.memo,
+                definition.asset_rules.decimals,
+            );
+            module_account::App::<BaseApp>::set_asset_metadata(ctx, &metadata)?;
+        }
+
+        let amount = U256::from(amount);
+        if let Some(max_units) = definition.asset_rules.max_units {
+            let issuance =
+                module_account::App::<BaseApp>::asset_total_issuance(ctx, &asset)
+                    .checked_add(amount)
+                    .c(d!("issuance overflow"))?;
+            if issuance > U256::from(max_units) {
+                return Err(eg!("the transfer to account exceeds the max units"));
+            }
+        }
+        module_account::App::<BaseApp>::asset_mint(
+            ctx,
+            &asset,
+            &Address::from(owner),
+            amount,
+        )
+    }
+
+    pub fn process_asset_definitions(
+        ctx: &Context,
+        tx: &FindoraTransaction,
+    ) -> Result<()> {
+        for op in &tx.body.operations {
+            match op {
+                Operation::DefineAsset(define) => {
+                    let asset = &define.body.asset;
+                    let metadata = asset_metadata(
+                        &asset.code,
+                        &asset.memo,
+                        asset.asset_rules.decimals,
+                    );
+                    module_account::App::<BaseApp>::set_asset_metadata(ctx, &metadata)?;
+                }
+                Operation::UpdateMemo(update) => {
+                    let code = &update.body.asset_type;
+                    // assets defined before the custom assets fork are registered
+                    // when first bridged to the EVM side
+                    if let Some(registered) =
+                        module_account::App::<BaseApp>::asset_metadata(
+                            ctx,
+                            &H256::from(code.val.0),
+                        )
+                    {
+                        let metadata = asset_metadata(
+                            code,
+                            &update.body.new_memo,
+                            registered.decimals,
+                        );
+                        module_account::App::<BaseApp>::set_asset_metadata(
+                            ctx, &metadata,
+                        )?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Maximum length of the FRC20 symbol of a custom asset.
+const MAX_ASSET_SYMBOL_LEN: usize = 12;
+
+/// The FRC20 attributes of a custom asset. The memo is used as the name, and
+/// as the symbol when it is a short single word, the asset code in base64
+/// takes their place otherwise.
+pub fn asset_metadata(code: &AssetTypeCode, memo: &Memo, decimals: u8) -> AssetMetadata {
+    let code_b64 = code.to_base64();
+    let memo = memo.0.trim();
+
+    let name = if memo.is_empty() {
+        code_b64.clone()
+    } else {
+        memo.to_owned()
+    };
+    let symbol = if !memo.is_empty()
+        && memo.chars().count() <= MAX_ASSET_SYMBOL_LEN
+        && !memo.contains(char::is_whitespace)
+    {
+        memo.to_owned()
+    } else {
+        code_b64.chars().take(MAX_ASSET_SYMBOL_LEN).collect()
+    };
+
+    AssetMetadata {
+        asset: H256::from(code.val.0),
+        name,
+        symbol,
+        decimals,
     }
 }
 
@@ -147,3 +265,26 @@ impl ModuleManager {
         Ok(ActionResult::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_metadata() {
+        let code = AssetTypeCode::new_from_vec(vec![7; 32]);
+
+        let metadata = asset_metadata(&code, &Memo(" GOLD ".to_owned()), 4);
+        assert_eq!(metadata.asset, H256::repeat_byte(7));
+        assert_eq!(metadata.name, "GOLD");
+        assert_eq!(metadata.symbol, "GOLD");
+        assert_eq!(metadata.decimals, 4);
+
+        let metadata = asset_metadata(&code, &Memo("Findora Gold".to_owned()), 6);
+        assert_eq!(metadata.name, "Findora Gold");
+        assert_eq!(metadata.symbol, code.to_base64()[..MAX_ASSET_SYMBOL_LEN]);
+
+        let metadata = asset_metadata(&code, &Memo::default(), 6);
+        assert_eq!(metadata.name, code.to_base64());
+    }
+}
@@ -0,0 +1,106 @@
+use primitive_types::H256;
+use std::collections::{HashMap, VecDeque};
+
+/// Maximum number of accepted transactions remembered for pending filters.
+const PENDING_TX_HISTORY: usize = 4096;
+/// Number of blocks after which a transaction not yet committed is
+/// considered to be evicted from the mempool.
+const PENDING_TX_LIFESPAN: u64 = 100;
+
+/// Ethereum transactions accepted by `check_tx` and not committed yet.
+#[derive(Default)]
+pub struct PendingTxPool {
+    /// transaction hash => height at which it entered the mempool
+    txs: HashMap<H256, u64>,
+    /// recently accepted transactions, ordered by sequence number
+    history: VecDeque<(u64, H256)>,
+    /// sequence number of the last accepted transaction
+    sequence: u64,
+}
+
+impl PendingTxPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track a new pending transaction, returns false if it is already known.
+    pub fn insert(&mut self, hash: H256, height: u64) -> bool {
+        if self.txs.insert(hash, height).is_some() {
+            return false;
+        }
+
+        self.sequence += 1;
+        self.history.push_back((self.sequence, hash));
+        if self.history.len() > PENDING_TX_HISTORY {
+            self.history.pop_front();
+        }
+        true
+    }
+
+    /// Stop tracking a transaction once it is committed or evicted.
+    pub fn remove(&mut self, hash: &H256) -> bool {
+        self.txs.remove(hash).is_some()
+    }
+
+    /// Drop the transactions staying in the mempool for too long.
+    pub fn prune(&mut self, height: u64) {
+        self.txs
+            .retain(|_, at| at.saturating_add(PENDING_TX_LIFESPAN) > height);
+    }
+
+    pub fn contains(&self, hash: &H256) -> bool {
+        self.txs.contains_key(hash)
+    }
+
+    pub fn len(&self) -> usize {
+        self.txs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.txs.is_empty()
+    }
+
+    /// Sequence number of the last accepted transaction.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Hashes of the transactions accepted after `sequence`, along with the
+    /// sequence number to resume from.
+    pub fn hashes_since(&self, sequence: u64) -> (Vec<H256>, u64) {
+        let hashes = self
+            .history
+            .iter()
+            .filter(|(seq, _)| *seq > sequence)
+            .map(|(_, hash)| *hash)
+            .collect();
+        (hashes, self.sequence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_tx_pool() {
+        let mut pool = PendingTxPool::new();
+        let (tx1, tx2) = (H256::repeat_byte(1), H256::repeat_byte(2));
+
+        assert!(pool.insert(tx1, 1));
+        assert!(!pool.insert(tx1, 1));
+        assert!(pool.insert(tx2, 2));
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.hashes_since(0), (vec![tx1, tx2], 2));
+        assert_eq!(pool.hashes_since(1), (vec![tx2], 2));
+        assert_eq!(pool.hashes_since(2), (vec![], 2));
+
+        // committed transactions are still reported to filters
+        assert!(pool.remove(&tx1));
+        assert!(!pool.contains(&tx1));
+        assert_eq!(pool.hashes_since(0), (vec![tx1, tx2], 2));
+
+        pool.prune(2 + PENDING_TX_LIFESPAN);
+        assert!(pool.is_empty());
+    }
+}
@@ -1,15 +1,24 @@
 use crate::storage::*;
 use crate::{App, Config};
-use fp_core::{account::SmartAccount, context::Context};
+use fp_core::{account::SmartAccount, context::Context, ensure};
 use fp_storage::{Borrow, BorrowMut};
-use fp_traits::account::AccountAsset;
-use fp_types::crypto::Address;
-use primitive_types::U256;
+use fp_traits::account::{AccountAsset, CustomAsset};
+use fp_types::{
+    assets::AssetMetadata,
+    crypto::{Address, HA160, HA256},
+};
+use primitive_types::{H160, H256, U256};
 use ruc::*;
 
 impl<C: Config> AccountAsset<Address> for App<C> {
     fn total_issuance(ctx: &Context) -> U256 {
-        TotalIssuance::get(ctx.state.read().borrow()).unwrap_or_default()
+        let version = ctx.version_of(None);
+        if version == 0 {
+            TotalIssuance::get(ctx.state.read().borrow()).unwrap_or_default()
+        } else {
+            TotalIssuance::get_session_or_ver(ctx.state.read().borrow(), version)
+                .unwrap_or_default()
+        }
     }
 
     fn account_of(
@@ -17,11 +26,11 @@ impl<C: Config> AccountAsset<Address> for App<C> {
         who: &Address,
         height: Option<u64>,
     ) -> Option<SmartAccount> {
-        let version = height.unwrap_or(0);
+        let version = ctx.version_of(height);
         if version == 0 {
             AccountStore::get(ctx.state.read().borrow(), who)
         } else {
-            AccountStore::get_ver(ctx.state.read().borrow(), who, version)
+            AccountStore::get_session_or_ver(ctx.state.read().borrow(), who, version)
         }
     }
 
@@ -143,7 +152,18 @@ impl<C: Config> AccountAsset<Address> for App<C> {
     }
 
     fn allowance(ctx: &Context, owner: &Address, spender: &Address) -> U256 {
-        Allowances::get(ctx.state.read().borrow(), owner, spender).unwrap_or_default()
+        let version = ctx.version_of(None);
+        if version == 0 {
+            Allowances::get(ctx.state.read().borrow(), owner, spender)
+        } else {
+            Allowances::get_session_or_ver(
+                ctx.state.read().borrow(),
+                owner,
+                spender,
+                version,
+            )
+        }
+        .unwrap_or_default()
     }
 
     fn approve(
@@ -155,3 +175,185 @@ impl<C: Config> AccountAsset<Address> for App<C> {
         Allowances::insert(ctx.state.write().borrow_mut(), owner, spender, &amount)
     }
 }
+
+impl<C: Config> CustomAsset<Address> for App<C> {
+    fn asset_metadata(ctx: &Context, asset: &H256) -> Option<AssetMetadata> {
+        let asset = HA256::new(*asset);
+        let version = ctx.version_of(None);
+        if version == 0 {
+            AssetMetadatas::get(ctx.state.read().borrow(), &asset)
+        } else {
+            AssetMetadatas::get_session_or_ver(
+                ctx.state.read().borrow(),
+                &asset,
+                version,
+            )
+        }
+    }
+
+    fn set_asset_metadata(ctx: &Context, metadata: &AssetMetadata) -> Result<()> {
+        let asset = HA256::new(metadata.asset);
+        let address = HA160::from(metadata.address());
+        if let Some(registered) =
+            AssetAddresses::get(ctx.state.read().borrow(), &address)
+        {
+            ensure!(
+                registered == asset,
+                format!("asset address collision: {:?}", address.0)
+            );
+        }
+
+        AssetAddresses::insert(ctx.state.write().borrow_mut(), &address, &asset)?;
+        AssetMetadatas::insert(ctx.state.write().borrow_mut(), &asset, metadata)
+    }
+
+    fn asset_of_address(ctx: &Context, address: &H160) -> Option<H256> {
+        let address = HA160::from(*address);
+        let version = ctx.version_of(None);
+        if version == 0 {
+            AssetAddresses::get(ctx.state.read().borrow(), &address)
+        } else {
+            AssetAddresses::get_session_or_ver(
+                ctx.state.read().borrow(),
+                &address,
+                version,
+            )
+        }
+        .map(|asset| asset.h256())
+    }
+
+    fn asset_total_issuance(ctx: &Context, asset: &H256) -> U256 {
+        let asset = HA256::new(*asset);
+        let version = ctx.version_of(None);
+        if version == 0 {
+            AssetIssuances::get(ctx.state.read().borrow(), &asset)
+        } else {
+            AssetIssuances::get_session_or_ver(
+                ctx.state.read().borrow(),
+                &asset,
+                version,
+            )
+        }
+        .unwrap_or_default()
+    }
+
+    fn asset_balance(ctx: &Context, asset: &H256, who: &Address) -> U256 {
+        let asset = HA256::new(*asset);
+        let version = ctx.version_of(None);
+        if version == 0 {
+            AssetBalances::get(ctx.state.read().borrow(), &asset, who)
+        } else {
+            AssetBalances::get_session_or_ver(
+                ctx.state.read().borrow(),
+                &asset,
+                who,
+                version,
+            )
+        }
+        .unwrap_or_default()
+    }
+
+    fn asset_transfer(
+        ctx: &Context,
+        asset: &H256,
+        sender: &Address,
+        dest: &Address,
+        amount: U256,
+    ) -> Result<()> {
+        if amount.is_zero() || sender == dest {
+            return Ok(());
+        }
+
+        let from_balance = Self::asset_balance(ctx, asset, sender)
+            .checked_sub(amount)
+            .c(d!("insufficient balance"))?;
+        let to_balance = Self::asset_balance(ctx, asset, dest)
+            .checked_add(amount)
+            .c(d!("balance overflow"))?;
+
+        let asset = HA256::new(*asset);
+        AssetBalances::insert(
+            ctx.state.write().borrow_mut(),
+            &asset,
+            sender,
+            &from_balance,
+        )?;
+        AssetBalances::insert(ctx.state.write().borrow_mut(), &asset, dest, &to_balance)
+    }
+
+    fn asset_mint(
+        ctx: &Context,
+        asset: &H256,
+        target: &Address,
+        amount: U256,
+    ) -> Result<()> {
+        if amount.is_zero() {
+            return Ok(());
+        }
+
+        let balance = Self::asset_balance(ctx, asset, target)
+            .checked_add(amount)
+            .c(d!("balance overflow"))?;
+        let issuance = Self::asset_total_issuance(ctx, asset)
+            .checked_add(amount)
+            .c(d!("issuance overflow"))?;
+
+        let asset = HA256::new(*asset);
+        AssetBalances::insert(ctx.state.write().borrow_mut(), &asset, target, &balance)?;
+        AssetIssuances::insert(ctx.state.write().borrow_mut(), &asset, &issuance)
+    }
+
+    fn asset_burn(
+        ctx: &Context,
+        asset: &H256,
+        target: &Address,
+        amount: U256,
+    ) -> Result<()> {
+        if amount.is_zero() {
+            return Ok(());
+        }
+
+        let balance = Self::asset_balance(ctx, asset, target)
+            .checked_sub(amount)
+            .c(d!("insufficient balance"))?;
+        let issuance = Self::asset_total_issuance(ctx, asset)
+            .checked_sub(amount)
+            .c(d!("insufficient issuance"))?;
+
+        let asset = HA256::new(*asset);
+        AssetBalances::insert(ctx.state.write().borrow_mut(), &asset, target, &balance)?;
+        AssetIssuances::insert(ctx.state.write().borrow_mut(), &asset, &issuance)
+    }
+
+    fn asset_allowance(
+        ctx: &Context,
+        asset: &H256,
+        owner: &Address,
+        spender: &Address,
+    ) -> U256 {
+        let key = AssetOwner(HA256::new(*asset), owner.clone());
+        let version = ctx.version_of(None);
+        if version == 0 {
+            AssetAllowances::get(ctx.state.read().borrow(), &key, spender)
+        } else {
+            AssetAllowances::get_session_or_ver(
+                ctx.state.read().borrow(),
+                &key,
+                spender,
+                version,
+            )
+        }
+        .unwrap_or_default()
+    }
+
+    fn asset_approve(
+        ctx: &Context,
+        asset: &H256,
+        owner: &Address,
+        spender: &Address,
+        amount: U256,
+    ) -> Result<()> {
+        let key = AssetOwner(HA256::new(*asset), owner.clone());
+        AssetAllowances::insert(ctx.state.write().borrow_mut(), &key, spender, &amount)
+    }
+}
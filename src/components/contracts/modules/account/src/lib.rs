@@ -28,8 +28,12 @@ impl Config for () {
 
 mod storage {
     use fp_core::account::SmartAccount;
-    use fp_types::crypto::Address;
+    use fp_types::{
+        assets::AssetMetadata,
+        crypto::{Address, HA160, HA256},
+    };
     use primitive_types::U256;
+    use std::str::FromStr;
 
     use fp_storage::*;
 
@@ -40,6 +44,38 @@ mod storage {
     // The owner approve his amount of funds to the spender.
     // owner => spender => amount
     generate_storage!(Account, Allowances => DoubleMap<Address, Address, U256>);
+
+    // The FRC20 attributes of the custom assets, asset => metadata
+    generate_storage!(Account, AssetMetadatas => Map<HA256, AssetMetadata>);
+    // The custom asset of a FRC20 address, address => asset
+    generate_storage!(Account, AssetAddresses => Map<HA160, HA256>);
+    // The total units of a custom asset transferred from the UTXO side.
+    generate_storage!(Account, AssetIssuances => Map<HA256, U256>);
+    // asset => owner => amount
+    generate_storage!(Account, AssetBalances => DoubleMap<HA256, Address, U256>);
+    // (asset, owner) => spender => amount
+    generate_storage!(Account, AssetAllowances => DoubleMap<AssetOwner, Address, U256>);
+
+    /// The key of the assets owned by an account.
+    pub struct AssetOwner(pub HA256, pub Address);
+
+    impl ToString for AssetOwner {
+        fn to_string(&self) -> String {
+            format!("{}:{}", self.0.to_string(), self.1)
+        }
+    }
+
+    impl FromStr for AssetOwner {
+        type Err = ();
+
+        fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+            let (asset, owner) = s.split_once(':').ok_or(())?;
+            Ok(AssetOwner(
+                HA256::from_str(asset).map_err(|_| ())?,
+                Address::from_str(owner)?,
+            ))
+        }
+    }
 }
 
 pub struct App<C> {
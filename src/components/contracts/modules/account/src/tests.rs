@@ -2,9 +2,10 @@ use crate::storage::*;
 use crate::App;
 use fp_core::{account::SmartAccount, context::Context};
 use fp_storage::{Borrow, BorrowMut};
-use fp_traits::account::AccountAsset;
+use fp_traits::account::{AccountAsset, CustomAsset};
+use fp_types::assets::AssetMetadata;
 use fp_types::crypto::Address;
-use fp_types::U256;
+use fp_types::{H160, H256, U256};
 use parking_lot::RwLock;
 use rand_chacha::rand_core::SeedableRng;
 use rand_chacha::ChaChaRng;
@@ -149,6 +150,58 @@ fn test_account_of() {
     assert_eq!(App::<()>::account_of(&ctx, &addr, Some(3)), Some(sa3));
 }
 
+#[test]
+fn test_versioned_context() {
+    //Setup database
+    let ctx = setup();
+
+    //Generate Address
+    let mut prng = ChaChaRng::from_entropy();
+    let key = XfrKeyPair::generate(&mut prng);
+    let addr = Address::from(key.pub_key);
+
+    //Commit two versions of the account
+    let sa1 = SmartAccount {
+        balance: 123.into(),
+        ..Default::default()
+    };
+    AccountStore::insert(ctx.state.write().borrow_mut(), &addr, &sa1).unwrap();
+    assert!(ctx.state.write().commit(1).is_ok());
+
+    let sa2 = SmartAccount {
+        balance: 456.into(),
+        ..Default::default()
+    };
+    AccountStore::insert(ctx.state.write().borrow_mut(), &addr, &sa2).unwrap();
+    assert!(ctx.state.write().commit(2).is_ok());
+
+    //Reads through a versioned context resolve the history
+    let mut ver_ctx = ctx.copy_with_new_state();
+    ver_ctx.version = Some(1);
+    assert_eq!(
+        App::<()>::account_of(&ver_ctx, &addr, None),
+        Some(sa1.clone())
+    );
+    assert_eq!(App::<()>::balance(&ver_ctx, &addr), sa1.balance);
+    assert_eq!(
+        App::<()>::account_of(&ver_ctx, &addr, Some(0)),
+        Some(sa2.clone())
+    );
+    assert_eq!(App::<()>::account_of(&ver_ctx, &addr, Some(2)), Some(sa2));
+
+    //Writes in a versioned context are read back from the session
+    let dest = Address::from(XfrKeyPair::generate(&mut prng).pub_key);
+    assert!(App::<()>::transfer(&ver_ctx, &addr, &dest, 100.into()).is_ok());
+    assert_eq!(App::<()>::balance(&ver_ctx, &addr), 23.into());
+    assert_eq!(App::<()>::balance(&ver_ctx, &dest), 100.into());
+    assert_eq!(App::<()>::inc_nonce(&ver_ctx, &addr).unwrap(), 1.into());
+    assert_eq!(App::<()>::nonce(&ver_ctx, &addr), 1.into());
+
+    //The original context is unaffected
+    assert_eq!(App::<()>::balance(&ctx, &addr), 456.into());
+    assert_eq!(App::<()>::balance(&ctx, &dest), 0.into());
+}
+
 #[test]
 fn test_account_balance() {
     //Setup database
@@ -376,3 +429,63 @@ fn test_account_refund() {
     assert_eq!(App::<()>::balance(&ctx, &address), 1200.into());
     assert_eq!(App::<()>::reserved_balance(&ctx, &address), 300.into());
 }
+
+#[test]
+fn test_custom_asset() {
+    //Setup database
+    let ctx = setup();
+
+    //Generate Addresses
+    let mut prng = ChaChaRng::from_entropy();
+    let alice = Address::from(XfrKeyPair::generate(&mut prng).pub_key);
+    let bob = Address::from(XfrKeyPair::generate(&mut prng).pub_key);
+
+    //Register the asset under its FRC20 address
+    let metadata = AssetMetadata {
+        asset: H256::repeat_byte(1),
+        name: "Findora Test Asset".to_string(),
+        symbol: "FTA".to_string(),
+        decimals: 6,
+    };
+    let asset = metadata.asset;
+    assert!(App::<()>::set_asset_metadata(&ctx, &metadata).is_ok());
+    assert_eq!(
+        App::<()>::asset_metadata(&ctx, &asset),
+        Some(metadata.clone())
+    );
+    assert_eq!(
+        App::<()>::asset_of_address(&ctx, &metadata.address()),
+        Some(asset)
+    );
+    assert_eq!(
+        App::<()>::asset_of_address(&ctx, &H160::from_low_u64_be(0x1000)),
+        None
+    );
+
+    //Balances are tracked per asset
+    assert!(App::<()>::asset_mint(&ctx, &asset, &alice, 100.into()).is_ok());
+    assert!(App::<()>::asset_transfer(&ctx, &asset, &alice, &bob, 40.into()).is_ok());
+    assert!(App::<()>::asset_transfer(&ctx, &asset, &alice, &bob, 80.into()).is_err());
+    assert_eq!(App::<()>::asset_balance(&ctx, &asset, &alice), 60.into());
+    assert_eq!(App::<()>::asset_balance(&ctx, &asset, &bob), 40.into());
+    assert_eq!(
+        App::<()>::asset_balance(&ctx, &H256::repeat_byte(2), &bob),
+        U256::zero()
+    );
+    assert_eq!(App::<()>::balance(&ctx, &bob), U256::zero());
+
+    assert!(App::<()>::asset_burn(&ctx, &asset, &bob, 10.into()).is_ok());
+    assert_eq!(App::<()>::asset_total_issuance(&ctx, &asset), 90.into());
+
+    //Allowances are tracked per asset
+    assert!(App::<()>::asset_approve(&ctx, &asset, &alice, &bob, 20.into()).is_ok());
+    assert_eq!(
+        App::<()>::asset_allowance(&ctx, &asset, &alice, &bob),
+        20.into()
+    );
+    assert_eq!(
+        App::<()>::asset_allowance(&ctx, &H256::repeat_byte(2), &alice, &bob),
+        U256::zero()
+    );
+    assert_eq!(App::<()>::allowance(&ctx, &alice, &bob), U256::zero());
+}
@@ -1,8 +1,9 @@
 use crate::storage::*;
-use crate::{App, Config, ContractLog, TransactionExecuted};
+use crate::{App, Config, ContractLog, TransactionData, TransactionExecuted};
 use config::abci::global_cfg::CFG;
 use ethereum::{
-    BlockV0 as Block, LegacyTransactionMessage, Receipt, TransactionV0 as Transaction,
+    BlockV2 as Block, EIP1559TransactionMessage, EIP2930TransactionMessage,
+    LegacyTransactionMessage, Receipt, TransactionV2 as Transaction,
 };
 use ethereum_types::{Bloom, BloomInput, H160, H256, H64, U256};
 use evm::{ExitFatal, ExitReason};
@@ -12,9 +13,10 @@ use fp_core::{
 use fp_events::Event;
 use fp_evm::{BlockId, CallOrCreateInfo, Runner, TransactionStatus};
 use fp_storage::{Borrow, BorrowMut};
+use fp_traits::evm::FeeCalculator;
 use fp_types::crypto::HA256;
 use fp_types::{actions::evm as EvmAction, crypto::secp256k1_ecdsa_recover};
-use fp_utils::{proposer_converter, timestamp_converter};
+use fp_utils::timestamp_converter;
 use log::{debug, info};
 use ruc::*;
 use sha3::{Digest, Keccak256};
@@ -23,12 +25,32 @@ impl<C: Config> App<C> {
     pub fn recover_signer(transaction: &Transaction) -> Option<H160> {
         let mut sig = [0u8; 65];
         let mut msg = [0u8; 32];
-        sig[0..32].copy_from_slice(&transaction.signature.r()[..]);
-        sig[32..64].copy_from_slice(&transaction.signature.s()[..]);
-        sig[64] = transaction.signature.standard_v();
-        msg.copy_from_slice(
-            &LegacyTransactionMessage::from(transaction.clone()).hash()[..],
-        );
+        match transaction {
+            Transaction::Legacy(t) => {
+                sig[0..32].copy_from_slice(&t.signature.r()[..]);
+                sig[32..64].copy_from_slice(&t.signature.s()[..]);
+                sig[64] = t.signature.standard_v();
+                msg.copy_from_slice(
+                    &LegacyTransactionMessage::from(t.clone()).hash()[..],
+                );
+            }
+            Transaction::EIP2930(t) => {
+                sig[0..32].copy_from_slice(&t.r[..]);
+                sig[32..64].copy_from_slice(&t.s[..]);
+                sig[64] = t.odd_y_parity as u8;
+                msg.copy_from_slice(
+                    &EIP2930TransactionMessage::from(t.clone()).hash()[..],
+                );
+            }
+            Transaction::EIP1559(t) => {
+                sig[0..32].copy_from_slice(&t.r[..]);
+                sig[32..64].copy_from_slice(&t.s[..]);
+                sig[64] = t.odd_y_parity as u8;
+                msg.copy_from_slice(
+                    &EIP1559TransactionMessage::from(t.clone()).hash()[..],
+                );
+            }
+        }
 
         let pubkey = secp256k1_ecdsa_recover(&sig, &msg).ok()?;
         Some(H160::from(H256::from_slice(
@@ -36,6 +58,11 @@ impl<C: Config> App<C> {
         )))
     }
 
+    /// The base fee per gas, EIP-1559 transactions pay it plus their priority fee.
+    pub fn base_fee() -> U256 {
+        C::FeeCalculator::min_gas_price()
+    }
+
     pub fn store_block(&mut self, ctx: &mut Context, block_number: U256) -> Result<()> {
         // #[cfg(feature = "debug_env")]
         // const EVM_FIRST_BLOCK_HEIGHT: U256 = U256::from(142_5000);
@@ -82,8 +109,7 @@ impl<C: Config> App<C> {
                 Some(BlockId::Number(block_number.saturating_sub(U256::one()))),
             )
             .unwrap_or_default(),
-            beneficiary: proposer_converter(ctx.header.proposer_address.clone())
-                .unwrap_or_default(),
+            beneficiary: ctx.proposer_evm_address(),
             state_root,
             receipts_root,
             logs_bloom,
@@ -105,7 +131,7 @@ impl<C: Config> App<C> {
         CurrentBlockNumber::put(ctx.db.write().borrow_mut(), &block_number)?;
         BlockHash::insert(ctx.db.write().borrow_mut(), &block_number, &block_hash)?;
         if is_store_block {
-            CurrentBlock::insert(ctx.db.write().borrow_mut(), &block_hash, &block)?;
+            CurrentBlockV2::insert(ctx.db.write().borrow_mut(), &block_hash, &block)?;
             CurrentReceipts::insert(
                 ctx.db.write().borrow_mut(),
                 &block_hash,
@@ -130,29 +156,20 @@ impl<C: Config> App<C> {
         let source = Self::recover_signer(&transaction)
             .ok_or_else(|| eg!("ExecuteTransaction: InvalidSignature"))?;
 
-        let transaction_hash =
-            H256::from_slice(Keccak256::digest(&rlp::encode(&transaction)).as_slice());
+        let transaction_hash = transaction.hash();
 
         let mut pending_txs: Vec<_> =
             PendingTransactions::get(ctx.db.read().borrow()).unwrap_or_default();
         let transaction_index = pending_txs.len() as u32;
 
-        let gas_limit = transaction.gas_limit;
+        let transaction_data = TransactionData::from(&transaction);
+        let gas_limit = transaction_data.gas_limit;
 
-        let execute_ret = Self::execute_transaction(
-            ctx,
-            source,
-            transaction.input.clone(),
-            transaction.value,
-            transaction.gas_limit,
-            Some(transaction.gas_price),
-            Some(transaction.nonce),
-            transaction.action,
-        );
+        let execute_ret = Self::execute_transaction(ctx, source, &transaction);
 
         if let Err(e) = execute_ret {
             let mut to = Default::default();
-            if let ethereum::TransactionAction::Call(target) = transaction.action {
+            if let ethereum::TransactionAction::Call(target) = transaction_data.action {
                 to = target;
             }
             events.push(Event::emit_event(
@@ -298,18 +315,24 @@ impl<C: Config> App<C> {
         })
     }
 
-    #[allow(clippy::too_many_arguments)]
     /// Execute an Ethereum transaction.
     pub fn execute_transaction(
         ctx: &Context,
         from: H160,
-        input: Vec<u8>,
-        value: U256,
-        gas_limit: U256,
-        gas_price: Option<U256>,
-        nonce: Option<U256>,
-        action: ethereum::TransactionAction,
+        transaction: &Transaction,
     ) -> Result<(Option<H160>, Option<H160>, CallOrCreateInfo)> {
+        let transaction_data = TransactionData::from(transaction);
+        let gas_price = transaction_data.effective_gas_price(Self::base_fee());
+        let TransactionData {
+            action,
+            input,
+            nonce,
+            gas_limit,
+            value,
+            access_list,
+            ..
+        } = transaction_data;
+
         match action {
             ethereum::TransactionAction::Call(target) => {
                 let res = C::Runner::call(
@@ -320,8 +343,9 @@ impl<C: Config> App<C> {
                         input,
                         value,
                         gas_limit: gas_limit.low_u64(),
-                        gas_price,
-                        nonce,
+                        gas_price: Some(gas_price),
+                        nonce: Some(nonce),
+                        access_list,
                     },
                     C::config(),
                 )?;
@@ -336,8 +360,9 @@ impl<C: Config> App<C> {
                         init: input,
                         value,
                         gas_limit: gas_limit.low_u64(),
-                        gas_price,
-                        nonce,
+                        gas_price: Some(gas_price),
+                        nonce: Some(nonce),
+                        access_list,
                     },
                     C::config(),
                 )?;
@@ -360,7 +385,17 @@ impl<C: Config> App<C> {
     /// Get the block with given block id.
     pub fn current_block(&self, ctx: &Context, id: Option<BlockId>) -> Option<Block> {
         let hash = HA256::new(Self::block_hash(ctx, id).unwrap_or_default());
-        CurrentBlock::get(ctx.db.read().borrow(), &hash)
+        CurrentBlockV2::get(ctx.db.read().borrow(), &hash).or_else(|| {
+            legacy::CurrentBlock::get(ctx.db.read().borrow(), &hash).map(|block| Block {
+                header: block.header,
+                transactions: block
+                    .transactions
+                    .into_iter()
+                    .map(Transaction::Legacy)
+                    .collect(),
+                ommers: block.ommers,
+            })
+        })
     }
 
     /// Get receipts with given block id.
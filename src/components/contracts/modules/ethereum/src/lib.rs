@@ -3,8 +3,11 @@
 
 mod basic;
 mod impls;
+mod transaction;
 
 use abci::{RequestEndBlock, ResponseEndBlock};
+use config::abci::global_cfg::CFG;
+use ethereum::TransactionV2 as Transaction;
 use ethereum_types::{H160, H256, U256};
 use evm::Config as EvmConfig;
 use fp_core::{
@@ -24,6 +27,8 @@ use fp_types::{actions::ethereum::Action, crypto::Address};
 use ruc::*;
 use std::marker::PhantomData;
 
+pub use transaction::TransactionData;
+
 pub const MODULE_NAME: &str = "ethereum";
 
 static ISTANBUL_CONFIG: EvmConfig = EvmConfig::istanbul();
@@ -52,7 +57,7 @@ pub trait Config {
 }
 
 pub mod storage {
-    use ethereum::{BlockV0 as Block, Receipt, TransactionV0 as Transaction};
+    use ethereum::{BlockV2 as Block, Receipt, TransactionV2 as Transaction};
     use ethereum_types::U256;
     use fp_evm::TransactionStatus;
     use fp_storage::*;
@@ -68,12 +73,23 @@ pub mod storage {
     generate_storage!(Ethereum, CurrentBlockNumber => Value<U256>);
     // Mapping for block number and hashes.
     generate_storage!(Ethereum, BlockHash => Map<U256, HA256>);
-    // The ethereum history blocks with block number.
-    generate_storage!(Ethereum, CurrentBlock => Map<HA256, Block>);
+    // The ethereum history blocks with block number,
+    // a new key is used since they can not be decoded as the legacy ones.
+    generate_storage!(Ethereum, CurrentBlockV2 => Map<HA256, Block>);
     // The ethereum history receipts with block number.
     generate_storage!(Ethereum, CurrentReceipts => Map<HA256, Vec<Receipt>>);
     // The ethereum history transaction statuses with block number.
     generate_storage!(Ethereum, CurrentTransactionStatuses => Map<HA256, Vec<TransactionStatus>>);
+
+    /// Blocks stored before typed transactions were supported.
+    pub mod legacy {
+        use ethereum::BlockV0 as Block;
+        use fp_storage::*;
+        use fp_types::crypto::HA256;
+
+        // The ethereum history blocks with block number.
+        generate_storage!(Ethereum, CurrentBlock => Map<HA256, Block>);
+    }
 }
 
 #[derive(Event)]
@@ -138,7 +154,8 @@ impl<C: Config> Executable for App<C> {
         ensure!(origin.is_none(), "InvalidTransaction: IllegalOrigin");
 
         match call {
-            Action::Transact(tx) => Self::do_transact(ctx, tx),
+            Action::Transact(tx) => Self::do_transact(ctx, Transaction::Legacy(tx)),
+            Action::TransactTyped(tx) => Self::do_transact(ctx, tx),
         }
     }
 }
@@ -147,8 +164,23 @@ impl<C: Config> ValidateUnsigned for App<C> {
     type Call = Action;
 
     fn validate_unsigned(ctx: &Context, call: &Self::Call) -> Result<()> {
-        let Action::Transact(transaction) = call;
-        if let Some(chain_id) = transaction.signature.chain_id() {
+        let transaction = match call {
+            Action::Transact(tx) => Transaction::Legacy(tx.clone()),
+            Action::TransactTyped(tx) => {
+                ensure!(
+                    ctx.header.height >= CFG.checkpoint.enable_eip1559_height,
+                    "InvalidTransaction: typed transactions are not enabled yet"
+                );
+                ensure!(
+                    !matches!(tx, Transaction::Legacy(_)),
+                    "InvalidTransaction: legacy transaction in typed envelope"
+                );
+                tx.clone()
+            }
+        };
+        let transaction_data = TransactionData::from(&transaction);
+
+        if let Some(chain_id) = transaction_data.chain_id {
             if chain_id != C::ChainId::get() {
                 return Err(eg!(format!(
                     "InvalidChainId, got {}, but expected {}",
@@ -160,25 +192,37 @@ impl<C: Config> ValidateUnsigned for App<C> {
             return Err(eg!("Must provide chainId".to_string()));
         }
 
-        let origin = Self::recover_signer(transaction)
+        let origin = Self::recover_signer(&transaction)
             .ok_or_else(|| eg!("InvalidSignature, can not recover signer address"))?;
 
         // Same as go ethereum, Min gas limit is 21000.
-        if transaction.gas_limit < U256::from(21000)
-            || transaction.gas_limit > C::BlockGasLimit::get()
+        if transaction_data.gas_limit < U256::from(21000)
+            || transaction_data.gas_limit > C::BlockGasLimit::get()
         {
             return Err(eg!(format!(
                 "InvalidGasLimit: got {}, the gas limit must be in range [21000, {}]",
-                transaction.gas_limit,
+                transaction_data.gas_limit,
                 C::BlockGasLimit::get()
             )));
         }
 
-        if transaction.gas_price < C::FeeCalculator::min_gas_price() {
+        let base_fee = Self::base_fee();
+        if let (Some(max_fee), Some(priority_fee)) = (
+            transaction_data.max_fee_per_gas,
+            transaction_data.max_priority_fee_per_gas,
+        ) {
+            if priority_fee > max_fee {
+                return Err(eg!(format!(
+                    "InvalidGasPrice: max priority fee per gas {} higher than max fee per gas {}",
+                    priority_fee, max_fee
+                )));
+            }
+        }
+        if transaction_data.max_gas_price() < base_fee {
             return Err(eg!(format!(
                 "InvalidGasPrice: got {}, but the minimum gas price is {}",
-                transaction.gas_price,
-                C::FeeCalculator::min_gas_price()
+                transaction_data.max_gas_price(),
+                base_fee
             )));
         }
 
@@ -186,15 +230,17 @@ impl<C: Config> ValidateUnsigned for App<C> {
         let nonce = C::AccountAsset::nonce(ctx, &account_id);
         let balance = C::AccountAsset::balance(ctx, &account_id);
 
-        if transaction.nonce < nonce {
+        if transaction_data.nonce < nonce {
             return Err(eg!(format!(
                 "InvalidNonce: origin: {:?}, got {}, but expected {}",
-                origin, transaction.nonce, nonce
+                origin, transaction_data.nonce, nonce
             )));
         }
 
-        let fee = transaction.gas_price.saturating_mul(transaction.gas_limit);
-        let total_payment = transaction.value.saturating_add(fee);
+        let fee = transaction_data
+            .max_gas_price()
+            .saturating_mul(transaction_data.gas_limit);
+        let total_payment = transaction_data.value.saturating_add(fee);
         if balance < total_payment {
             return Err(eg!(format!(
                 "InsufficientBalance, origin: {:?}, actual balance {}, but expected payment {}",
@@ -0,0 +1,88 @@
+use ethereum::{TransactionAction, TransactionV2 as Transaction};
+use ethereum_types::{H160, H256, U256};
+
+/// The fields shared by all the ethereum transaction types.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionData {
+    pub action: TransactionAction,
+    pub input: Vec<u8>,
+    pub nonce: U256,
+    pub gas_limit: U256,
+    /// Gas price of a legacy or EIP-2930 transaction.
+    pub gas_price: Option<U256>,
+    /// Fee cap of an EIP-1559 transaction.
+    pub max_fee_per_gas: Option<U256>,
+    /// Tip cap of an EIP-1559 transaction.
+    pub max_priority_fee_per_gas: Option<U256>,
+    pub value: U256,
+    pub chain_id: Option<u64>,
+    pub access_list: Vec<(H160, Vec<H256>)>,
+}
+
+impl TransactionData {
+    /// The price per gas actually paid by the transaction with the given base fee.
+    pub fn effective_gas_price(&self, base_fee: U256) -> U256 {
+        match (self.max_fee_per_gas, self.max_priority_fee_per_gas) {
+            (Some(max_fee), Some(priority_fee)) => {
+                max_fee.min(base_fee.saturating_add(priority_fee))
+            }
+            _ => self.gas_price.unwrap_or_default(),
+        }
+    }
+
+    /// The highest price per gas the sender may pay.
+    pub fn max_gas_price(&self) -> U256 {
+        self.max_fee_per_gas.or(self.gas_price).unwrap_or_default()
+    }
+}
+
+impl From<&Transaction> for TransactionData {
+    fn from(t: &Transaction) -> Self {
+        match t {
+            Transaction::Legacy(t) => TransactionData {
+                action: t.action,
+                input: t.input.clone(),
+                nonce: t.nonce,
+                gas_limit: t.gas_limit,
+                gas_price: Some(t.gas_price),
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
+                value: t.value,
+                chain_id: t.signature.chain_id(),
+                access_list: Vec::new(),
+            },
+            Transaction::EIP2930(t) => TransactionData {
+                action: t.action,
+                input: t.input.clone(),
+                nonce: t.nonce,
+                gas_limit: t.gas_limit,
+                gas_price: Some(t.gas_price),
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
+                value: t.value,
+                chain_id: Some(t.chain_id),
+                access_list: t
+                    .access_list
+                    .iter()
+                    .map(|item| (item.address, item.slots.clone()))
+                    .collect(),
+            },
+            Transaction::EIP1559(t) => TransactionData {
+                action: t.action,
+                input: t.input.clone(),
+                nonce: t.nonce,
+                gas_limit: t.gas_limit,
+                gas_price: None,
+                max_fee_per_gas: Some(t.max_fee_per_gas),
+                max_priority_fee_per_gas: Some(t.max_priority_fee_per_gas),
+                value: t.value,
+                chain_id: Some(t.chain_id),
+                access_list: t
+                    .access_list
+                    .iter()
+                    .map(|item| (item.address, item.slots.clone()))
+                    .collect(),
+            },
+        }
+    }
+}
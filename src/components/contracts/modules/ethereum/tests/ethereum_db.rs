@@ -1,10 +1,14 @@
 use baseapp::BaseApp;
-use ethereum::{TransactionAction, TransactionSignature, TransactionV0};
+use ethereum::{
+    BlockV0, PartialHeader, TransactionAction, TransactionSignature, TransactionV0,
+    TransactionV2,
+};
 use fp_core::context::Context;
+use fp_evm::BlockId;
 use fp_storage::{Borrow, BorrowMut, RwLock};
 use fp_types::crypto::HA256;
 use fp_types::{H256, U256};
-use module_ethereum::storage::TransactionIndex;
+use module_ethereum::storage::{legacy, TransactionIndex};
 use sha3::{Digest, Keccak256};
 use std::env::temp_dir;
 use std::sync::Arc;
@@ -12,6 +16,19 @@ use std::time::SystemTime;
 use storage::db::{FinDB, RocksDB};
 use storage::state::ChainState;
 
+fn gen_legacy_txn(nonce: u32) -> TransactionV0 {
+    TransactionV0 {
+        nonce: U256::from(nonce),
+        gas_price: Default::default(),
+        gas_limit: Default::default(),
+        action: TransactionAction::Create,
+        value: Default::default(),
+        input: vec![],
+        signature: TransactionSignature::new(27, H256::random(), H256::random())
+            .unwrap(),
+    }
+}
+
 fn setup() -> Context {
     let time = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
@@ -47,16 +64,7 @@ fn test_eth_db_migrate_txn_index() {
     //Create txn hashes, block numbers and indices and save them to chain-state
     let mut txns = Vec::with_capacity(5);
     for i in 0..5 {
-        let txn = TransactionV0 {
-            nonce: U256::from(i),
-            gas_price: Default::default(),
-            gas_limit: Default::default(),
-            action: TransactionAction::Create,
-            value: Default::default(),
-            input: vec![],
-            signature: TransactionSignature::new(27, H256::random(), H256::random())
-                .unwrap(),
-        };
+        let txn = gen_legacy_txn(i);
 
         let transaction_hash =
             H256::from_slice(Keccak256::digest(&rlp::encode(&txn)).as_slice());
@@ -80,3 +88,47 @@ fn test_eth_db_migrate_txn_index() {
         assert_eq!(value.unwrap(), txn.1);
     }
 }
+
+#[test]
+fn test_eth_db_read_legacy_block() {
+    let ctx = setup();
+
+    let partial_header = PartialHeader {
+        parent_hash: Default::default(),
+        beneficiary: Default::default(),
+        state_root: Default::default(),
+        receipts_root: Default::default(),
+        logs_bloom: Default::default(),
+        difficulty: Default::default(),
+        number: U256::one(),
+        gas_limit: Default::default(),
+        gas_used: Default::default(),
+        timestamp: 0,
+        extra_data: vec![],
+        mix_hash: Default::default(),
+        nonce: Default::default(),
+    };
+    let txns = vec![gen_legacy_txn(0), gen_legacy_txn(1)];
+    let block = BlockV0::new(partial_header, txns.clone(), vec![]);
+    let block_hash = block.header.hash();
+
+    // stored before typed transactions were supported
+    legacy::CurrentBlock::insert(
+        ctx.db.write().borrow_mut(),
+        &HA256::new(block_hash),
+        &block,
+    )
+    .unwrap();
+
+    let app = module_ethereum::App::<BaseApp>::new(false);
+    let current = app
+        .current_block(&ctx, Some(BlockId::Hash(block_hash)))
+        .unwrap();
+    assert_eq!(block.header, current.header);
+    assert_eq!(
+        txns.into_iter()
+            .map(TransactionV2::Legacy)
+            .collect::<Vec<_>>(),
+        current.transactions
+    );
+}
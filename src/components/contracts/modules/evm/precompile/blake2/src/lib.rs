@@ -0,0 +1,117 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod eip_152;
+
+#[cfg(test)]
+mod tests;
+
+use evm::{executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use module_evm::precompile::{FinState, Precompile, PrecompileId};
+
+/// Length of the input of the F compression function, EIP-152.
+const BLAKE2_F_ARG_LEN: usize = 213;
+
+/// The Blake2F builtin, EIP-152.
+pub struct Blake2F;
+
+impl Blake2F {
+    const GAS_COST_PER_ROUND: u64 = 1;
+}
+
+impl PrecompileId for Blake2F {
+    fn contract_id() -> u64 {
+        0x9
+    }
+}
+
+impl Precompile for Blake2F {
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        _context: &Context,
+        _state: &FinState,
+    ) -> core::result::Result<PrecompileOutput, ExitError> {
+        let (output, cost) = blake2_f(input, target_gas)?;
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost,
+            output,
+            logs: Default::default(),
+        })
+    }
+}
+
+/// Run the F compression function over the EIP-152 encoded `input`,
+/// returns the new state vector along with the gas cost.
+fn blake2_f(input: &[u8], target_gas: Option<u64>) -> Result<(Vec<u8>, u64), ExitError> {
+    if input.len() != BLAKE2_F_ARG_LEN {
+        return Err(ExitError::Other(
+            "input length for Blake2 F precompile should be exactly 213 bytes".into(),
+        ));
+    }
+
+    let mut rounds_buf = [0u8; 4];
+    rounds_buf.copy_from_slice(&input[0..4]);
+    let rounds = u32::from_be_bytes(rounds_buf);
+
+    let cost = rounds as u64 * Blake2F::GAS_COST_PER_ROUND;
+    if let Some(gas_left) = target_gas {
+        if gas_left < cost {
+            return Err(ExitError::OutOfGas);
+        }
+    }
+
+    // the words are little endian encoded
+    let read_word = |offset: usize| -> u64 {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&input[offset..offset + 8]);
+        u64::from_le_bytes(buf)
+    };
+
+    let mut h = [0u64; 8];
+    for (i, state_word) in h.iter_mut().enumerate() {
+        *state_word = read_word(4 + i * 8);
+    }
+
+    let mut m = [0u64; 16];
+    for (i, msg_word) in m.iter_mut().enumerate() {
+        *msg_word = read_word(68 + i * 8);
+    }
+
+    let t = [read_word(196), read_word(204)];
+
+    let f = match input[212] {
+        0 => false,
+        1 => true,
+        _ => {
+            return Err(ExitError::Other(
+                "incorrect final block indicator flag".into(),
+            ))
+        }
+    };
+
+    eip_152::compress(&mut h, m, t, f, rounds as usize);
+
+    let mut output = Vec::with_capacity(64);
+    for state_word in h.iter() {
+        output.extend_from_slice(&state_word.to_le_bytes());
+    }
+
+    Ok((output, cost))
+}
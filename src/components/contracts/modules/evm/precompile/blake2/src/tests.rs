@@ -0,0 +1,79 @@
+use crate::*;
+
+// Test vectors from EIP-152
+const INPUT_HEAD_12: &str = "0000000c";
+const INPUT_HEAD_0: &str = "00000000";
+const INPUT_BODY: &str =
+    "48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5\
+    d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b\
+    6162630000000000000000000000000000000000000000000000000000000000\
+    0000000000000000000000000000000000000000000000000000000000000000\
+    0000000000000000000000000000000000000000000000000000000000000000\
+    0000000000000000000000000000000000000000000000000000000000000000\
+    03000000000000000000000000000000";
+
+fn input(head: &str, flag: &str) -> Vec<u8> {
+    hex::decode(format!("{}{}{}", head, INPUT_BODY, flag)).unwrap()
+}
+
+#[test]
+fn blake2_f_twelve_rounds() {
+    let expected = hex::decode(
+        "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+        7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+    )
+    .unwrap();
+
+    assert_eq!(
+        blake2_f(&input(INPUT_HEAD_12, "01"), None),
+        Ok((expected, 12))
+    );
+}
+
+#[test]
+fn blake2_f_zero_rounds() {
+    let expected = hex::decode(
+        "08c9bcf367e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5\
+        d282e6ad7f520e511f6c3e2b8c68059b9442be0454267ce079217e1319cde05b",
+    )
+    .unwrap();
+
+    assert_eq!(
+        blake2_f(&input(INPUT_HEAD_0, "01"), None),
+        Ok((expected, 0))
+    );
+}
+
+#[test]
+fn blake2_f_not_final_block() {
+    let expected = hex::decode(
+        "75ab69d3190a562c51aef8d88f1c2775876944407270c42c9844252c26d28752\
+        98743e7f6d5ea2f2d3e8d226039cd31b4e426ac4f2d3d666a610c2116fde4735",
+    )
+    .unwrap();
+
+    assert_eq!(
+        blake2_f(&input(INPUT_HEAD_12, "00"), None),
+        Ok((expected, 12))
+    );
+}
+
+#[test]
+fn blake2_f_invalid_input() {
+    assert_eq!(
+        blake2_f(&[0u8; 212], None),
+        Err(ExitError::Other(
+            "input length for Blake2 F precompile should be exactly 213 bytes".into()
+        ))
+    );
+    assert_eq!(
+        blake2_f(&input(INPUT_HEAD_12, "02"), None),
+        Err(ExitError::Other(
+            "incorrect final block indicator flag".into()
+        ))
+    );
+    assert_eq!(
+        blake2_f(&input(INPUT_HEAD_12, "01"), Some(11)),
+        Err(ExitError::OutOfGas)
+    );
+}
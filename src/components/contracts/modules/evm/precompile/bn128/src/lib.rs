@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests;
+
+use evm::{executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use module_evm::precompile::{FinState, LinearCostPrecompile, Precompile, PrecompileId};
+
+/// Gas cost of the pairing check, EIP-1108 (Istanbul).
+const BN128_PAIRING_BASE: u64 = 45_000;
+const BN128_PAIRING_PER_POINT: u64 = 34_000;
+
+/// Size of one (G1, G2) pair in the pairing input.
+const PAIR_ELEMENT_LEN: usize = 192;
+
+/// Copy `source[offset..]` into `target`, the missing bytes are left to zero.
+fn read_input(source: &[u8], target: &mut [u8], offset: usize) {
+    if source.len() <= offset {
+        return;
+    }
+
+    let len = core::cmp::min(target.len(), source.len() - offset);
+    target[..len].copy_from_slice(&source[offset..][..len]);
+}
+
+fn read_fr(input: &[u8], start_inx: usize) -> Result<bn::Fr, ExitError> {
+    let mut buf = [0u8; 32];
+    read_input(input, &mut buf, start_inx);
+
+    bn::Fr::from_slice(&buf)
+        .map_err(|_| ExitError::Other("Invalid field element".into()))
+}
+
+fn read_point(input: &[u8], start_inx: usize) -> Result<bn::G1, ExitError> {
+    use bn::{AffineG1, Fq, Group, G1};
+
+    let mut px_buf = [0u8; 32];
+    let mut py_buf = [0u8; 32];
+    read_input(input, &mut px_buf, start_inx);
+    read_input(input, &mut py_buf, start_inx + 32);
+
+    let px = Fq::from_slice(&px_buf)
+        .map_err(|_| ExitError::Other("Invalid point x coordinate".into()))?;
+    let py = Fq::from_slice(&py_buf)
+        .map_err(|_| ExitError::Other("Invalid point y coordinate".into()))?;
+
+    Ok(if px == Fq::zero() && py == Fq::zero() {
+        G1::zero()
+    } else {
+        AffineG1::new(px, py)
+            .map_err(|_| ExitError::Other("Invalid curve point".into()))?
+            .into()
+    })
+}
+
+/// Encode a point as its affine coordinates, the point at infinity is all zero.
+fn write_point(point: bn::G1) -> Result<Vec<u8>, ExitError> {
+    use bn::AffineG1;
+
+    let mut buf = [0u8; 64];
+    if let Some(point) = AffineG1::from_jacobian(point) {
+        point.x().to_big_endian(&mut buf[0..32]).map_err(|_| {
+            ExitError::Other("Cannot fail since 0..32 is 32-byte length".into())
+        })?;
+        point.y().to_big_endian(&mut buf[32..64]).map_err(|_| {
+            ExitError::Other("Cannot fail since 32..64 is 32-byte length".into())
+        })?;
+    }
+
+    Ok(buf.to_vec())
+}
+
+/// The Bn128Add builtin, EIP-196.
+pub struct Bn128Add;
+
+impl PrecompileId for Bn128Add {
+    fn contract_id() -> u64 {
+        0x6
+    }
+}
+
+impl LinearCostPrecompile for Bn128Add {
+    const BASE: u64 = 150;
+    const WORD: u64 = 0;
+
+    fn execute(
+        input: &[u8],
+        _: u64,
+    ) -> core::result::Result<(ExitSucceed, Vec<u8>), ExitError> {
+        let p1 = read_point(input, 0)?;
+        let p2 = read_point(input, 64)?;
+
+        Ok((ExitSucceed::Returned, write_point(p1 + p2)?))
+    }
+}
+
+/// The Bn128Mul builtin, EIP-196.
+pub struct Bn128Mul;
+
+impl PrecompileId for Bn128Mul {
+    fn contract_id() -> u64 {
+        0x7
+    }
+}
+
+impl LinearCostPrecompile for Bn128Mul {
+    const BASE: u64 = 6_000;
+    const WORD: u64 = 0;
+
+    fn execute(
+        input: &[u8],
+        _: u64,
+    ) -> core::result::Result<(ExitSucceed, Vec<u8>), ExitError> {
+        let p = read_point(input, 0)?;
+        let fr = read_fr(input, 64)?;
+
+        Ok((ExitSucceed::Returned, write_point(p * fr)?))
+    }
+}
+
+/// The Bn128Pairing builtin, EIP-197.
+pub struct Bn128Pairing;
+
+impl PrecompileId for Bn128Pairing {
+    fn contract_id() -> u64 {
+        0x8
+    }
+}
+
+impl Precompile for Bn128Pairing {
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        _context: &Context,
+        _state: &FinState,
+    ) -> core::result::Result<PrecompileOutput, ExitError> {
+        let (success, cost) = pairing_check(input, target_gas)?;
+
+        let mut output = [0u8; 32];
+        output[31] = success as u8;
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost,
+            output: output.to_vec(),
+            logs: Default::default(),
+        })
+    }
+}
+
+/// Check that the product of the pairings of the input points is one,
+/// returns the result along with the gas cost.
+fn pairing_check(
+    input: &[u8],
+    target_gas: Option<u64>,
+) -> Result<(bool, u64), ExitError> {
+    use bn::{pairing_batch, AffineG1, AffineG2, Fq, Fq2, Group, Gt, G1, G2};
+
+    if input.len() % PAIR_ELEMENT_LEN != 0 {
+        return Err(ExitError::Other("bad elliptic curve pairing size".into()));
+    }
+
+    let elements = input.len() / PAIR_ELEMENT_LEN;
+    let cost = BN128_PAIRING_BASE + BN128_PAIRING_PER_POINT * elements as u64;
+    if let Some(gas_left) = target_gas {
+        if gas_left < cost {
+            return Err(ExitError::OutOfGas);
+        }
+    }
+
+    // the empty input is a valid pairing check
+    if elements == 0 {
+        return Ok((true, cost));
+    }
+
+    let read_fq = |offset: usize, err: &str| -> Result<Fq, ExitError> {
+        Fq::from_slice(&input[offset..offset + 32])
+            .map_err(|_| ExitError::Other(err.to_string().into()))
+    };
+
+    let mut pairs = Vec::with_capacity(elements);
+    for idx in 0..elements {
+        let start = idx * PAIR_ELEMENT_LEN;
+        let a_x = read_fq(start, "Invalid a argument x coordinate")?;
+        let a_y = read_fq(start + 32, "Invalid a argument y coordinate")?;
+        // the coefficients of G2 are encoded with the imaginary part first
+        let b_a_y = read_fq(
+            start + 64,
+            "Invalid b argument imaginary coeff x coordinate",
+        )?;
+        let b_a_x = read_fq(start + 96, "Invalid b argument real coeff x coordinate")?;
+        let b_b_y = read_fq(
+            start + 128,
+            "Invalid b argument imaginary coeff y coordinate",
+        )?;
+        let b_b_x = read_fq(start + 160, "Invalid b argument real coeff y coordinate")?;
+
+        let a = if a_x.is_zero() && a_y.is_zero() {
+            G1::zero()
+        } else {
+            G1::from(AffineG1::new(a_x, a_y).map_err(|_| {
+                ExitError::Other("Invalid a argument - not on curve".into())
+            })?)
+        };
+
+        let b_a = Fq2::new(b_a_x, b_a_y);
+        let b_b = Fq2::new(b_b_x, b_b_y);
+        let b = if b_a.is_zero() && b_b.is_zero() {
+            G2::zero()
+        } else {
+            G2::from(AffineG2::new(b_a, b_b).map_err(|_| {
+                ExitError::Other("Invalid b argument - not on curve".into())
+            })?)
+        };
+
+        pairs.push((a, b));
+    }
+
+    Ok((pairing_batch(&pairs) == Gt::one(), cost))
+}
@@ -0,0 +1,87 @@
+use crate::*;
+
+const G1: &str = "0000000000000000000000000000000000000000000000000000000000000001\
+                  0000000000000000000000000000000000000000000000000000000000000002";
+const G1_NEG: &str = "0000000000000000000000000000000000000000000000000000000000000001\
+                      30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45";
+const G1_DOUBLE: &str = "030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3\
+                         15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4";
+const G1_TRIPLE: &str = "0769bf9ac56bea3ff40232bcb1b6bd159315d84715b8e679f2d355961915abf0\
+                         2ab799bee0489429554fdb7c8d086475319e63b40b9c5b57cdf1ff3dd9fe2261";
+const G2: &str = "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+                  1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+                  090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+                  12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa";
+
+fn decode(input: &str) -> Vec<u8> {
+    hex::decode(input).unwrap()
+}
+
+#[test]
+fn bn128_add() {
+    let input = decode(&format!("{}{}", G1, G1));
+    let (_, output) = <Bn128Add as LinearCostPrecompile>::execute(&input, 150).unwrap();
+    assert_eq!(output, decode(G1_DOUBLE));
+
+    let input = decode(&format!("{}{}", G1_DOUBLE, G1));
+    let (_, output) = <Bn128Add as LinearCostPrecompile>::execute(&input, 150).unwrap();
+    assert_eq!(output, decode(G1_TRIPLE));
+
+    // P + (-P) is the point at infinity
+    let input = decode(&format!("{}{}", G1, G1_NEG));
+    let (_, output) = <Bn128Add as LinearCostPrecompile>::execute(&input, 150).unwrap();
+    assert_eq!(output, vec![0u8; 64]);
+
+    // missing input is padded with zeros
+    let (_, output) = <Bn128Add as LinearCostPrecompile>::execute(&[], 150).unwrap();
+    assert_eq!(output, vec![0u8; 64]);
+}
+
+#[test]
+fn bn128_add_invalid_point() {
+    let input = decode(&format!("{}{}", G1_DOUBLE, G1).replacen("03", "04", 1));
+    assert_eq!(
+        <Bn128Add as LinearCostPrecompile>::execute(&input, 150),
+        Err(ExitError::Other("Invalid curve point".into()))
+    );
+}
+
+#[test]
+fn bn128_mul() {
+    let scalar = format!("{:064x}", 3);
+    let input = decode(&format!("{}{}", G1, scalar));
+    let (_, output) = <Bn128Mul as LinearCostPrecompile>::execute(&input, 6000).unwrap();
+    assert_eq!(output, decode(G1_TRIPLE));
+
+    let scalar = format!("{:064x}", 0);
+    let input = decode(&format!("{}{}", G1, scalar));
+    let (_, output) = <Bn128Mul as LinearCostPrecompile>::execute(&input, 6000).unwrap();
+    assert_eq!(output, vec![0u8; 64]);
+}
+
+#[test]
+fn bn128_pairing() {
+    // e(P, Q) * e(-P, Q) == 1
+    let input = decode(&format!("{}{}{}{}", G1, G2, G1_NEG, G2));
+    assert_eq!(pairing_check(&input, None), Ok((true, 45_000 + 2 * 34_000)));
+
+    // e(P, Q) != 1
+    let input = decode(&format!("{}{}", G1, G2));
+    assert_eq!(pairing_check(&input, None), Ok((false, 45_000 + 34_000)));
+
+    assert_eq!(pairing_check(&[], None), Ok((true, 45_000)));
+}
+
+#[test]
+fn bn128_pairing_invalid_input() {
+    assert_eq!(
+        pairing_check(&[0u8; 100], None),
+        Err(ExitError::Other("bad elliptic curve pairing size".into()))
+    );
+
+    let input = decode(&format!("{}{}", G1, G2));
+    assert_eq!(
+        pairing_check(&input, Some(50_000)),
+        Err(ExitError::OutOfGas)
+    );
+}
@@ -0,0 +1,402 @@
+use crate::{
+    Call, APPROVAL_EVENT_SELECTOR, GAS_ALLOWANCE, GAS_APPROVE, GAS_BALANCE_OF,
+    GAS_DECIMALS, GAS_NAME, GAS_SYMBOL, GAS_TOTAL_SUPPLY, GAS_TRANSFER,
+    GAS_TRANSFER_FROM, TRANSFER_EVENT_SELECTOR,
+};
+use core::marker::PhantomData;
+use ethereum_types::{H160, H256, U256};
+use evm::{executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use evm_precompile_utils::{
+    error, Address, EvmDataReader, EvmDataWriter, EvmResult, Gasometer, LogsBuilder,
+};
+use fp_traits::{account::CustomAsset, evm::AddressMapping};
+use fp_types::assets::{is_asset_address, AssetMetadata, ASSET_ADDRESS_PREFIX};
+use log::debug;
+use module_evm::{
+    precompile::{FinState, Precompile, PrecompileId},
+    Config,
+};
+
+/// FRC20 precompiles of the custom assets, one per asset at the address given by
+/// `fp_types::assets::asset_address`.
+pub struct FRC20Assets<C> {
+    _marker: PhantomData<C>,
+}
+
+impl<C: Config> PrecompileId for FRC20Assets<C> {
+    /// The prefix of the asset addresses.
+    fn contract_id() -> u64 {
+        u32::from_be_bytes(ASSET_ADDRESS_PREFIX) as u64
+    }
+
+    /// Only the addresses of the registered assets.
+    fn is_precompile(address: H160, state: &FinState) -> bool {
+        is_asset_address(&address)
+            && C::CustomAsset::asset_of_address(state, &address).is_some()
+    }
+}
+
+impl<C: Config> Precompile for FRC20Assets<C> {
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        context: &Context,
+        state: &FinState,
+    ) -> Result<PrecompileOutput, ExitError> {
+        let asset = C::CustomAsset::asset_of_address(state, &context.address)
+            .ok_or_else(|| error("FRC20: unknown asset"))?;
+        let mut input = EvmDataReader::new(input);
+
+        match &input.read_selector()? {
+            Call::Name => Self::name(state, &asset, input, target_gas),
+            Call::Symbol => Self::symbol(state, &asset, input, target_gas),
+            Call::Decimals => Self::decimals(state, &asset, input, target_gas),
+            Call::TotalSupply => Self::total_supply(state, &asset, input, target_gas),
+            Call::BalanceOf => Self::balance_of(state, &asset, input, target_gas),
+            Call::Allowance => Self::allowance(state, &asset, input, target_gas),
+            Call::Approve => Self::approve(state, &asset, input, target_gas, context),
+            Call::Transfer => Self::transfer(state, &asset, input, target_gas, context),
+            Call::TransferFrom => {
+                Self::transfer_from(state, &asset, input, target_gas, context)
+            }
+        }
+    }
+}
+
+impl<C: Config> FRC20Assets<C> {
+    fn metadata(state: &FinState, asset: &H256) -> EvmResult<AssetMetadata> {
+        C::CustomAsset::asset_metadata(state, asset)
+            .ok_or_else(|| error("FRC20: unknown asset"))
+    }
+
+    /// Returns the name of the token.
+    fn name(
+        state: &FinState,
+        asset: &H256,
+        input: EvmDataReader,
+        target_gas: Option<u64>,
+    ) -> EvmResult<PrecompileOutput> {
+        let mut gasometer = Gasometer::new(target_gas);
+        gasometer.record_cost(GAS_NAME)?;
+
+        input.expect_arguments(0)?;
+
+        let name = Self::metadata(state, asset)?.name;
+        debug!(target: "evm", "FRC20Assets#name: {:?}, {}", asset, name);
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost: gasometer.used_gas(),
+            output: encode_string(&name),
+            logs: vec![],
+        })
+    }
+
+    /// Returns the symbol of the token, usually a shorter version of the name.
+    fn symbol(
+        state: &FinState,
+        asset: &H256,
+        input: EvmDataReader,
+        target_gas: Option<u64>,
+    ) -> EvmResult<PrecompileOutput> {
+        let mut gasometer = Gasometer::new(target_gas);
+        gasometer.record_cost(GAS_SYMBOL)?;
+
+        input.expect_arguments(0)?;
+
+        let symbol = Self::metadata(state, asset)?.symbol;
+        debug!(target: "evm", "FRC20Assets#symbol: {:?}, {}", asset, symbol);
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost: gasometer.used_gas(),
+            output: encode_string(&symbol),
+            logs: vec![],
+        })
+    }
+
+    /// Returns the number of decimals used to get its user representation,
+    /// the one of the asset rules.
+    fn decimals(
+        state: &FinState,
+        asset: &H256,
+        input: EvmDataReader,
+        target_gas: Option<u64>,
+    ) -> EvmResult<PrecompileOutput> {
+        let mut gasometer = Gasometer::new(target_gas);
+        gasometer.record_cost(GAS_DECIMALS)?;
+
+        input.expect_arguments(0)?;
+
+        let decimals = Self::metadata(state, asset)?.decimals;
+        debug!(target: "evm", "FRC20Assets#decimals: {:?}, {}", asset, decimals);
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost: gasometer.used_gas(),
+            output: EvmDataWriter::new().write(decimals).build(),
+            logs: vec![],
+        })
+    }
+
+    /// Returns the amount of tokens in existence.
+    fn total_supply(
+        state: &FinState,
+        asset: &H256,
+        input: EvmDataReader,
+        target_gas: Option<u64>,
+    ) -> EvmResult<PrecompileOutput> {
+        let mut gasometer = Gasometer::new(target_gas);
+        gasometer.record_cost(GAS_TOTAL_SUPPLY)?;
+
+        input.expect_arguments(0)?;
+
+        let amount: U256 = C::CustomAsset::asset_total_issuance(state, asset);
+        debug!(target: "evm", "FRC20Assets#total_supply: {:?}, {:?}", asset, amount);
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost: gasometer.used_gas(),
+            output: EvmDataWriter::new().write(amount).build(),
+            logs: vec![],
+        })
+    }
+
+    /// Returns the amount of tokens owned by `owner`.
+    fn balance_of(
+        state: &FinState,
+        asset: &H256,
+        mut input: EvmDataReader,
+        target_gas: Option<u64>,
+    ) -> EvmResult<PrecompileOutput> {
+        let mut gasometer = Gasometer::new(target_gas);
+        gasometer.record_cost(GAS_BALANCE_OF)?;
+
+        input.expect_arguments(1)?;
+
+        let owner: H160 = input.read::<Address>()?.into();
+        let owner_id = C::AddressMapping::convert_to_account_id(owner);
+        let amount: U256 = C::CustomAsset::asset_balance(state, asset, &owner_id);
+        debug!(target: "evm",
+            "FRC20Assets#balance_of: {:?}, owner: {:?}, amount: {:?}",
+            asset, owner, amount
+        );
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost: gasometer.used_gas(),
+            output: EvmDataWriter::new().write(amount).build(),
+            logs: vec![],
+        })
+    }
+
+    /// Returns the remaining number of tokens that `spender` will be allowed to spend on behalf
+    /// of `owner` through {transferFrom}. This is zero by default.
+    fn allowance(
+        state: &FinState,
+        asset: &H256,
+        mut input: EvmDataReader,
+        target_gas: Option<u64>,
+    ) -> EvmResult<PrecompileOutput> {
+        let mut gasometer = Gasometer::new(target_gas);
+        gasometer.record_cost(GAS_ALLOWANCE)?;
+
+        input.expect_arguments(2)?;
+
+        let owner: H160 = input.read::<Address>()?.into();
+        let owner_id = C::AddressMapping::convert_to_account_id(owner);
+        let spender: H160 = input.read::<Address>()?.into();
+        let spender_id = C::AddressMapping::convert_to_account_id(spender);
+        let amount: U256 =
+            C::CustomAsset::asset_allowance(state, asset, &owner_id, &spender_id);
+        debug!(target: "evm",
+            "FRC20Assets#allowance: {:?}, owner: {:?}, spender: {:?}, allowance: {:?}",
+            asset, owner, spender, amount
+        );
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost: gasometer.used_gas(),
+            output: EvmDataWriter::new().write(amount).build(),
+            logs: vec![],
+        })
+    }
+
+    /// Sets `amount` as the allowance of `spender` over the caller's tokens.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    fn approve(
+        state: &FinState,
+        asset: &H256,
+        mut input: EvmDataReader,
+        target_gas: Option<u64>,
+        context: &Context,
+    ) -> EvmResult<PrecompileOutput> {
+        let mut gasometer = Gasometer::new(target_gas);
+        gasometer.record_cost(GAS_APPROVE)?;
+        gasometer.record_log_costs_manual(3, 32)?;
+
+        input.expect_arguments(2)?;
+
+        let caller = C::AddressMapping::convert_to_account_id(context.caller);
+        let spender: H160 = input.read::<Address>()?.into();
+        if spender == H160::zero() {
+            return Err(error("FRC20: approve to the zero address"));
+        }
+        let spender_id = C::AddressMapping::convert_to_account_id(spender);
+        let amount: U256 = input.read()?;
+        debug!(target: "evm",
+            "FRC20Assets#approve: {:?}, sender: {:?}, spender: {:?}, amount: {:?}",
+            asset, context.caller, spender, amount
+        );
+
+        C::CustomAsset::asset_approve(state, asset, &caller, &spender_id, amount)
+            .map_err(|e| error(format!("{:?}", e)))?;
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost: gasometer.used_gas(),
+            output: EvmDataWriter::new().write(true).build(),
+            logs: LogsBuilder::new(context.address)
+                .log3(
+                    APPROVAL_EVENT_SELECTOR,
+                    context.caller,
+                    spender,
+                    EvmDataWriter::new().write(amount).build(),
+                )
+                .build(),
+        })
+    }
+
+    /// Moves `amount` tokens from the caller's account to `recipient`.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    fn transfer(
+        state: &FinState,
+        asset: &H256,
+        mut input: EvmDataReader,
+        target_gas: Option<u64>,
+        context: &Context,
+    ) -> EvmResult<PrecompileOutput> {
+        let mut gasometer = Gasometer::new(target_gas);
+        gasometer.record_cost(GAS_TRANSFER)?;
+        gasometer.record_log_costs_manual(3, 32)?;
+
+        input.expect_arguments(2)?;
+
+        let caller = C::AddressMapping::convert_to_account_id(context.caller);
+        let recipient: H160 = input.read::<Address>()?.into();
+        if recipient == H160::zero() {
+            return Err(error("FRC20: transfer to the zero address"));
+        }
+        let recipient_id = C::AddressMapping::convert_to_account_id(recipient);
+        let amount: U256 = input.read()?;
+        debug!(target: "evm",
+            "FRC20Assets#transfer: {:?}, sender: {:?}, to: {:?}, amount: {:?}",
+            asset, context.caller, recipient, amount
+        );
+
+        C::CustomAsset::asset_transfer(state, asset, &caller, &recipient_id, amount)
+            .map_err(|e| error(format!("{:?}", e)))?;
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost: gasometer.used_gas(),
+            output: EvmDataWriter::new().write(true).build(),
+            logs: LogsBuilder::new(context.address)
+                .log3(
+                    TRANSFER_EVENT_SELECTOR,
+                    context.caller,
+                    recipient,
+                    EvmDataWriter::new().write(amount).build(),
+                )
+                .build(),
+        })
+    }
+
+    /// Moves `amount` tokens from `sender` to `recipient` using the allowance mechanism.
+    /// `amount` is then deducted from the caller's allowance.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    fn transfer_from(
+        state: &FinState,
+        asset: &H256,
+        mut input: EvmDataReader,
+        target_gas: Option<u64>,
+        context: &Context,
+    ) -> EvmResult<PrecompileOutput> {
+        let mut gasometer = Gasometer::new(target_gas);
+        gasometer.record_cost(GAS_TRANSFER_FROM)?;
+        gasometer.record_log_costs_manual(3, 32)?;
+        gasometer.record_log_costs_manual(3, 32)?;
+
+        input.expect_arguments(3)?;
+
+        let caller = C::AddressMapping::convert_to_account_id(context.caller);
+        let from: H160 = input.read::<Address>()?.into();
+        if from == H160::zero() {
+            return Err(error("FRC20: transfer from the zero address"));
+        }
+        let from_id = C::AddressMapping::convert_to_account_id(from);
+        let recipient: H160 = input.read::<Address>()?.into();
+        if recipient == H160::zero() {
+            return Err(error("FRC20: transfer to the zero address"));
+        }
+        let recipient_id = C::AddressMapping::convert_to_account_id(recipient);
+        let amount: U256 = input.read()?;
+        let allowance = C::CustomAsset::asset_allowance(state, asset, &from_id, &caller);
+        if allowance < amount {
+            return Err(error("FRC20: transfer amount exceeds allowance"));
+        }
+        debug!(target: "evm",
+            "FRC20Assets#transfer_from: {:?}, sender: {:?}, from: {:?}, to: {:?}, amount: {:?}",
+            asset, context.caller, from, recipient, amount
+        );
+
+        C::CustomAsset::asset_transfer(state, asset, &from_id, &recipient_id, amount)
+            .map_err(|e| error(format!("{:?}", e)))?;
+
+        C::CustomAsset::asset_approve(
+            state,
+            asset,
+            &from_id,
+            &caller,
+            allowance.saturating_sub(amount),
+        )
+        .map_err(|e| error(format!("{:?}", e)))?;
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost: gasometer.used_gas(),
+            output: EvmDataWriter::new().write(true).build(),
+            logs: LogsBuilder::new(context.address)
+                .log3(
+                    TRANSFER_EVENT_SELECTOR,
+                    from,
+                    recipient,
+                    EvmDataWriter::new().write(amount).build(),
+                )
+                .log3(
+                    APPROVAL_EVENT_SELECTOR,
+                    from,
+                    context.caller,
+                    EvmDataWriter::new()
+                        .write(allowance.saturating_sub(amount))
+                        .build(),
+                )
+                .build(),
+        })
+    }
+}
+
+/// ABI encoding of a string return value.
+pub(crate) fn encode_string(value: &str) -> Vec<u8> {
+    let mut data = value.as_bytes().to_vec();
+    data.resize((data.len() + 31) / 32 * 32, 0);
+
+    EvmDataWriter::new()
+        .write(U256::from(32))
+        .write(U256::from(value.len()))
+        .write_raw_bytes(&data)
+        .build()
+}
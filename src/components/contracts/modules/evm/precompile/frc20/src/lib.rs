@@ -1,6 +1,9 @@
+mod assets;
 #[cfg(test)]
 mod tests;
 
+pub use assets::FRC20Assets;
+
 use core::marker::PhantomData;
 use ethereum_types::{H160, U256};
 use evm::{executor::PrecompileOutput, Context, ExitError, ExitSucceed};
@@ -2,7 +2,10 @@ use crate::*;
 use baseapp::BaseApp;
 use fp_mocks::*;
 
+use ethereum_types::H256;
 use evm_precompile_utils::{error, EvmDataWriter, LogsBuilder};
+use fp_traits::account::CustomAsset;
+use fp_types::assets::AssetMetadata;
 use module_evm::precompile::Precompile;
 use sha3::{Digest, Keccak256};
 
@@ -273,3 +276,151 @@ fn transfer_from_works() {
 
     allowance(ALICE_ECDSA.address, BOB_ECDSA.address, U256::from(100));
 }
+
+#[test]
+fn encode_string_works() {
+    assert_eq!(assets::encode_string("Findora"), FRC20_NAME.to_vec());
+    assert_eq!(assets::encode_string("FRA"), FRC20_SYMBOL.to_vec());
+}
+
+#[test]
+fn frc20_assets_works() {
+    let metadata = AssetMetadata {
+        asset: H256::repeat_byte(9),
+        name: "Findora Test Asset".to_string(),
+        symbol: "FTA".to_string(),
+        decimals: 4,
+    };
+    let address = metadata.address();
+    {
+        let ctx = &BASE_APP.lock().unwrap().deliver_state;
+        module_account::App::<BaseApp>::set_asset_metadata(ctx, &metadata).unwrap();
+        module_account::App::<BaseApp>::asset_mint(
+            ctx,
+            &metadata.asset,
+            &ALICE_ECDSA.account_id,
+            U256::from(1000),
+        )
+        .unwrap();
+    }
+    {
+        let state = &BASE_APP.lock().unwrap().deliver_state;
+        assert!(FRC20Assets::<BaseApp>::is_precompile(address, state));
+        assert!(!FRC20Assets::<BaseApp>::is_precompile(
+            H160::from_low_u64_be(FRC20_PRECOMPILE_ADDRESS),
+            state
+        ));
+        // an asset address of no registered asset
+        assert!(!FRC20Assets::<BaseApp>::is_precompile(
+            AssetMetadata {
+                asset: H256::repeat_byte(8),
+                ..metadata.clone()
+            }
+            .address(),
+            state
+        ));
+    }
+
+    let call = |input: Vec<u8>, caller: H160, address: H160| {
+        FRC20Assets::<BaseApp>::execute(
+            &input,
+            None,
+            &evm::Context {
+                address,
+                caller,
+                apparent_value: From::from(0),
+            },
+            &BASE_APP.lock().unwrap().deliver_state,
+        )
+    };
+
+    assert_eq!(
+        call(
+            EvmDataWriter::new().write_selector(Call::Name).build(),
+            ALICE_ECDSA.address,
+            H160::repeat_byte(0xff),
+        ),
+        Err(error("FRC20: unknown asset"))
+    );
+    assert_eq!(
+        call(
+            EvmDataWriter::new().write_selector(Call::Symbol).build(),
+            ALICE_ECDSA.address,
+            address,
+        ),
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            output: assets::encode_string("FTA"),
+            cost: GAS_SYMBOL,
+            logs: Default::default(),
+        })
+    );
+    assert_eq!(
+        call(
+            EvmDataWriter::new().write_selector(Call::Decimals).build(),
+            ALICE_ECDSA.address,
+            address,
+        ),
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            output: EvmDataWriter::new().write(4_u8).build(),
+            cost: GAS_DECIMALS,
+            logs: Default::default(),
+        })
+    );
+    assert_eq!(
+        call(
+            EvmDataWriter::new()
+                .write_selector(Call::Transfer)
+                .write(Address(BOB_ECDSA.address))
+                .write(U256::from(400))
+                .build(),
+            ALICE_ECDSA.address,
+            address,
+        ),
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            output: EvmDataWriter::new().write(true).build(),
+            cost: GAS_TRANSFER + 1756,
+            logs: LogsBuilder::new(address)
+                .log3(
+                    TRANSFER_EVENT_SELECTOR,
+                    ALICE_ECDSA.address,
+                    BOB_ECDSA.address,
+                    EvmDataWriter::new().write(U256::from(400)).build(),
+                )
+                .build(),
+        })
+    );
+    assert_eq!(
+        call(
+            EvmDataWriter::new()
+                .write_selector(Call::BalanceOf)
+                .write(Address(BOB_ECDSA.address))
+                .build(),
+            ALICE_ECDSA.address,
+            address,
+        ),
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            output: EvmDataWriter::new().write(U256::from(400)).build(),
+            cost: GAS_BALANCE_OF,
+            logs: Default::default(),
+        })
+    );
+    assert_eq!(
+        call(
+            EvmDataWriter::new()
+                .write_selector(Call::TotalSupply)
+                .build(),
+            ALICE_ECDSA.address,
+            address,
+        ),
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            output: EvmDataWriter::new().write(U256::from(1000)).build(),
+            cost: GAS_TOTAL_SUPPLY,
+            logs: Default::default(),
+        })
+    );
+}
@@ -5,19 +5,24 @@ use fp_core::context::Context;
 use fp_evm::Account;
 use fp_storage::{Borrow, BorrowMut};
 use fp_traits::{
-    account::AccountAsset,
+    account::{AccountAsset, CustomAsset},
     evm::{AddressMapping, OnChargeEVMTransaction},
 };
-use fp_types::crypto::{HA160, HA256};
-use fp_utils::proposer_converter;
+use fp_types::{
+    assets::is_asset_address,
+    crypto::{HA160, HA256},
+};
 use ruc::Result;
 
 impl<C: Config> App<C> {
     /// Check whether an account is empty.
     pub fn is_account_empty(ctx: &Context, address: &HA160) -> bool {
         let account = Self::account_basic(ctx, address);
-        let code_len =
-            AccountCodes::decode_len(ctx.state.read().borrow(), address).unwrap_or(0);
+        let code_len = if ctx.version.is_none() {
+            AccountCodes::decode_len(ctx.state.read().borrow(), address).unwrap_or(0)
+        } else {
+            Self::account_codes(ctx, address, None).map_or(0, |code| code.len())
+        };
 
         account.nonce == U256::zero() && account.balance == U256::zero() && code_len == 0
     }
@@ -45,12 +50,21 @@ impl<C: Config> App<C> {
         if address.0 == H160::from_low_u64_be(0x1000) {
             return Some(b"fra".to_vec());
         }
+        if is_asset_address(&address.0)
+            && C::CustomAsset::asset_of_address(ctx, &address.0).is_some()
+        {
+            return Some(b"frc20".to_vec());
+        }
 
-        let version = height.unwrap_or(0);
+        let version = ctx.version_of(height);
         if version == 0 {
             AccountCodes::get_bytes(ctx.state.read().borrow(), address)
         } else {
-            AccountCodes::get_ver_bytes(ctx.state.read().borrow(), address, version)
+            AccountCodes::get_session_or_ver_bytes(
+                ctx.state.read().borrow(),
+                address,
+                version,
+            )
         }
     }
 
@@ -61,11 +75,16 @@ impl<C: Config> App<C> {
         index: &HA256,
         height: Option<u64>,
     ) -> Option<H256> {
-        let version = height.unwrap_or(0);
+        let version = ctx.version_of(height);
         if version == 0 {
             AccountStorages::get(ctx.state.read().borrow(), address, index)
         } else {
-            AccountStorages::get_ver(ctx.state.read().borrow(), address, index, version)
+            AccountStorages::get_session_or_ver(
+                ctx.state.read().borrow(),
+                address,
+                index,
+                version,
+            )
         }
     }
 
@@ -80,8 +99,7 @@ impl<C: Config> App<C> {
 
     /// Get the block proposer.
     pub fn find_proposer(ctx: &Context) -> H160 {
-        // TODO
-        proposer_converter(ctx.header.proposer_address.clone()).unwrap_or_default()
+        ctx.proposer_evm_address()
     }
 }
 
@@ -90,7 +108,6 @@ impl<C: Config> App<C> {
 /// `OnUnbalanced`).
 impl<C: Config> OnChargeEVMTransaction for App<C> {
     fn withdraw_fee(ctx: &Context, who: &H160, fee: U256) -> Result<()> {
-        // TODO fee pay to block author
         let account_id = C::AddressMapping::convert_to_account_id(*who);
         C::AccountAsset::withdraw(ctx, &account_id, fee)
     }
@@ -103,6 +120,14 @@ impl<C: Config> OnChargeEVMTransaction for App<C> {
     ) -> Result<()> {
         let account_id = C::AddressMapping::convert_to_account_id(*who);
         C::AccountAsset::refund(ctx, &account_id, already_withdrawn)?;
-        C::AccountAsset::burn(ctx, &account_id, corrected_fee)
+
+        // The fee goes to the staking account of the block proposer if known,
+        // which it spends with its staking key, or it is burnt.
+        match ctx.block_proposer.as_ref() {
+            Some(proposer) => {
+                C::AccountAsset::transfer(ctx, &account_id, proposer, corrected_fee)
+            }
+            None => C::AccountAsset::burn(ctx, &account_id, corrected_fee),
+        }
     }
 }
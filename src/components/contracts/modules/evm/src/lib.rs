@@ -16,7 +16,7 @@ use fp_core::{
 };
 use fp_storage::Borrow;
 use fp_traits::{
-    account::AccountAsset,
+    account::{AccountAsset, CustomAsset},
     evm::{AddressMapping, BlockHashMapping, DecimalsMapping, FeeCalculator},
 };
 use fp_types::{
@@ -34,6 +34,8 @@ pub const MODULE_NAME: &str = "evm";
 pub trait Config {
     /// Account module interface to read/write account assets.
     type AccountAsset: AccountAsset<Address>;
+    /// Account module interface to read/write the custom assets.
+    type CustomAsset: CustomAsset<Address>;
     /// Mapping from address to account id.
     type AddressMapping: AddressMapping;
     /// The block gas limit. Can be a simple constant, or an adjustment algorithm in another pallet.
@@ -46,7 +48,8 @@ pub trait Config {
     type DecimalsMapping: DecimalsMapping;
     /// Calculator for current gas price.
     type FeeCalculator: FeeCalculator;
-    /// Precompiles associated with this EVM engine.
+    /// Precompiles associated with this EVM engine, each one is available from
+    /// its activation height.
     type Precompiles: PrecompileSet;
 }
 
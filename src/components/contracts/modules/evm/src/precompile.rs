@@ -1,7 +1,9 @@
 use crate::runtime::stack::FindoraStackState;
 use ethereum_types::H160;
 use evm::{executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use fp_core::macros::Get;
 use impl_trait_for_tuples::impl_for_tuples;
+use std::marker::PhantomData;
 
 pub use fp_core::context::Context as FinState;
 
@@ -41,9 +43,78 @@ pub trait Precompile {
 pub trait PrecompileId {
     /// Return the precompiled contract id.
     fn contract_id() -> u64;
+
+    /// Whether the precompile serves `address` in `state`,
+    /// by default the one of its contract id.
+    fn is_precompile(address: H160, _state: &FinState) -> bool {
+        address == H160::from_low_u64_be(Self::contract_id())
+    }
+
+    /// Return the block height from which the precompile is available.
+    fn activation_height() -> i64 {
+        0
+    }
+}
+
+/// Register the precompile `P` from the block height given by `H`.
+///
+/// The precompile address behaves as a plain account before the activation
+/// height, so that replaying older blocks is not affected by new precompiles.
+pub struct ActivatedAt<P, H>(PhantomData<(P, H)>);
+
+impl<P: PrecompileId, H: Get<i64>> PrecompileId for ActivatedAt<P, H> {
+    fn contract_id() -> u64 {
+        P::contract_id()
+    }
+
+    fn is_precompile(address: H160, state: &FinState) -> bool {
+        P::is_precompile(address, state)
+    }
+
+    fn activation_height() -> i64 {
+        H::get()
+    }
+}
+
+impl<P: Precompile, H> Precompile for ActivatedAt<P, H> {
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        context: &Context,
+        state: &FinState,
+    ) -> core::result::Result<PrecompileOutput, ExitError> {
+        P::execute(input, target_gas, context, state)
+    }
+}
+
+/// Register the precompile `P` under the contract id given by `I`, to move
+/// a precompile whose address is taken by a standard one.
+pub struct Relocated<P, I>(PhantomData<(P, I)>);
+
+impl<P: PrecompileId, I: Get<u64>> PrecompileId for Relocated<P, I> {
+    fn contract_id() -> u64 {
+        I::get()
+    }
+
+    fn activation_height() -> i64 {
+        P::activation_height()
+    }
+}
+
+impl<P: Precompile, I> Precompile for Relocated<P, I> {
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        context: &Context,
+        state: &FinState,
+    ) -> core::result::Result<PrecompileOutput, ExitError> {
+        P::execute(input, target_gas, context, state)
+    }
 }
 
-#[impl_for_tuples(16)]
+/// The precompiles are matched in order, so a precompile takes over the address
+/// of the following ones once activated.
+#[impl_for_tuples(32)]
 #[tuple_types_no_default_trait_bound]
 impl PrecompileSet for Tuple {
     for_tuples!( where #( Tuple: Precompile + PrecompileId )* );
@@ -56,8 +127,10 @@ impl PrecompileSet for Tuple {
         state: &mut FindoraStackState<'context, 'vicinity, 'config, T>,
         _is_static: bool,
     ) -> Option<core::result::Result<PrecompileOutput, ExitError>> {
+        let height = state.ctx.header.height;
         for_tuples!( #(
-            if address == H160::from_low_u64_be(Tuple::contract_id()) {
+            if height >= Tuple::activation_height() && Tuple::is_precompile(address, state.ctx)
+            {
                 return Some(Tuple::execute(input, target_gas, context, state.ctx))
             }
         )* );
@@ -117,3 +190,37 @@ fn ensure_linear_cost(
 
     Ok(cost)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fp_core::parameter_types;
+    use fp_mocks::BASE_APP;
+
+    struct Dummy;
+
+    impl PrecompileId for Dummy {
+        fn contract_id() -> u64 {
+            0x42
+        }
+    }
+
+    parameter_types! {
+        pub const ForkHeight: i64 = 100;
+        pub const NewId: u64 = 0x400;
+    }
+
+    #[test]
+    fn test_activated_precompile() {
+        assert_eq!(Dummy::activation_height(), 0);
+        assert_eq!(ActivatedAt::<Dummy, ForkHeight>::contract_id(), 0x42);
+        assert_eq!(ActivatedAt::<Dummy, ForkHeight>::activation_height(), 100);
+
+        type Moved = ActivatedAt<Relocated<Dummy, NewId>, ForkHeight>;
+        assert_eq!(Moved::contract_id(), 0x400);
+        assert_eq!(Moved::activation_height(), 100);
+        let state = BASE_APP.lock().unwrap().deliver_state.clone();
+        assert!(Moved::is_precompile(H160::from_low_u64_be(0x400), &state));
+        assert!(!Moved::is_precompile(H160::from_low_u64_be(0x42), &state));
+    }
+}
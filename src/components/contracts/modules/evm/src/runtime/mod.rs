@@ -1,2 +1,3 @@
 pub mod runner;
 pub mod stack;
+pub mod tracer;
@@ -14,6 +14,20 @@ use ruc::*;
 use sha3::{Digest, Keccak256};
 use std::marker::PhantomData;
 
+/// Gas charged per address in an EIP-2930 access list.
+const ACCESS_LIST_ADDRESS_GAS: u64 = 2400;
+/// Gas charged per storage key in an EIP-2930 access list.
+const ACCESS_LIST_STORAGE_KEY_GAS: u64 = 1900;
+
+/// Intrinsic gas of an EIP-2930 access list.
+pub fn access_list_gas(access_list: &[(H160, Vec<H256>)]) -> u64 {
+    access_list.iter().fold(0, |gas, (_, keys)| {
+        gas.saturating_add(ACCESS_LIST_ADDRESS_GAS).saturating_add(
+            ACCESS_LIST_STORAGE_KEY_GAS.saturating_mul(keys.len() as u64),
+        )
+    })
+}
+
 #[derive(Default)]
 pub struct ActionRunner<C: Config> {
     _marker: PhantomData<C>,
@@ -29,6 +43,7 @@ impl<C: Config> ActionRunner<C> {
         gas_limit: u64,
         gas_price: Option<U256>,
         nonce: Option<U256>,
+        access_list: &[(H160, Vec<H256>)],
         config: &'config evm::Config,
         f: F,
     ) -> Result<ExecutionInfo<R>>
@@ -54,7 +69,12 @@ impl<C: Config> ActionRunner<C> {
             origin: source,
         };
 
-        let metadata = StackSubstateMetadata::new(gas_limit, config);
+        // The access list is charged up front as part of the intrinsic gas,
+        // the remaining gas is left to the execution.
+        let access_list_gas = access_list_gas(access_list);
+        ensure!(access_list_gas <= gas_limit, "OutOfGas: access list");
+
+        let metadata = StackSubstateMetadata::new(gas_limit - access_list_gas, config);
         let state = FindoraStackState::new(ctx, &vicinity, metadata);
         let mut executor =
             StackExecutor::new_with_precompile(state, config, C::Precompiles::execute);
@@ -86,8 +106,8 @@ impl<C: Config> ActionRunner<C> {
         // Execute the EVM call.
         let (reason, retv) = f(&mut executor);
 
-        let used_gas = U256::from(executor.used_gas());
-        let actual_fee = executor.fee(gas_price);
+        let used_gas = U256::from(executor.used_gas() + access_list_gas);
+        let actual_fee = used_gas.saturating_mul(gas_price);
         log::debug!(
             target: "evm",
             "Execution {:?} [source: {:?}, value: {}, gas_price {}, gas_limit: {}, actual_fee: {}]",
@@ -145,6 +165,7 @@ impl<C: Config> Runner for ActionRunner<C> {
             args.gas_limit,
             args.gas_price,
             args.nonce,
+            &args.access_list,
             config,
             |executor| {
                 executor.transact_call(
@@ -166,6 +187,7 @@ impl<C: Config> Runner for ActionRunner<C> {
             args.gas_limit,
             args.gas_price,
             args.nonce,
+            &args.access_list,
             config,
             |executor| {
                 let address = executor.create_address(evm::CreateScheme::Legacy {
@@ -197,6 +219,7 @@ impl<C: Config> Runner for ActionRunner<C> {
             args.gas_limit,
             args.gas_price,
             args.nonce,
+            &args.access_list,
             config,
             |executor| {
                 let address = executor.create_address(evm::CreateScheme::Create2 {
@@ -0,0 +1,486 @@
+//! EVM execution tracer, records the opcode level struct logs and the call tree
+//! of an execution through the `tracing` hooks of the evm crates.
+
+use ethereum_types::{H160, H256, U256};
+use evm::{CreateScheme, ExitError, ExitReason, Opcode};
+use evm_gasometer::tracing as gas_tracing;
+use evm_runtime::tracing as runtime_tracing;
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+/// Options of the struct logger.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TraceConfig {
+    /// Collect the opcode level struct logs, the call tree is always collected.
+    pub struct_logs: bool,
+    pub disable_storage: bool,
+    pub disable_memory: bool,
+    pub disable_stack: bool,
+}
+
+/// One executed opcode.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StructLog {
+    pub pc: u64,
+    pub op: u8,
+    pub gas: u64,
+    pub gas_cost: u64,
+    pub depth: u64,
+    pub stack: Option<Vec<H256>>,
+    pub memory: Option<Vec<u8>>,
+    pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallType {
+    Call,
+    CallCode,
+    DelegateCall,
+    StaticCall,
+    Create,
+    Create2,
+    SelfDestruct,
+}
+
+impl CallType {
+    /// The name used by the geth `callTracer`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CallType::Call => "CALL",
+            CallType::CallCode => "CALLCODE",
+            CallType::DelegateCall => "DELEGATECALL",
+            CallType::StaticCall => "STATICCALL",
+            CallType::Create => "CREATE",
+            CallType::Create2 => "CREATE2",
+            CallType::SelfDestruct => "SELFDESTRUCT",
+        }
+    }
+}
+
+/// One frame of the call tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallFrame {
+    pub call_type: CallType,
+    pub from: H160,
+    pub to: H160,
+    pub value: U256,
+    pub gas: u64,
+    pub gas_used: u64,
+    pub input: Vec<u8>,
+    pub output: Vec<u8>,
+    pub error: Option<String>,
+    pub calls: Vec<CallFrame>,
+}
+
+/// Everything collected during a traced execution.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TraceResult {
+    pub struct_logs: Vec<StructLog>,
+    pub call: Option<CallFrame>,
+}
+
+#[derive(Default)]
+struct TraceState {
+    config: TraceConfig,
+    struct_logs: Vec<StructLog>,
+    // Whether the gas of the last struct log is already recorded.
+    step_gas_recorded: bool,
+    storages: BTreeMap<H160, BTreeMap<H256, H256>>,
+    // Remaining gas seen by the gasometer of the innermost frame.
+    gas_left: u64,
+    frames: Vec<CallFrame>,
+    root: Option<CallFrame>,
+}
+
+impl TraceState {
+    fn enter(&mut self, frame: CallFrame) {
+        self.frames.push(frame);
+    }
+
+    fn exit(&mut self, reason: &ExitReason, return_value: &[u8]) {
+        if let Some(mut frame) = self.frames.pop() {
+            frame.gas_used = frame.gas.saturating_sub(self.gas_left);
+            frame.output = return_value.to_vec();
+            frame.error = exit_error(reason);
+            match self.frames.last_mut() {
+                Some(parent) => parent.calls.push(frame),
+                None => self.root = Some(frame),
+            }
+        }
+    }
+
+    fn record_gas(&mut self, cost: u64, snapshot: gas_tracing::Snapshot) {
+        let gas_left = snapshot
+            .gas_limit
+            .saturating_sub(snapshot.used_gas)
+            .saturating_sub(snapshot.memory_gas);
+        self.gas_left = gas_left.saturating_sub(cost);
+        if !self.step_gas_recorded {
+            if let Some(log) = self.struct_logs.last_mut() {
+                log.gas = gas_left;
+                log.gas_cost = cost;
+            }
+            self.step_gas_recorded = true;
+        }
+    }
+}
+
+/// Map an exit reason to the error reported by geth, `None` on success.
+pub fn exit_error(reason: &ExitReason) -> Option<String> {
+    match reason {
+        ExitReason::Succeed(_) => None,
+        ExitReason::Revert(_) => Some("execution reverted".to_string()),
+        ExitReason::Error(ExitError::OutOfGas) => Some("out of gas".to_string()),
+        ExitReason::Error(ExitError::CallTooDeep) => {
+            Some("max call depth exceeded".to_string())
+        }
+        ExitReason::Error(e) => Some(format!("{:?}", e)),
+        ExitReason::Fatal(e) => Some(format!("{:?}", e)),
+    }
+}
+
+struct EvmListener(Rc<RefCell<TraceState>>);
+
+impl evm::tracing::EventListener for EvmListener {
+    fn event(&mut self, event: evm::tracing::Event) {
+        use evm::tracing::Event;
+
+        let mut state = self.0.borrow_mut();
+        match event {
+            Event::Call {
+                code_address,
+                transfer,
+                input,
+                target_gas,
+                is_static,
+                context,
+            } => {
+                let call_type = if is_static {
+                    CallType::StaticCall
+                } else if code_address == context.address {
+                    CallType::Call
+                } else if transfer.is_some() {
+                    CallType::CallCode
+                } else {
+                    CallType::DelegateCall
+                };
+                let to = if call_type == CallType::DelegateCall {
+                    code_address
+                } else {
+                    context.address
+                };
+                let frame = CallFrame {
+                    call_type,
+                    from: context.caller,
+                    to,
+                    value: transfer
+                        .as_ref()
+                        .map(|t| t.value)
+                        .unwrap_or(context.apparent_value),
+                    gas: target_gas.unwrap_or(state.gas_left),
+                    gas_used: 0,
+                    input: input.to_vec(),
+                    output: vec![],
+                    error: None,
+                    calls: vec![],
+                };
+                state.enter(frame);
+            }
+            Event::Create {
+                caller,
+                address,
+                scheme,
+                value,
+                init_code,
+                target_gas,
+            } => {
+                let call_type = match scheme {
+                    CreateScheme::Create2 { .. } => CallType::Create2,
+                    _ => CallType::Create,
+                };
+                let frame = CallFrame {
+                    call_type,
+                    from: caller,
+                    to: address,
+                    value,
+                    gas: target_gas.unwrap_or(state.gas_left),
+                    gas_used: 0,
+                    input: init_code.to_vec(),
+                    output: vec![],
+                    error: None,
+                    calls: vec![],
+                };
+                state.enter(frame);
+            }
+            Event::Suicide {
+                address,
+                target,
+                balance,
+            } => {
+                let frame = CallFrame {
+                    call_type: CallType::SelfDestruct,
+                    from: address,
+                    to: target,
+                    value: balance,
+                    gas: 0,
+                    gas_used: 0,
+                    input: vec![],
+                    output: vec![],
+                    error: None,
+                    calls: vec![],
+                };
+                if let Some(parent) = state.frames.last_mut() {
+                    parent.calls.push(frame);
+                }
+            }
+            Event::Exit {
+                reason,
+                return_value,
+            } => state.exit(reason, return_value),
+            _ => {}
+        }
+    }
+}
+
+struct RuntimeListener(Rc<RefCell<TraceState>>);
+
+impl runtime_tracing::EventListener for RuntimeListener {
+    fn event(&mut self, event: runtime_tracing::Event) {
+        use runtime_tracing::Event;
+
+        let mut state = self.0.borrow_mut();
+        match event {
+            Event::Step {
+                context: _,
+                opcode,
+                position,
+                stack,
+                memory,
+            } => {
+                if !state.config.struct_logs {
+                    return;
+                }
+                let config = state.config;
+                let log = StructLog {
+                    pc: position.as_ref().map(|p| *p as u64).unwrap_or_default(),
+                    op: opcode.0,
+                    gas: state.gas_left,
+                    gas_cost: 0,
+                    depth: state.frames.len() as u64,
+                    stack: (!config.disable_stack).then(|| stack.data().clone()),
+                    memory: (!config.disable_memory).then(|| memory.data().clone()),
+                    storage: None,
+                };
+                state.struct_logs.push(log);
+                state.step_gas_recorded = false;
+            }
+            Event::SLoad {
+                address,
+                index,
+                value,
+            }
+            | Event::SStore {
+                address,
+                index,
+                value,
+            } => {
+                let storage = state.storages.entry(address).or_default();
+                storage.insert(index, value);
+                let storage = storage.clone();
+                if state.config.struct_logs && !state.config.disable_storage {
+                    if let Some(log) = state.struct_logs.last_mut() {
+                        log.storage = Some(storage);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+struct GasListener(Rc<RefCell<TraceState>>);
+
+impl gas_tracing::EventListener for GasListener {
+    fn event(&mut self, event: gas_tracing::Event) {
+        use gas_tracing::Event;
+
+        let mut state = self.0.borrow_mut();
+        match event {
+            Event::RecordCost { cost, snapshot } => state.record_gas(cost, snapshot),
+            Event::RecordDynamicCost {
+                gas_cost,
+                memory_gas,
+                gas_refund: _,
+                snapshot,
+            } => {
+                let memory_cost = memory_gas.saturating_sub(snapshot.memory_gas);
+                state.record_gas(gas_cost.saturating_add(memory_cost), snapshot)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Collects the trace of everything the EVM executes inside [`Tracer::trace`].
+pub struct Tracer {
+    state: Rc<RefCell<TraceState>>,
+}
+
+impl Tracer {
+    pub fn new(config: TraceConfig) -> Self {
+        let state = TraceState {
+            config,
+            ..Default::default()
+        };
+        Tracer {
+            state: Rc::new(RefCell::new(state)),
+        }
+    }
+
+    /// Run `f` with the tracing hooks of the evm crates attached to this tracer.
+    pub fn trace<R, F: FnOnce() -> R>(&self, f: F) -> R {
+        let mut evm_listener = EvmListener(self.state.clone());
+        let mut runtime_listener = RuntimeListener(self.state.clone());
+        let mut gas_listener = GasListener(self.state.clone());
+
+        evm::tracing::using(&mut evm_listener, || {
+            runtime_tracing::using(&mut runtime_listener, || {
+                gas_tracing::using(&mut gas_listener, f)
+            })
+        })
+    }
+
+    /// Consume the tracer, `used_gas` is the gas charged for the whole execution
+    /// and replaces the gas used of the root frame, which misses the intrinsic cost.
+    pub fn finish(self, used_gas: U256) -> TraceResult {
+        let mut state = self.state.borrow_mut();
+        let mut call = state.root.take();
+        if let Some(call) = call.as_mut() {
+            call.gas_used = used_gas.low_u64();
+        }
+        TraceResult {
+            struct_logs: std::mem::take(&mut state.struct_logs),
+            call,
+        }
+    }
+}
+
+/// The mnemonic of an opcode, as printed in struct logs.
+pub fn opcode_name(op: u8) -> String {
+    let name = match Opcode(op) {
+        Opcode::STOP => "STOP",
+        Opcode::ADD => "ADD",
+        Opcode::MUL => "MUL",
+        Opcode::SUB => "SUB",
+        Opcode::DIV => "DIV",
+        Opcode::SDIV => "SDIV",
+        Opcode::MOD => "MOD",
+        Opcode::SMOD => "SMOD",
+        Opcode::ADDMOD => "ADDMOD",
+        Opcode::MULMOD => "MULMOD",
+        Opcode::EXP => "EXP",
+        Opcode::SIGNEXTEND => "SIGNEXTEND",
+        Opcode::LT => "LT",
+        Opcode::GT => "GT",
+        Opcode::SLT => "SLT",
+        Opcode::SGT => "SGT",
+        Opcode::EQ => "EQ",
+        Opcode::ISZERO => "ISZERO",
+        Opcode::AND => "AND",
+        Opcode::OR => "OR",
+        Opcode::XOR => "XOR",
+        Opcode::NOT => "NOT",
+        Opcode::BYTE => "BYTE",
+        Opcode::SHL => "SHL",
+        Opcode::SHR => "SHR",
+        Opcode::SAR => "SAR",
+        Opcode::SHA3 => "SHA3",
+        Opcode::ADDRESS => "ADDRESS",
+        Opcode::BALANCE => "BALANCE",
+        Opcode::ORIGIN => "ORIGIN",
+        Opcode::CALLER => "CALLER",
+        Opcode::CALLVALUE => "CALLVALUE",
+        Opcode::CALLDATALOAD => "CALLDATALOAD",
+        Opcode::CALLDATASIZE => "CALLDATASIZE",
+        Opcode::CALLDATACOPY => "CALLDATACOPY",
+        Opcode::CODESIZE => "CODESIZE",
+        Opcode::CODECOPY => "CODECOPY",
+        Opcode::GASPRICE => "GASPRICE",
+        Opcode::EXTCODESIZE => "EXTCODESIZE",
+        Opcode::EXTCODECOPY => "EXTCODECOPY",
+        Opcode::RETURNDATASIZE => "RETURNDATASIZE",
+        Opcode::RETURNDATACOPY => "RETURNDATACOPY",
+        Opcode::EXTCODEHASH => "EXTCODEHASH",
+        Opcode::BLOCKHASH => "BLOCKHASH",
+        Opcode::COINBASE => "COINBASE",
+        Opcode::TIMESTAMP => "TIMESTAMP",
+        Opcode::NUMBER => "NUMBER",
+        Opcode::DIFFICULTY => "DIFFICULTY",
+        Opcode::GASLIMIT => "GASLIMIT",
+        Opcode::CHAINID => "CHAINID",
+        Opcode::SELFBALANCE => "SELFBALANCE",
+        Opcode::POP => "POP",
+        Opcode::MLOAD => "MLOAD",
+        Opcode::MSTORE => "MSTORE",
+        Opcode::MSTORE8 => "MSTORE8",
+        Opcode::SLOAD => "SLOAD",
+        Opcode::SSTORE => "SSTORE",
+        Opcode::JUMP => "JUMP",
+        Opcode::JUMPI => "JUMPI",
+        Opcode::PC => "PC",
+        Opcode::MSIZE => "MSIZE",
+        Opcode::GAS => "GAS",
+        Opcode::JUMPDEST => "JUMPDEST",
+        Opcode::CREATE => "CREATE",
+        Opcode::CALL => "CALL",
+        Opcode::CALLCODE => "CALLCODE",
+        Opcode::RETURN => "RETURN",
+        Opcode::DELEGATECALL => "DELEGATECALL",
+        Opcode::CREATE2 => "CREATE2",
+        Opcode::STATICCALL => "STATICCALL",
+        Opcode::REVERT => "REVERT",
+        Opcode::INVALID => "INVALID",
+        Opcode::SUICIDE => "SELFDESTRUCT",
+        _ => match op {
+            0x60..=0x7f => return format!("PUSH{}", op - 0x5f),
+            0x80..=0x8f => return format!("DUP{}", op - 0x7f),
+            0x90..=0x9f => return format!("SWAP{}", op - 0x8f),
+            0xa0..=0xa4 => return format!("LOG{}", op - 0xa0),
+            _ => return format!("opcode 0x{:x} not defined", op),
+        },
+    };
+    name.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opcode_names() {
+        assert_eq!(opcode_name(0x00), "STOP");
+        assert_eq!(opcode_name(0x55), "SSTORE");
+        assert_eq!(opcode_name(0x60), "PUSH1");
+        assert_eq!(opcode_name(0x7f), "PUSH32");
+        assert_eq!(opcode_name(0x8f), "DUP16");
+        assert_eq!(opcode_name(0x90), "SWAP1");
+        assert_eq!(opcode_name(0xa4), "LOG4");
+        assert_eq!(opcode_name(0xff), "SELFDESTRUCT");
+        assert_eq!(opcode_name(0x0c), "opcode 0xc not defined");
+    }
+
+    #[test]
+    fn exit_errors() {
+        assert_eq!(
+            exit_error(&ExitReason::Succeed(evm::ExitSucceed::Stopped)),
+            None
+        );
+        assert_eq!(
+            exit_error(&ExitReason::Revert(evm::ExitRevert::Reverted)),
+            Some("execution reverted".to_string())
+        );
+        assert_eq!(
+            exit_error(&ExitReason::Error(ExitError::OutOfGas)),
+            Some("out of gas".to_string())
+        );
+    }
+}
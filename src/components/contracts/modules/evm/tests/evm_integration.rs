@@ -288,6 +288,7 @@ fn test_balance_of_with_eth_call(contract: ERC20, who: H160) -> U256 {
         gas_limit: DEFAULT_GAS_LIMIT,
         gas_price: None,
         nonce: None,
+        access_list: vec![],
     };
 
     let info = <BaseApp as module_ethereum::Config>::Runner::call(
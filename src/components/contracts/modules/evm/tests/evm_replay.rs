@@ -0,0 +1,116 @@
+#![allow(clippy::field_reassign_with_default)]
+
+#[allow(dead_code)]
+mod utils;
+
+use abci::*;
+use baseapp::{AssetLookup, BaseApp, ChainId};
+use ethereum::{TransactionV0, TransactionV2};
+use ethereum_types::H256;
+use fp_evm::CallOrCreateInfo;
+use fp_mocks::*;
+use fp_storage::Borrow;
+use fp_types::{
+    actions::ethereum::Action as EthereumAction, actions::Action,
+    assemble::UncheckedTransaction,
+};
+use fp_utils::tx::EvmRawTxWrapper;
+use module_evm::{
+    runtime::tracer::{TraceConfig, Tracer},
+    storage::*,
+};
+use sha3::{Digest, Keccak256};
+use std::sync::Arc;
+use utils::*;
+
+fn wrap_transaction(tx: &TransactionV0) -> Vec<u8> {
+    let function = Action::Ethereum(EthereumAction::Transact(tx.clone()));
+    let tx = UncheckedTransaction::<()>::new_unsigned(function);
+    EvmRawTxWrapper::wrap(&serde_json::to_vec(&tx).unwrap())
+}
+
+fn deliver_transaction(tx: &TransactionV0) -> CallOrCreateInfo {
+    let mut req = RequestDeliverTx::default();
+    req.tx = wrap_transaction(tx);
+    let resp = BASE_APP.lock().unwrap().deliver_tx(&req);
+    assert_eq!(
+        resp.code, 0,
+        "deliver tx failed, code: {}, log: {}",
+        resp.code, resp.log
+    );
+    serde_json::from_slice::<CallOrCreateInfo>(&resp.data).unwrap()
+}
+
+#[test]
+fn trace_second_transaction_of_block() {
+    test_mint_balance(
+        &ALICE_ECDSA.account_id,
+        100_0000_0000_0000_0000_u64.into(),
+        1,
+    );
+
+    let mut req = RequestBeginBlock::default();
+    req.hash = b"test".to_vec();
+    let mut header = Header::default();
+    header.height = 2;
+    req.set_header(header);
+    let _ = BASE_APP.lock().unwrap().begin_block(&req);
+
+    // the second transaction calls the contract deployed by the first one
+    let constructor = ERC20Constructor::load();
+    let abi = constructor.0.abi.clone();
+    let deploy = constructor
+        .deploy("erc20", "FRA", 0.into())
+        .sign(&ALICE_ECDSA.private_key, ChainId::get());
+    let address = match deliver_transaction(&deploy) {
+        CallOrCreateInfo::Create(info) => info.value,
+        info => panic!("not expected result: {:?}", info),
+    };
+    let mint = ERC20(DeployedContract { abi, address })
+        .mint(BOB_ECDSA.address, 10000.into(), 1.into())
+        .sign(&ALICE_ECDSA.private_key, ChainId::get());
+    deliver_transaction(&mint);
+
+    let mut req = RequestEndBlock::default();
+    req.height = 2;
+    let _ = BASE_APP.lock().unwrap().end_block(&req);
+    let _ = BASE_APP.lock().unwrap().commit(&RequestCommit::new());
+
+    // replay the block on top of its parent until the traced transaction
+    let mut ctx = BASE_APP.lock().unwrap().create_context_at(1).unwrap();
+    ctx.header.height = 2;
+    let txs = [wrap_transaction(&deploy), wrap_transaction(&mint)];
+    let hash = H256::from_slice(Keccak256::digest(&rlp::encode(&mint)).as_slice());
+    let asset_of: AssetLookup = Arc::new(|_| None);
+    BaseApp::replay_block_until(&ctx, &txs, hash, &asset_of).unwrap();
+    assert!(AccountCodes::contains_key(
+        ctx.state.read().borrow(),
+        &address.into()
+    ));
+
+    let tracer = Tracer::new(TraceConfig {
+        struct_logs: true,
+        ..Default::default()
+    });
+    let (_, _, info) = tracer
+        .trace(|| {
+            module_ethereum::App::<BaseApp>::execute_transaction(
+                &ctx,
+                ALICE_ECDSA.address,
+                &TransactionV2::Legacy(mint),
+            )
+        })
+        .unwrap();
+    let used_gas = match info {
+        CallOrCreateInfo::Call(info) => {
+            assert!(
+                info.exit_reason.is_succeed(),
+                "erc20 mint failed: {:?}",
+                info.exit_reason
+            );
+            info.used_gas
+        }
+        info => panic!("not expected result: {:?}", info),
+    };
+    assert!(!tracer.finish(used_gas).struct_logs.is_empty());
+}
@@ -1,13 +1,17 @@
 use crate::storage::*;
 use crate::{App, Config};
+use config::abci::global_cfg::CFG;
 use fp_core::{context::Context, ensure, transaction::ActionResult};
 use fp_storage::{Borrow, BorrowMut};
-use fp_traits::{account::AccountAsset, evm::DecimalsMapping};
+use fp_traits::{
+    account::{AccountAsset, CustomAsset},
+    evm::DecimalsMapping,
+};
 use fp_types::actions::xhub::NonConfidentialTransfer;
 use fp_types::{actions::xhub::NonConfidentialOutput, crypto::Address};
 use ledger::data_model::ASSET_TYPE_FRA;
 use log::debug;
-use primitive_types::U256;
+use primitive_types::{H256, U256};
 use ruc::*;
 
 impl<C: Config> App<C> {
@@ -16,36 +20,77 @@ impl<C: Config> App<C> {
         sender: Address,
         call: NonConfidentialTransfer,
     ) -> Result<ActionResult> {
+        let asset = call
+            .outputs
+            .first()
+            .map(|output| output.asset)
+            .unwrap_or(ASSET_TYPE_FRA);
+        if ctx.header.height < CFG.checkpoint.evm_custom_assets_height {
+            ensure!(
+                asset == ASSET_TYPE_FRA,
+                "Invalid asset type only support FRA"
+            );
+        }
+
         let mut transfer_amount = 0;
         for output in &call.outputs {
             ensure!(
-                output.asset == ASSET_TYPE_FRA,
-                "Invalid asset type only support FRA"
+                output.asset == asset,
+                "Invalid asset type outputs must have the same asset type"
             );
             transfer_amount += output.amount;
         }
 
-        debug!(target: "xhub", "transfer to UTXO {} FRA", transfer_amount);
+        debug!(target: "xhub", "transfer to UTXO {} {:?}", transfer_amount, asset);
 
         ensure!(
             call.input_value == transfer_amount,
             "Input value mismatch utxo output"
         );
 
+        if asset == ASSET_TYPE_FRA {
+            Self::burn_fra(ctx, &sender, transfer_amount)?;
+        } else {
+            Self::burn_custom_asset(ctx, &sender, H256::from(asset.0), transfer_amount)?;
+        }
+
+        if transfer_amount != 0 {
+            Self::add_mint(ctx, call.outputs)?;
+        }
+        Ok(ActionResult::default())
+    }
+
+    fn burn_fra(ctx: &Context, sender: &Address, transfer_amount: u64) -> Result<()> {
         let amount = C::DecimalsMapping::from_native_token(U256::from(transfer_amount))
             .ok_or_else(|| eg!("the transfer to UTXO amount is too large"))?;
 
-        let sa = C::AccountAsset::account_of(ctx, &sender, None)
+        let sa = C::AccountAsset::account_of(ctx, sender, None)
             .c(d!("account does not exist"))?;
         if sa.balance < amount {
             return Err(eg!("insufficient balance"));
         }
 
-        if !amount.is_zero() {
-            C::AccountAsset::burn(ctx, &sender, amount)?;
-            Self::add_mint(ctx, call.outputs)?;
+        C::AccountAsset::burn(ctx, sender, amount)
+    }
+
+    /// The custom assets keep their UTXO decimals on the EVM side.
+    fn burn_custom_asset(
+        ctx: &Context,
+        sender: &Address,
+        asset: H256,
+        transfer_amount: u64,
+    ) -> Result<()> {
+        ensure!(
+            C::CustomAsset::asset_metadata(ctx, &asset).is_some(),
+            "Invalid asset type not bridged to EVM"
+        );
+
+        let amount = U256::from(transfer_amount);
+        if C::CustomAsset::asset_balance(ctx, &asset, sender) < amount {
+            return Err(eg!("insufficient balance"));
         }
-        Ok(ActionResult::default())
+
+        C::CustomAsset::asset_burn(ctx, &asset, sender, amount)
     }
 
     pub(crate) fn add_mint(
@@ -9,7 +9,10 @@ use fp_core::{
     module::AppModule,
     transaction::{ActionResult, Executable},
 };
-use fp_traits::{account::AccountAsset, evm::DecimalsMapping};
+use fp_traits::{
+    account::{AccountAsset, CustomAsset},
+    evm::DecimalsMapping,
+};
 use fp_types::{actions::xhub::Action, crypto::Address};
 use ruc::*;
 use std::marker::PhantomData;
@@ -19,6 +22,8 @@ pub const MODULE_NAME: &str = "xhub";
 pub trait Config {
     /// Account module interface to read/write account assets.
     type AccountAsset: AccountAsset<Address>;
+    /// Account module interface to read/write the custom assets.
+    type CustomAsset: CustomAsset<Address>;
     /// Mapping from eth decimals to native token decimals.
     type DecimalsMapping: DecimalsMapping;
 }
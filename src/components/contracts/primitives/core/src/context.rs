@@ -1,4 +1,6 @@
 use abci::Header;
+use fp_types::crypto::Address;
+use primitive_types::H160;
 use storage::{
     db::{FinDB, RocksDB},
     state::{ChainState, State},
@@ -27,6 +29,11 @@ pub struct Context {
     pub run_mode: RunTxMode,
     pub header: Header,
     pub header_hash: Vec<u8>,
+    /// Some(height): state reads fall back to the versioned data at `height`
+    /// unless the value has been changed in the session
+    pub version: Option<u64>,
+    /// Staking account of the block proposer, resolved by the host application
+    pub block_proposer: Option<Address>,
 }
 
 impl Context {
@@ -40,6 +47,8 @@ impl Context {
             run_mode: RunTxMode::None,
             header: Default::default(),
             header_hash: vec![],
+            version: None,
+            block_proposer: None,
         }
     }
 
@@ -50,6 +59,8 @@ impl Context {
             run_mode: RunTxMode::None,
             header: self.header.clone(),
             header_hash: self.header_hash(),
+            version: self.version,
+            block_proposer: self.block_proposer.clone(),
         }
     }
 
@@ -63,6 +74,8 @@ impl Context {
             run_mode: RunTxMode::None,
             header: self.header.clone(),
             header_hash: self.header_hash(),
+            version: self.version,
+            block_proposer: self.block_proposer.clone(),
         }
     }
 }
@@ -79,4 +92,23 @@ impl Context {
     pub fn header_hash(&self) -> Vec<u8> {
         self.header_hash.clone()
     }
+
+    /// Resolve the state version to read, an explicit height takes precedence
+    /// over the version of the context. `0` means the latest state.
+    pub fn version_of(&self, height: Option<u64>) -> u64 {
+        height.or(self.version).unwrap_or(0)
+    }
+
+    /// EVM address of the block proposer, derived from its staking account when
+    /// resolved, from the tendermint address in the header otherwise.
+    /// It only identifies the proposer, the fees are credited to `block_proposer`.
+    pub fn proposer_evm_address(&self) -> H160 {
+        match self.block_proposer.as_ref() {
+            Some(account) => account.to_evm_address(),
+            None if self.header.proposer_address.len() >= 20 => {
+                H160::from_slice(&self.header.proposer_address[0..20])
+            }
+            None => H160::default(),
+        }
+    }
 }
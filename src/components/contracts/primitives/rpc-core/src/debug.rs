@@ -0,0 +1,28 @@
+//! Debug rpc interface.
+
+use ethereum_types::H256;
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+
+use crate::types::{BlockNumber, CallRequest, TraceParams, TraceResult};
+
+pub use rpc_impl_DebugApi::gen_server::DebugApi as DebugApiServer;
+
+/// Debug rpc interface.
+#[rpc(server)]
+pub trait DebugApi {
+    /// Re-executes a committed transaction on the state of its parent block and
+    /// returns the trace of the execution.
+    #[rpc(name = "debug_traceTransaction")]
+    fn trace_transaction(&self, _: H256, _: Option<TraceParams>) -> Result<TraceResult>;
+
+    /// Executes a call on the state of the given block and returns the trace of
+    /// the execution.
+    #[rpc(name = "debug_traceCall")]
+    fn trace_call(
+        &self,
+        _: CallRequest,
+        _: Option<BlockNumber>,
+        _: Option<TraceParams>,
+    ) -> Result<TraceResult>;
+}
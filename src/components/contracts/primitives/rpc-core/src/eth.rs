@@ -23,8 +23,8 @@ use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_derive::rpc;
 
 use crate::types::{
-    BlockNumber, Bytes, CallRequest, Filter, Index, Log, Receipt, RichBlock, SyncStatus,
-    Transaction, TransactionRequest, Work,
+    BlockNumber, Bytes, CallRequest, FeeHistory, Filter, Index, Log, Receipt, RichBlock,
+    SyncStatus, Transaction, TransactionRequest, Work,
 };
 
 pub use rpc_impl_EthApi::gen_server::EthApi as EthApiServer;
@@ -79,6 +79,19 @@ pub trait EthApi {
     #[rpc(name = "eth_gasPrice")]
     fn gas_price(&self) -> Result<U256>;
 
+    /// Returns the base fee and priority fee history of the given range of blocks.
+    #[rpc(name = "eth_feeHistory")]
+    fn fee_history(
+        &self,
+        _: U256,
+        _: BlockNumber,
+        _: Option<Vec<f64>>,
+    ) -> Result<FeeHistory>;
+
+    /// Returns a suggestion of priority fee per gas for EIP-1559 transactions.
+    #[rpc(name = "eth_maxPriorityFeePerGas")]
+    fn max_priority_fee_per_gas(&self) -> Result<U256>;
+
     /// Returns highest block number.
     #[rpc(name = "eth_blockNumber")]
     fn block_number(&self) -> Result<U256>;
@@ -16,6 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+mod debug;
 mod eth;
 mod eth_filter;
 mod eth_pubsub;
@@ -24,6 +25,7 @@ mod web3;
 
 pub mod types;
 
+pub use debug::{DebugApi, DebugApiServer};
 pub use eth::{EthApi, EthApiServer};
 pub use eth_filter::{EthFilterApi, EthFilterApiServer};
 pub use eth_pubsub::{EthPubSubApi, EthPubSubApiServer};
@@ -90,6 +90,9 @@ pub struct Block {
     pub transactions: BlockTransactions,
     /// Size in bytes
     pub size: Option<U256>,
+    /// Base fee per gas
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>,
 }
 
 /// Block header representation.
@@ -131,6 +134,9 @@ pub struct Header {
     pub seal_fields: Vec<Bytes>,
     /// Size in bytes
     pub size: Option<U256>,
+    /// Base fee per gas
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>,
 }
 
 /// Block representation with additional info.
@@ -16,8 +16,8 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::types::Bytes;
-use ethereum_types::{H160, U256};
+use crate::types::{AccessListItem, Bytes};
+use ethereum_types::{H160, U256, U64};
 use serde::Deserialize;
 
 /// Call request
@@ -31,6 +31,10 @@ pub struct CallRequest {
     pub to: Option<H160>,
     /// Gas Price
     pub gas_price: Option<U256>,
+    /// Max fee per gas, EIP-1559 only
+    pub max_fee_per_gas: Option<U256>,
+    /// Max priority fee per gas, EIP-1559 only
+    pub max_priority_fee_per_gas: Option<U256>,
     /// Gas
     pub gas: Option<U256>,
     /// Value
@@ -39,4 +43,9 @@ pub struct CallRequest {
     pub data: Option<Bytes>,
     /// Nonce
     pub nonce: Option<U256>,
+    /// EIP-2930 access list
+    pub access_list: Option<Vec<AccessListItem>>,
+    /// EIP-2718 transaction type
+    #[serde(rename = "type")]
+    pub transaction_type: Option<U64>,
 }
@@ -23,11 +23,13 @@ mod block;
 mod block_number;
 mod bytes;
 mod call_request;
+mod fee;
 mod filter;
 mod index;
 mod log;
 mod receipt;
 mod sync;
+mod trace;
 mod transaction;
 mod transaction_request;
 mod work;
@@ -41,6 +43,7 @@ pub use self::block::{Block, BlockTransactions, Header, Rich, RichBlock, RichHea
 pub use self::block_number::BlockNumber;
 pub use self::bytes::Bytes;
 pub use self::call_request::CallRequest;
+pub use self::fee::FeeHistory;
 pub use self::filter::{
     Filter, FilterAddress, FilterChanges, FilterPool, FilterPoolItem, FilterType,
     FilteredParams, Topic, VariadicValue,
@@ -52,9 +55,10 @@ pub use self::sync::{
     ChainStatus, EthProtocolInfo, PeerCount, PeerInfo, PeerNetworkInfo,
     PeerProtocolsInfo, Peers, PipProtocolInfo, SyncInfo, SyncStatus, TransactionStats,
 };
+pub use self::trace::{CallFrame, StructLog, StructLogTrace, TraceParams, TraceResult};
 pub use self::transaction::{
-    LocalTransactionStatus, PendingTransaction, PendingTransactions, RichRawTransaction,
-    Transaction,
+    AccessListItem, LocalTransactionStatus, PendingTransaction, PendingTransactions,
+    RichRawTransaction, Transaction,
 };
 pub use self::transaction_request::TransactionRequest;
 pub use self::work::Work;
@@ -54,4 +54,9 @@ pub struct Receipt {
     // NOTE(niklasad1): Unknown after EIP98 rules, if it's missing then skip serializing it
     #[serde(skip_serializing_if = "Option::is_none", rename = "status")]
     pub status_code: Option<U64>,
+    /// Price per gas actually paid by the transaction
+    pub effective_gas_price: U256,
+    /// EIP-2718 transaction type
+    #[serde(rename = "type")]
+    pub transaction_type: U64,
 }
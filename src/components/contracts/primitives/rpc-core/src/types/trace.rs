@@ -0,0 +1,141 @@
+//! Types of the `debug_` tracing api, compatible with the geth tracers.
+
+use crate::types::Bytes;
+use ethereum_types::{H160, H256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Tracer options of `debug_traceTransaction` and `debug_traceCall`.
+#[derive(Debug, Default, PartialEq, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceParams {
+    /// Skip the storage of struct logs
+    pub disable_storage: Option<bool>,
+    /// Skip the memory of struct logs
+    pub disable_memory: Option<bool>,
+    /// Skip the stack of struct logs
+    pub disable_stack: Option<bool>,
+    /// Name of the tracer, the struct logger is used if not provided.
+    /// Only `callTracer` is supported.
+    pub tracer: Option<String>,
+    /// Accepted for compatibility, not enforced
+    pub timeout: Option<String>,
+}
+
+/// One executed opcode.
+#[derive(Debug, PartialEq, Eq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    /// Program counter
+    pub pc: u64,
+    /// Opcode mnemonic
+    pub op: String,
+    /// Remaining gas before the opcode
+    pub gas: u64,
+    /// Gas charged by the opcode
+    pub gas_cost: u64,
+    /// Call depth, starting from 1
+    pub depth: u64,
+    /// Stack before the opcode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack: Option<Vec<H256>>,
+    /// Memory before the opcode, in 32 bytes words
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Vec<H256>>,
+    /// Storage slots of the contract touched so far
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// Output of the default struct logger.
+#[derive(Debug, PartialEq, Eq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogTrace {
+    /// Gas used by the execution
+    pub gas: u64,
+    /// Whether the execution failed
+    pub failed: bool,
+    /// Return value of the execution
+    pub return_value: Bytes,
+    /// Executed opcodes
+    pub struct_logs: Vec<StructLog>,
+}
+
+/// Output of the `callTracer`.
+#[derive(Debug, PartialEq, Eq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    /// CALL, STATICCALL, DELEGATECALL, CREATE, ...
+    #[serde(rename = "type")]
+    pub call_type: String,
+    /// Caller
+    pub from: H160,
+    /// Callee, or the created contract
+    pub to: H160,
+    /// Transferred value
+    pub value: U256,
+    /// Gas provided to the frame
+    pub gas: U256,
+    /// Gas used by the frame
+    pub gas_used: U256,
+    /// Call data, or init code
+    pub input: Bytes,
+    /// Return data
+    pub output: Bytes,
+    /// Error, if the frame failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Nested frames
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<CallFrame>,
+}
+
+/// Result of a trace request.
+#[derive(Debug, PartialEq, Eq, Serialize, Clone)]
+#[serde(untagged)]
+pub enum TraceResult {
+    /// Struct logger output
+    StructLogs(StructLogTrace),
+    /// Call tracer output
+    CallTracer(CallFrame),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_params_deserialization() {
+        let s = r#"{"disableStorage":true,"tracer":"callTracer"}"#;
+        let deserialized: TraceParams = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            deserialized,
+            TraceParams {
+                disable_storage: Some(true),
+                tracer: Some("callTracer".to_string()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn call_frame_serialization() {
+        let frame = CallFrame {
+            call_type: "CALL".to_string(),
+            from: H160::from_low_u64_be(1),
+            to: H160::from_low_u64_be(2),
+            value: U256::zero(),
+            gas: U256::from(21000),
+            gas_used: U256::from(21000),
+            input: Bytes::new(vec![]),
+            output: Bytes::new(vec![]),
+            error: None,
+            calls: vec![],
+        };
+        let serialized = serde_json::to_string(&TraceResult::CallTracer(frame)).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"type":"CALL","from":"0x0000000000000000000000000000000000000001","to":"0x0000000000000000000000000000000000000002","value":"0x0","gas":"0x5208","gasUsed":"0x5208","input":"0x","output":"0x"}"#
+        );
+    }
+}
@@ -19,7 +19,7 @@
 use crate::types::Bytes;
 use ethereum_types::{H160, H256, H512, U256, U64};
 use serde::ser::SerializeStruct;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
 use std::{
     collections::HashMap,
     sync::{Arc, Mutex},
@@ -47,6 +47,12 @@ pub struct Transaction {
     pub value: U256,
     /// Gas Price
     pub gas_price: U256,
+    /// Max fee per gas of an EIP-1559 transaction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+    /// Max priority fee per gas of an EIP-1559 transaction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
     /// Gas
     pub gas: U256,
     /// Data
@@ -67,6 +73,22 @@ pub struct Transaction {
     pub r: U256,
     /// The S field of the signature.
     pub s: U256,
+    /// EIP-2718 transaction type
+    #[serde(rename = "type")]
+    pub transaction_type: U64,
+    /// EIP-2930 access list
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<Vec<AccessListItem>>,
+}
+
+/// EIP-2930 access list item
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListItem {
+    /// Accessed address
+    pub address: H160,
+    /// Accessed storage keys
+    pub storage_keys: Vec<H256>,
 }
 
 /// Local Transaction Status
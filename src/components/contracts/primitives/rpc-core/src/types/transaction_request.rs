@@ -18,8 +18,8 @@
 
 //! `TransactionRequest` type
 
-use crate::types::Bytes;
-use ethereum_types::{H160, U256};
+use crate::types::{AccessListItem, Bytes};
+use ethereum_types::{H160, U256, U64};
 use serde::{Deserialize, Serialize};
 
 /// Transaction request coming from RPC
@@ -33,6 +33,10 @@ pub struct TransactionRequest {
     pub to: Option<H160>,
     /// Gas Price
     pub gas_price: Option<U256>,
+    /// Max fee per gas, EIP-1559 only
+    pub max_fee_per_gas: Option<U256>,
+    /// Max priority fee per gas, EIP-1559 only
+    pub max_priority_fee_per_gas: Option<U256>,
     /// Gas
     pub gas: Option<U256>,
     /// Value of transaction in wei
@@ -41,4 +45,9 @@ pub struct TransactionRequest {
     pub data: Option<Bytes>,
     /// Transaction's nonce
     pub nonce: Option<U256>,
+    /// EIP-2930 access list
+    pub access_list: Option<Vec<AccessListItem>>,
+    /// EIP-2718 transaction type
+    #[serde(rename = "type")]
+    pub transaction_type: Option<U64>,
 }
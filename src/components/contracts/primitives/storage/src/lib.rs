@@ -16,8 +16,18 @@ pub use std::ops::{Deref, DerefMut};
 pub use std::sync::Arc;
 pub use storage::store::traits::StatelessStore;
 
+use storage::{db::MerkleDB, state::State};
+
 const DB_SEPARATOR: &str = "_";
 
+/// Whether the value under `key` has been written or removed in the session of `state`,
+/// aka since the last commit.
+pub fn changed_in_session<D: MerkleDB>(state: &State<D>, key: &[u8]) -> bool {
+    let mut kvs = Default::default();
+    state.iterate_cache(key, &mut kvs);
+    kvs.contains_key(key)
+}
+
 /// An instance of a storage in a module.
 pub trait StorageInstance {
     /// Prefix of a module to isolate it from other modules.
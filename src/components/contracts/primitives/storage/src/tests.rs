@@ -128,3 +128,49 @@ fn storage_double_map_test() {
     let kvs = Data::iterate_prefix(state.read().borrow(), &1);
     assert_eq!(kvs, vec![(3, 20)]);
 }
+
+#[test]
+fn storage_get_session_or_ver() {
+    generate_storage!(Findora, Balance => Map<String, u32>);
+
+    let state = setup_temp_db();
+    let alice = "alice".to_string();
+    let bob = "bob".to_string();
+
+    assert!(Balance::insert(state.write().borrow_mut(), &alice, &1).is_ok());
+    assert!(Balance::insert(state.write().borrow_mut(), &bob, &1).is_ok());
+    state.write().commit(1).unwrap();
+    assert!(Balance::insert(state.write().borrow_mut(), &alice, &2).is_ok());
+    assert!(Balance::insert(state.write().borrow_mut(), &bob, &2).is_ok());
+    state.write().commit(2).unwrap();
+
+    // unchanged in the session
+    assert_eq!(
+        Balance::get_session_or_ver(state.read().borrow(), &alice, 1),
+        Some(1)
+    );
+
+    assert!(Balance::insert(state.write().borrow_mut(), &alice, &3).is_ok());
+    Balance::remove(state.write().borrow_mut(), &bob);
+    assert_eq!(
+        Balance::get_session_or_ver(state.read().borrow(), &alice, 1),
+        Some(3)
+    );
+    assert_eq!(
+        Balance::get_session_or_ver(state.read().borrow(), &bob, 1),
+        None
+    );
+
+    state.write().discard_session();
+    assert_eq!(
+        Balance::get_session_or_ver(state.read().borrow(), &bob, 1),
+        Some(1)
+    );
+
+    // written back to the latest committed value
+    assert!(Balance::insert(state.write().borrow_mut(), &alice, &2).is_ok());
+    assert_eq!(
+        Balance::get_session_or_ver(state.read().borrow(), &alice, 1),
+        Some(2)
+    );
+}
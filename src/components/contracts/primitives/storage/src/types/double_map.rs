@@ -85,6 +85,21 @@ where
         .unwrap()
     }
 
+    /// Load the value associated with the given keys from the session
+    /// if it has been changed there, otherwise the versioned one.
+    pub fn get_session_or_ver<D: MerkleDB>(
+        state: &State<D>,
+        k1: &Key1,
+        k2: &Key2,
+        height: u64,
+    ) -> Option<Value> {
+        if changed_in_session(state, Self::build_key_for(k1, k2).as_slice()) {
+            Self::get(state, k1, k2)
+        } else {
+            Self::get_ver(state, k1, k2, height)
+        }
+    }
+
     /// Store a value to be associated with the given key from the map.
     pub fn insert<D: MerkleDB>(
         state: &mut State<D>,
@@ -98,6 +98,34 @@ where
         Instance::get_v::<D>(state, Self::build_key_for(key).as_slice(), height).unwrap()
     }
 
+    /// Load the value associated with the given key from the session
+    /// if it has been changed there, otherwise the versioned one.
+    pub fn get_session_or_ver<D: MerkleDB>(
+        state: &State<D>,
+        key: &Key,
+        height: u64,
+    ) -> Option<Value> {
+        if changed_in_session(state, Self::build_key_for(key).as_slice()) {
+            Self::get(state, key)
+        } else {
+            Self::get_ver(state, key, height)
+        }
+    }
+
+    /// Load the serialized value associated with the given key from the session
+    /// if it has been changed there, otherwise the versioned one.
+    pub fn get_session_or_ver_bytes<D: MerkleDB>(
+        state: &State<D>,
+        key: &Key,
+        height: u64,
+    ) -> Option<Vec<u8>> {
+        if changed_in_session(state, Self::build_key_for(key).as_slice()) {
+            Self::get_bytes(state, key)
+        } else {
+            Self::get_ver_bytes(state, key, height)
+        }
+    }
+
     /// Load the unique key value pair with specified prefix.
     pub fn get_unique_prefix<D: MerkleDB>(
         state: &State<D>,
@@ -80,6 +80,19 @@ where
         .unwrap()
     }
 
+    /// Load the value from the session of the provided storage instance
+    /// if it has been changed there, otherwise the versioned one.
+    pub fn get_session_or_ver<D: MerkleDB>(
+        state: &State<D>,
+        height: u64,
+    ) -> Option<Value> {
+        if changed_in_session(state, <Self as StoragePrefixKey>::store_key().as_ref()) {
+            Self::get(state)
+        } else {
+            Self::get_ver(state, height)
+        }
+    }
+
     /// Store a value under this hashed key into the provided storage instance.
     pub fn put<D: MerkleDB>(state: &mut State<D>, val: &Value) -> Result<()> {
         Instance::set_obj::<Value, D>(
@@ -1,5 +1,6 @@
 use fp_core::{account::SmartAccount, context::Context};
-use primitive_types::U256;
+use fp_types::assets::AssetMetadata;
+use primitive_types::{H160, H256, U256};
 use ruc::Result;
 
 pub trait AccountAsset<Address> {
@@ -58,6 +59,68 @@ pub trait AccountAsset<Address> {
     ) -> Result<()>;
 }
 
+/// The custom assets defined on the UTXO side and held by the smart accounts,
+/// an asset being identified by its asset type code.
+pub trait CustomAsset<Address> {
+    /// The FRC20 attributes of `asset`, if registered.
+    fn asset_metadata(ctx: &Context, asset: &H256) -> Option<AssetMetadata>;
+
+    /// Registers `metadata.asset` under its FRC20 address, or updates its attributes.
+    fn set_asset_metadata(ctx: &Context, metadata: &AssetMetadata) -> Result<()>;
+
+    /// The asset whose FRC20 contract is at `address`.
+    fn asset_of_address(ctx: &Context, address: &H160) -> Option<H256>;
+
+    /// The total units of `asset` transferred from the UTXO side.
+    fn asset_total_issuance(ctx: &Context, asset: &H256) -> U256;
+
+    /// The balance of `asset` owned by `who`.
+    fn asset_balance(ctx: &Context, asset: &H256, who: &Address) -> U256;
+
+    /// Transfer some `asset` from `sender` to `dest`.
+    fn asset_transfer(
+        ctx: &Context,
+        asset: &H256,
+        sender: &Address,
+        dest: &Address,
+        amount: U256,
+    ) -> Result<()>;
+
+    /// Mints `amount` of `asset` to `target`.
+    fn asset_mint(
+        ctx: &Context,
+        asset: &H256,
+        target: &Address,
+        amount: U256,
+    ) -> Result<()>;
+
+    /// Burns `amount` of `asset` from `target`.
+    fn asset_burn(
+        ctx: &Context,
+        asset: &H256,
+        target: &Address,
+        amount: U256,
+    ) -> Result<()>;
+
+    /// Returns the remaining units of `asset` that `spender` will be allowed to
+    /// spend on behalf of `owner`. This is zero by default.
+    fn asset_allowance(
+        ctx: &Context,
+        asset: &H256,
+        owner: &Address,
+        spender: &Address,
+    ) -> U256;
+
+    /// Sets `amount` as the allowance of `spender` over the `asset` of `owner`.
+    fn asset_approve(
+        ctx: &Context,
+        asset: &H256,
+        owner: &Address,
+        spender: &Address,
+        amount: U256,
+    ) -> Result<()>;
+}
+
 /// Outputs the current transaction fee.
 pub trait FeeCalculator {
     fn min_fee() -> U256;
@@ -1,7 +1,9 @@
-use ethereum::TransactionV0 as Transaction;
+use ethereum::{TransactionV0 as LegacyTransaction, TransactionV2 as Transaction};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Action {
-    Transact(Transaction),
+    Transact(LegacyTransaction),
+    /// EIP-2718 typed transaction, EIP-2930 or EIP-1559.
+    TransactTyped(Transaction),
 }
@@ -17,6 +17,9 @@ pub struct Call {
     pub gas_limit: u64,
     pub gas_price: Option<U256>,
     pub nonce: Option<U256>,
+    /// EIP-2930 access list
+    #[serde(default)]
+    pub access_list: Vec<(H160, Vec<H256>)>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -27,6 +30,9 @@ pub struct Create {
     pub gas_limit: u64,
     pub gas_price: Option<U256>,
     pub nonce: Option<U256>,
+    /// EIP-2930 access list
+    #[serde(default)]
+    pub access_list: Vec<(H160, Vec<H256>)>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -38,4 +44,7 @@ pub struct Create2 {
     pub gas_limit: u64,
     pub gas_price: Option<U256>,
     pub nonce: Option<U256>,
+    /// EIP-2930 access list
+    #[serde(default)]
+    pub access_list: Vec<(H160, Vec<H256>)>,
 }
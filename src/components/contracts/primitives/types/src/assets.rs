@@ -0,0 +1,37 @@
+use fp_utils::hashing::keccak_256;
+use primitive_types::{H160, H256};
+use serde::{Deserialize, Serialize};
+
+/// Prefix of the FRC20 contract addresses of the custom assets.
+pub const ASSET_ADDRESS_PREFIX: [u8; 4] = [0xff; 4];
+
+/// The FRC20 attributes of a custom asset, taken from its definition on the UTXO side.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetMetadata {
+    /// The asset type code.
+    pub asset: H256,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+impl AssetMetadata {
+    /// The FRC20 contract address of the asset.
+    pub fn address(&self) -> H160 {
+        asset_address(&self.asset)
+    }
+}
+
+/// The FRC20 contract address of a custom asset, the prefix followed by the
+/// first 16 bytes of the keccak hash of the asset type code.
+pub fn asset_address(asset: &H256) -> H160 {
+    let mut address = [0u8; 20];
+    address[..4].copy_from_slice(&ASSET_ADDRESS_PREFIX);
+    address[4..].copy_from_slice(&keccak_256(asset.as_bytes())[..16]);
+    H160::from(address)
+}
+
+/// Whether `address` belongs to the address range of the custom assets.
+pub fn is_asset_address(address: &H160) -> bool {
+    address.as_bytes().starts_with(&ASSET_ADDRESS_PREFIX)
+}
@@ -102,6 +102,18 @@ impl From<H160> for Address32 {
     }
 }
 
+impl Address32 {
+    /// The EVM address of the account, the original one for accounts mapped from
+    /// an EVM address, the last 20 bytes of the keccak hash otherwise.
+    pub fn to_evm_address(&self) -> H160 {
+        if self.0.starts_with(b"evm:") {
+            H160::from_slice(&self.0[4..24])
+        } else {
+            H160::from_slice(&keccak_256(&self.0)[12..])
+        }
+    }
+}
+
 /// A wrapper of the Hash type defined inf fixed-hash crate.
 #[derive(
     Clone, Eq, PartialEq, Ord, PartialOrd, Default, Hash, Serialize, Deserialize, Debug,
@@ -5,5 +5,6 @@ pub use primitive_types::{H160, H256, U256};
 
 pub mod actions;
 pub mod assemble;
+pub mod assets;
 pub mod crypto;
 pub mod transaction;
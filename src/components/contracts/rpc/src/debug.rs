@@ -0,0 +1,289 @@
+use crate::eth::{request_access_list, request_gas_price, EthApiImpl};
+use crate::internal_err;
+use baseapp::{AssetLookup, BaseApp};
+use ethereum::TransactionV2 as EthereumTransaction;
+use ethereum_types::{H256, U256};
+use evm::ExitReason;
+use fp_core::context::Context;
+use fp_evm::{BlockId, CallOrCreateInfo, Runner};
+use fp_rpc_core::types::{
+    BlockNumber, Bytes, CallFrame, CallRequest, StructLog, StructLogTrace, TraceParams,
+    TraceResult,
+};
+use fp_rpc_core::DebugApi;
+use fp_traits::base::BaseProvider;
+use fp_types::actions::evm::{Call, Create};
+use jsonrpc_core::Result;
+use log::debug;
+use module_evm::runtime::tracer::{self, TraceConfig, Tracer};
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+const CALL_TRACER: &str = "callTracer";
+
+pub struct DebugApiImpl {
+    account_base_app: Arc<RwLock<BaseApp>>,
+    eth: EthApiImpl,
+    asset_of: AssetLookup,
+}
+
+impl DebugApiImpl {
+    pub fn new(
+        account_base_app: Arc<RwLock<BaseApp>>,
+        eth: EthApiImpl,
+        asset_of: AssetLookup,
+    ) -> Self {
+        Self {
+            account_base_app,
+            eth,
+            asset_of,
+        }
+    }
+
+    /// Execute a committed ethereum transaction on `ctx`.
+    fn replay_transaction(
+        ctx: &Context,
+        transaction: EthereumTransaction,
+    ) -> Result<CallOrCreateInfo> {
+        let source = module_ethereum::App::<BaseApp>::recover_signer(&transaction)
+            .ok_or_else(|| internal_err("replay transaction: invalid signature"))?;
+
+        module_ethereum::App::<BaseApp>::execute_transaction(ctx, source, &transaction)
+            .map(|(_, _, info)| info)
+            .map_err(|err| internal_err(format!("replay transaction error: {:?}", err)))
+    }
+}
+
+impl DebugApi for DebugApiImpl {
+    fn trace_transaction(
+        &self,
+        hash: H256,
+        params: Option<TraceParams>,
+    ) -> Result<TraceResult> {
+        debug!(target: "eth_rpc", "trace_transaction, hash:{:?}, params:{:?}", hash, params);
+
+        let config = trace_config(&params)?;
+        let app = self.account_base_app.read();
+        let (number, index) = app
+            .transaction_index(hash)
+            .ok_or_else(|| internal_err(format!("transaction not found: {:?}", hash)))?;
+        let block = app
+            .current_block(Some(BlockId::Number(number)))
+            .ok_or_else(|| internal_err(format!("block not found: {}", number)))?;
+        let parent = number
+            .as_u64()
+            .checked_sub(1)
+            .ok_or_else(|| internal_err("cannot trace a transaction of block 0"))?;
+
+        // execute on top of the parent block, with the environment of the block
+        let mut ctx = app.create_context_at(parent).map_err(|err| {
+            internal_err(format!("create query context error: {:?}", err))
+        })?;
+        drop(app);
+        ctx.header
+            .mut_time()
+            .set_seconds(block.header.timestamp as i64);
+        ctx.header.height = block.header.number.as_u64() as i64;
+        ctx.header.proposer_address = Vec::from(block.header.beneficiary.as_bytes());
+        ctx.block_proposer = None;
+
+        // replay all the transactions in front of it, including the findora ones
+        let txs = self.eth.block_txs(number.as_u64())?;
+        BaseApp::replay_block_until(&ctx, &txs, hash, &self.asset_of)
+            .map_err(|err| internal_err(format!("replay block error: {:?}", err)))?;
+        let transaction = block
+            .transactions
+            .into_iter()
+            .nth(index as usize)
+            .ok_or_else(|| internal_err("transaction not found in block"))?;
+
+        let tracer = Tracer::new(config);
+        let info = tracer.trace(|| Self::replay_transaction(&ctx, transaction))?;
+        let (reason, used_gas) = match info {
+            CallOrCreateInfo::Call(info) => (info.exit_reason, info.used_gas),
+            CallOrCreateInfo::Create(info) => (info.exit_reason, info.used_gas),
+        };
+
+        trace_result(tracer, &params, reason, used_gas)
+    }
+
+    fn trace_call(
+        &self,
+        request: CallRequest,
+        number: Option<BlockNumber>,
+        params: Option<TraceParams>,
+    ) -> Result<TraceResult> {
+        debug!(target: "eth_rpc", "trace_call, request:{:?}, number:{:?}, params:{:?}", request, number, params);
+
+        let CallRequest {
+            from,
+            to,
+            gas_price,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            gas,
+            value,
+            data,
+            nonce,
+            access_list,
+            ..
+        } = request;
+        let gas_price =
+            request_gas_price(gas_price, max_fee_per_gas, max_priority_fee_per_gas);
+        let access_list = request_access_list(access_list);
+
+        let (ctx, block) = self.eth.create_call_context(number)?;
+        let gas_limit = match gas {
+            Some(amount) => amount,
+            None => {
+                if let Some(block) = block {
+                    block.header.gas_limit
+                } else {
+                    <BaseApp as module_evm::Config>::BlockGasLimit::get()
+                }
+            }
+        };
+        let data = data.map(|d| d.0).unwrap_or_default();
+
+        let mut config = <BaseApp as module_ethereum::Config>::config().clone();
+        config.estimate = true;
+
+        let tracer = Tracer::new(trace_config(&params)?);
+        let (reason, used_gas) = match to {
+            Some(to) => {
+                let call = Call {
+                    source: from.unwrap_or_default(),
+                    target: to,
+                    input: data,
+                    value: value.unwrap_or_default(),
+                    gas_limit: gas_limit.as_u64(),
+                    gas_price,
+                    nonce,
+                    access_list,
+                };
+
+                let info = tracer
+                    .trace(|| {
+                        <BaseApp as module_ethereum::Config>::Runner::call(
+                            &ctx, call, &config,
+                        )
+                    })
+                    .map_err(|err| {
+                        internal_err(format!("evm runner call error: {:?}", err))
+                    })?;
+                (info.exit_reason, info.used_gas)
+            }
+            None => {
+                let create = Create {
+                    source: from.unwrap_or_default(),
+                    init: data,
+                    value: value.unwrap_or_default(),
+                    gas_limit: gas_limit.as_u64(),
+                    gas_price,
+                    nonce,
+                    access_list,
+                };
+
+                let info = tracer
+                    .trace(|| {
+                        <BaseApp as module_ethereum::Config>::Runner::create(
+                            &ctx, create, &config,
+                        )
+                    })
+                    .map_err(|err| {
+                        internal_err(format!("evm runner create error: {:?}", err))
+                    })?;
+                (info.exit_reason, info.used_gas)
+            }
+        };
+
+        trace_result(tracer, &params, reason, used_gas)
+    }
+}
+
+fn is_call_tracer(params: &Option<TraceParams>) -> bool {
+    params
+        .as_ref()
+        .and_then(|p| p.tracer.as_deref())
+        .map_or(false, |t| t == CALL_TRACER)
+}
+
+fn trace_config(params: &Option<TraceParams>) -> Result<TraceConfig> {
+    let params = params.clone().unwrap_or_default();
+    if let Some(name) = params.tracer.as_ref() {
+        if name != CALL_TRACER {
+            return Err(internal_err(format!("unsupported tracer: {}", name)));
+        }
+    }
+
+    Ok(TraceConfig {
+        struct_logs: params.tracer.is_none(),
+        disable_storage: params.disable_storage.unwrap_or_default(),
+        disable_memory: params.disable_memory.unwrap_or_default(),
+        disable_stack: params.disable_stack.unwrap_or_default(),
+    })
+}
+
+fn trace_result(
+    tracer: Tracer,
+    params: &Option<TraceParams>,
+    reason: ExitReason,
+    used_gas: U256,
+) -> Result<TraceResult> {
+    let trace = tracer.finish(used_gas);
+    let call = trace
+        .call
+        .ok_or_else(|| internal_err("no call traced in execution"))?;
+
+    if is_call_tracer(params) {
+        return Ok(TraceResult::CallTracer(call_frame_build(call)));
+    }
+
+    Ok(TraceResult::StructLogs(StructLogTrace {
+        gas: used_gas.low_u64(),
+        failed: tracer::exit_error(&reason).is_some(),
+        return_value: Bytes(call.output),
+        struct_logs: trace
+            .struct_logs
+            .into_iter()
+            .map(struct_log_build)
+            .collect(),
+    }))
+}
+
+fn call_frame_build(frame: tracer::CallFrame) -> CallFrame {
+    CallFrame {
+        call_type: frame.call_type.as_str().to_string(),
+        from: frame.from,
+        to: frame.to,
+        value: frame.value,
+        gas: U256::from(frame.gas),
+        gas_used: U256::from(frame.gas_used),
+        input: Bytes(frame.input),
+        output: Bytes(frame.output),
+        error: frame.error,
+        calls: frame.calls.into_iter().map(call_frame_build).collect(),
+    }
+}
+
+fn struct_log_build(log: tracer::StructLog) -> StructLog {
+    StructLog {
+        pc: log.pc,
+        op: tracer::opcode_name(log.op),
+        gas: log.gas,
+        gas_cost: log.gas_cost,
+        depth: log.depth,
+        stack: log.stack,
+        memory: log.memory.map(|memory| {
+            memory
+                .chunks(32)
+                .map(|word| {
+                    let mut buf = [0u8; 32];
+                    buf[..word.len()].copy_from_slice(word);
+                    H256::from(buf)
+                })
+                .collect()
+        }),
+        storage: log.storage,
+    }
+}
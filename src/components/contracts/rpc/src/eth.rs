@@ -1,16 +1,18 @@
-use crate::{error_on_execution_failure, internal_err};
+use crate::{error_on_execution_failure, internal_err, sync::SyncStatusProvider};
 use baseapp::{extensions::SignedExtra, BaseApp};
 use ethereum::{
-    BlockV0 as EthereumBlock, LegacyTransactionMessage as EthereumTransactionMessage,
-    LegacyTransactionMessage, TransactionV0 as EthereumTransaction,
+    BlockV2 as EthereumBlock, EIP1559TransactionMessage, EIP2930TransactionMessage,
+    EnvelopedEncodable, LegacyTransactionMessage, TransactionV0 as LegacyTransaction,
+    TransactionV2 as EthereumTransaction,
 };
 use ethereum_types::{BigEndianHash, H160, H256, H512, H64, U256, U64};
 use evm::{ExitError, ExitReason};
+use fp_core::context::Context;
 use fp_evm::{BlockId, Runner, TransactionStatus};
 use fp_rpc_core::types::{
-    Block, BlockNumber, BlockTransactions, Bytes, CallRequest, Filter, FilteredParams,
-    Index, Log, Receipt, Rich, RichBlock, SyncStatus, Transaction, TransactionRequest,
-    Work,
+    AccessListItem, Block, BlockNumber, BlockTransactions, Bytes, CallRequest,
+    FeeHistory, Filter, FilteredParams, Index, Log, Receipt, Rich, RichBlock,
+    SyncStatus, Transaction, TransactionRequest, Work,
 };
 use fp_rpc_core::EthApi;
 use fp_traits::{
@@ -27,26 +29,33 @@ use fp_utils::tx::EvmRawTxWrapper;
 use jsonrpc_core::{futures::future, BoxFuture, Result};
 use lazy_static::lazy_static;
 use log::{debug, warn};
+use module_ethereum::TransactionData;
 use parking_lot::RwLock;
 use ruc::eg;
 use sha3::{Digest, Keccak256};
 use std::collections::BTreeMap;
-use std::convert::Into;
+use std::convert::{Into, TryFrom};
 use std::ops::Range;
 use std::sync::{mpsc, Arc};
-use tendermint::abci::Code;
+use tendermint::{abci::Code, block::Height};
 use tendermint_rpc::{Client, HttpClient};
 use tokio::runtime::Runtime;
 
 lazy_static! {
-    static ref RT: Runtime =
+    pub(crate) static ref RT: Runtime =
         Runtime::new().expect("Failed to create thread pool executor");
 }
 
+/// Maximum number of blocks queried by `eth_feeHistory`.
+const MAX_FEE_HISTORY: u64 = 1024;
+/// Number of recent blocks used to suggest a priority fee.
+const PRIORITY_FEE_BLOCKS: u64 = 20;
+
 pub struct EthApiImpl {
     account_base_app: Arc<RwLock<BaseApp>>,
     signers: Vec<SecpPair>,
     tm_client: Arc<HttpClient>,
+    sync_status: Arc<SyncStatusProvider>,
     max_past_logs: u32,
 }
 
@@ -55,12 +64,14 @@ impl EthApiImpl {
         url: String,
         account_base_app: Arc<RwLock<BaseApp>>,
         signers: Vec<SecpPair>,
+        sync_status: Arc<SyncStatusProvider>,
         max_past_logs: u32,
     ) -> Self {
         Self {
             account_base_app,
             signers,
             tm_client: Arc::new(HttpClient::new(url.as_str()).unwrap()),
+            sync_status,
             max_past_logs,
         }
     }
@@ -120,6 +131,62 @@ impl EthApiImpl {
             .map_err(internal_err)?;
         Ok(range)
     }
+
+    /// The raw transactions of the tendermint block at `height`, in the delivered order.
+    pub fn block_txs(&self, height: u64) -> Result<Vec<Vec<u8>>> {
+        let height = Height::try_from(height).map_err(internal_err)?;
+        let client = self.tm_client.clone();
+        let (tx, rx) = mpsc::channel();
+        RT.spawn(async move {
+            let resp = client.block(height).await;
+            tx.send(resp).unwrap();
+        });
+
+        let resp = rx
+            .recv()
+            .map_err(internal_err)?
+            .map_err(|err| internal_err(format!("tendermint block error: {}", err)))?;
+        Ok(resp
+            .block
+            .data
+            .iter()
+            .map(|tx| tx.as_bytes().to_vec())
+            .collect())
+    }
+
+    /// Create the context to execute a call on top of the state of the given
+    /// block, with the block header applied to the EVM environment.
+    pub fn create_call_context(
+        &self,
+        number: Option<BlockNumber>,
+    ) -> Result<(Context, Option<EthereumBlock>)> {
+        let height = self.block_number_to_height(number)?;
+        let app = self.account_base_app.read();
+        let (ctx, block) = match height {
+            Some(h) if h > 0 => (
+                app.create_context_at(h),
+                app.current_block(Some(BlockId::Number(U256::from(h)))),
+            ),
+            _ => (
+                app.create_query_context(height, false),
+                app.current_block(None),
+            ),
+        };
+
+        let mut ctx = ctx.map_err(|err| {
+            internal_err(format!("create query context error: {:?}", err))
+        })?;
+        if let Some(block) = block.as_ref() {
+            ctx.header
+                .mut_time()
+                .set_seconds(block.header.timestamp as i64);
+            ctx.header.height = block.header.number.as_u64() as i64;
+            ctx.header.proposer_address = Vec::from(block.header.beneficiary.as_bytes());
+            // the beneficiary is already the EVM address of the proposer
+            ctx.block_proposer = None;
+        }
+        Ok((ctx, block))
+    }
 }
 
 impl EthApi for EthApiImpl {
@@ -190,11 +257,14 @@ impl EthApi for EthApiImpl {
             Err(e) => return Box::pin(future::err(e)),
         };
 
-        let message = EthereumTransactionMessage {
+        let message = LegacyTransactionMessage {
             nonce,
-            gas_price: request.gas_price.unwrap_or_else(
-                <BaseApp as module_evm::Config>::FeeCalculator::min_gas_price,
-            ),
+            gas_price: request
+                .gas_price
+                .or(request.max_fee_per_gas)
+                .unwrap_or_else(
+                    <BaseApp as module_evm::Config>::FeeCalculator::min_gas_price,
+                ),
             gas_limit: request.gas.unwrap_or_else(U256::max_value),
             value: request.value.unwrap_or_else(U256::zero),
             input: request.data.map(|s| s.into_vec()).unwrap_or_default(),
@@ -259,25 +329,32 @@ impl EthApi for EthApiImpl {
         Box::pin(future::ok(transaction_hash))
     }
 
-    fn call(&self, request: CallRequest, _: Option<BlockNumber>) -> Result<Bytes> {
-        debug!(target: "eth_rpc", "call, request:{:?}", request);
+    fn call(&self, request: CallRequest, number: Option<BlockNumber>) -> Result<Bytes> {
+        debug!(target: "eth_rpc", "call, request:{:?}, number:{:?}", request, number);
 
         let CallRequest {
             from,
             to,
             gas_price,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
             gas,
             value,
             data,
             nonce,
+            access_list,
+            ..
         } = request;
+        let gas_price =
+            request_gas_price(gas_price, max_fee_per_gas, max_priority_fee_per_gas);
+        let access_list = request_access_list(access_list);
 
-        let block = self.account_base_app.read().current_block(None);
+        let (ctx, block) = self.create_call_context(number)?;
         // use given gas limit or query current block's limit
         let gas_limit = match gas {
             Some(amount) => amount,
             None => {
-                if let Some(block) = block.clone() {
+                if let Some(block) = block {
                     block.header.gas_limit
                 } else {
                     <BaseApp as module_evm::Config>::BlockGasLimit::get()
@@ -289,21 +366,6 @@ impl EthApi for EthApiImpl {
         let mut config = <BaseApp as module_ethereum::Config>::config().clone();
         config.estimate = true;
 
-        let mut ctx = self
-            .account_base_app
-            .read()
-            .create_query_context(None, false)
-            .map_err(|err| {
-                internal_err(format!("create query context error: {:?}", err))
-            })?;
-        if let Some(block) = block {
-            ctx.header
-                .mut_time()
-                .set_seconds(block.header.timestamp as i64);
-            ctx.header.height = block.header.number.as_u64() as i64;
-            ctx.header.proposer_address = Vec::from(block.header.beneficiary.as_bytes())
-        }
-
         match to {
             Some(to) => {
                 let call = Call {
@@ -314,6 +376,7 @@ impl EthApi for EthApiImpl {
                     gas_limit: gas_limit.as_u64(),
                     gas_price,
                     nonce,
+                    access_list,
                 };
 
                 let info = <BaseApp as module_ethereum::Config>::Runner::call(
@@ -336,6 +399,7 @@ impl EthApi for EthApiImpl {
                     gas_limit: gas_limit.as_u64(),
                     gas_price,
                     nonce,
+                    access_list,
                 };
 
                 let info = <BaseApp as module_ethereum::Config>::Runner::create(
@@ -354,8 +418,7 @@ impl EthApi for EthApiImpl {
     }
 
     fn syncing(&self) -> Result<SyncStatus> {
-        // TODO
-        Ok(SyncStatus::None)
+        self.sync_status.sync_status()
     }
 
     fn author(&self) -> Result<H160> {
@@ -376,6 +439,95 @@ impl EthApi for EthApiImpl {
         Ok(<BaseApp as module_evm::Config>::FeeCalculator::min_gas_price())
     }
 
+    fn fee_history(
+        &self,
+        block_count: U256,
+        newest_block: BlockNumber,
+        reward_percentiles: Option<Vec<f64>>,
+    ) -> Result<FeeHistory> {
+        debug!(target: "eth_rpc", "fee_history, block_count:{:?}, newest_block:{:?}, reward_percentiles:{:?}", block_count, newest_block, reward_percentiles);
+
+        if let Some(percentiles) = reward_percentiles.as_ref() {
+            let mut previous = 0.0;
+            for p in percentiles {
+                if !(0.0..=100.0).contains(p) || *p < previous {
+                    return Err(internal_err(format!(
+                        "invalid reward percentiles: {:?}",
+                        percentiles
+                    )));
+                }
+                previous = *p;
+            }
+        }
+
+        let app = self.account_base_app.read();
+        let current_number = app.current_block_number().unwrap_or_default();
+        let newest = match newest_block {
+            BlockNumber::Num(num) => U256::from(num).min(current_number),
+            BlockNumber::Earliest => U256::zero(),
+            BlockNumber::Latest | BlockNumber::Pending => current_number,
+            BlockNumber::Hash { hash, .. } => app
+                .current_block(Some(BlockId::Hash(hash)))
+                .map(|block| block.header.number)
+                .ok_or_else(|| internal_err(format!("block not found: {:?}", hash)))?,
+        };
+        let block_count = block_count
+            .min(U256::from(MAX_FEE_HISTORY))
+            .min(newest.saturating_add(U256::one()));
+        let oldest = newest.saturating_add(U256::one()) - block_count;
+
+        let base_fee = module_ethereum::App::<BaseApp>::base_fee();
+        let mut fee_history = FeeHistory {
+            oldest_block: oldest,
+            base_fee_per_gas: vec![],
+            gas_used_ratio: vec![],
+            reward: reward_percentiles.as_ref().map(|_| vec![]),
+        };
+
+        let mut number = oldest;
+        while number <= newest && !block_count.is_zero() {
+            let id = Some(BlockId::Number(number));
+            let block = app.current_block(id.clone());
+            let receipts = app.current_receipts(id).unwrap_or_default();
+
+            fee_history.base_fee_per_gas.push(base_fee);
+            fee_history.gas_used_ratio.push(match block.as_ref() {
+                Some(block) if !block.header.gas_limit.is_zero() => {
+                    block.header.gas_used.low_u64() as f64
+                        / block.header.gas_limit.low_u64() as f64
+                }
+                _ => 0.0,
+            });
+            if let (Some(percentiles), Some(reward)) =
+                (reward_percentiles.as_ref(), fee_history.reward.as_mut())
+            {
+                reward.push(block_rewards(block, receipts, base_fee, percentiles));
+            }
+            number = number.saturating_add(U256::one());
+        }
+        // the base fee of the block following the newest one
+        fee_history.base_fee_per_gas.push(base_fee);
+
+        Ok(fee_history)
+    }
+
+    fn max_priority_fee_per_gas(&self) -> Result<U256> {
+        let history = self.fee_history(
+            U256::from(PRIORITY_FEE_BLOCKS),
+            BlockNumber::Latest,
+            Some(vec![50.0]),
+        )?;
+
+        // the highest median priority fee over the recent blocks
+        Ok(history
+            .reward
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .max()
+            .unwrap_or_default())
+    }
+
     fn block_number(&self) -> Result<U256> {
         let height = self
             .account_base_app
@@ -528,18 +680,21 @@ impl EthApi for EthApiImpl {
     }
 
     fn send_raw_transaction(&self, bytes: Bytes) -> BoxFuture<Result<H256>> {
-        let transaction = match rlp::decode::<EthereumTransaction>(&bytes.0[..]) {
-            Ok(transaction) => transaction,
-            Err(_) => {
+        let transaction = match decode_raw_transaction(&bytes.0[..]) {
+            Some(transaction) => transaction,
+            None => {
                 return Box::pin(future::err(internal_err("decode transaction failed")));
             }
         };
         debug!(target: "eth_rpc", "send_raw_transaction :{:?}", transaction);
 
-        let transaction_hash =
-            H256::from_slice(Keccak256::digest(&rlp::encode(&transaction)).as_slice());
-        let function =
-            actions::Action::Ethereum(actions::ethereum::Action::Transact(transaction));
+        let transaction_hash = transaction.hash();
+        // legacy transactions keep their own action, so that they are still
+        // accepted by the nodes not supporting typed transactions yet.
+        let function = actions::Action::Ethereum(match transaction {
+            EthereumTransaction::Legacy(tx) => actions::ethereum::Action::Transact(tx),
+            tx => actions::ethereum::Action::TransactTyped(tx),
+        });
         let txn = serde_json::to_vec(
             &UncheckedTransaction::<SignedExtra>::new_unsigned(function),
         )
@@ -578,46 +733,14 @@ impl EthApi for EthApiImpl {
     ) -> Result<U256> {
         debug!(target: "eth_rpc", "estimate_gas, block number {:?} request:{:?}", number, request);
 
-        let (block_id, pending) = match number.unwrap_or(BlockNumber::Latest) {
-            BlockNumber::Num(num) => {
-                let range = self.version_range()?;
-                if range.contains(&num) || num == range.end {
-                    (Some(BlockId::Number(U256::from(num))), false)
-                } else {
-                    return Err(internal_err(format!(
-                        "block number: {} exceeds version range: {:?}",
-                        num, range
-                    )));
-                }
-            }
-            BlockNumber::Earliest => (
-                Some(BlockId::Number(U256::from(self.version_range()?.start))),
-                false,
-            ),
-            BlockNumber::Hash {
-                hash,
-                require_canonical: _,
-            } => {
-                if let Some(block) = self
-                    .account_base_app
-                    .read()
-                    .current_block(Some(BlockId::Hash(hash)))
-                {
-                    (Some(BlockId::Number(block.header.number)), false)
-                } else {
-                    return Err(internal_err("Cannot find the specified block"));
-                }
-            }
-            BlockNumber::Latest => (None, false),
-            BlockNumber::Pending => (None, true),
-        };
-
+        // Resolve the block up front, so that a pruned or unknown block fails
+        // before running the binary search.
+        let (_, block) = self.create_call_context(number.clone())?;
         let gas_limit = <BaseApp as module_evm::Config>::BlockGasLimit::get();
 
         let mut highest = if let Some(gas) = request.gas {
             gas
-        } else if let Some(block) = self.account_base_app.read().current_block(block_id)
-        {
+        } else if let Some(block) = block {
             block.header.gas_limit
         } else {
             gas_limit
@@ -625,9 +748,12 @@ impl EthApi for EthApiImpl {
 
         // recap gas limit according to account balance
         if let Some(from) = request.from {
-            let gas_price = request.gas_price.unwrap_or_default();
+            let gas_price = request
+                .gas_price
+                .or(request.max_fee_per_gas)
+                .unwrap_or_default();
             if gas_price > U256::zero() {
-                let balance = self.balance(from, None).unwrap_or_default();
+                let balance = self.balance(from, number.clone()).unwrap_or_default();
                 let mut available = balance;
                 if let Some(value) = request.value {
                     if value > available {
@@ -659,23 +785,24 @@ impl EthApi for EthApiImpl {
         let execute_call_or_create = move |request: CallRequest,
                                            gas_limit|
               -> Result<ExecuteResult> {
-            let ctx = self
-                .account_base_app
-                .read()
-                .create_query_context(if pending { None } else { Some(0) }, false)
-                .map_err(|err| {
-                    internal_err(format!("create query context error: {:?}", err))
-                })?;
+            let (ctx, _) = self.create_call_context(number.clone())?;
 
             let CallRequest {
                 from,
                 to,
                 gas_price,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
                 gas,
                 value,
                 data,
                 nonce,
+                access_list,
+                ..
             } = request;
+            let gas_price =
+                request_gas_price(gas_price, max_fee_per_gas, max_priority_fee_per_gas);
+            let access_list = request_access_list(access_list);
 
             let gas_limit = core::cmp::min(
                 gas.unwrap_or_else(|| U256::from(gas_limit)).low_u64(),
@@ -695,6 +822,7 @@ impl EthApi for EthApiImpl {
                         gas_limit,
                         gas_price,
                         nonce,
+                        access_list,
                     };
 
                     let info = <BaseApp as module_ethereum::Config>::Runner::call(
@@ -719,6 +847,7 @@ impl EthApi for EthApiImpl {
                         gas_limit,
                         gas_price,
                         nonce,
+                        access_list,
                     };
 
                     let info = <BaseApp as module_ethereum::Config>::Runner::create(
@@ -914,6 +1043,7 @@ impl EthApi for EthApiImpl {
                 );
                 let receipt = receipts[index].clone();
                 let status = statuses[index].clone();
+                let transaction = block.transactions[index].clone();
                 let mut cumulative_receipts = receipts;
                 cumulative_receipts.truncate((status.transaction_index + 1) as usize);
 
@@ -967,6 +1097,11 @@ impl EthApi for EthApiImpl {
                     status_code: Some(U64::from(receipt.state_root.to_low_u64_be())),
                     logs_bloom: receipt.logs_bloom,
                     state_root: None,
+                    effective_gas_price:
+                        TransactionData::from(&transaction).effective_gas_price(
+                            module_ethereum::App::<BaseApp>::base_fee(),
+                        ),
+                    transaction_type: transaction_type(&transaction),
                 }));
             }
             _ => Ok(None),
@@ -1064,7 +1199,7 @@ impl EthApi for EthApiImpl {
 pub fn sign_transaction_message(
     message: LegacyTransactionMessage,
     private_key: &H256,
-) -> ruc::Result<EthereumTransaction> {
+) -> ruc::Result<LegacyTransaction> {
     let signing_message = libsecp256k1::Message::parse_slice(&message.hash()[..])
         .map_err(|_| eg!("invalid signing message"))?;
     let secret = &libsecp256k1::SecretKey::parse_slice(&private_key[..])
@@ -1079,7 +1214,7 @@ pub fn sign_transaction_message(
     let r = H256::from_slice(&rs[0..32]);
     let s = H256::from_slice(&rs[32..64]);
 
-    Ok(EthereumTransaction {
+    Ok(LegacyTransaction {
         nonce: message.nonce,
         gas_price: message.gas_price,
         gas_limit: message.gas_limit,
@@ -1145,19 +1280,13 @@ fn rich_block_build(
                         block
                             .transactions
                             .iter()
-                            .map(|transaction| {
-                                H256::from_slice(
-                                    Keccak256::digest(&rlp::encode(
-                                        &transaction.clone(),
-                                    ))
-                                    .as_slice(),
-                                )
-                            })
+                            .map(|transaction| transaction.hash())
                             .collect(),
                     )
                 }
             },
             size: Some(U256::from(rlp::encode(&block).len() as u32)),
+            base_fee_per_gas: Some(module_ethereum::App::<BaseApp>::base_fee()),
         },
         extra_info: BTreeMap::new(),
     }
@@ -1172,10 +1301,37 @@ fn transaction_build(
         Ok(p) => Some(p),
         Err(_e) => None,
     };
+    let transaction_data = TransactionData::from(&transaction);
+    // the price actually paid once included in a block, the fee cap otherwise
+    let gas_price = if block.is_some() {
+        transaction_data.effective_gas_price(module_ethereum::App::<BaseApp>::base_fee())
+    } else {
+        transaction_data.max_gas_price()
+    };
+    let (standard_v, v, r, s) = match &transaction {
+        EthereumTransaction::Legacy(t) => (
+            U256::from(t.signature.standard_v()),
+            U256::from(t.signature.v()),
+            U256::from(t.signature.r().as_bytes()),
+            U256::from(t.signature.s().as_bytes()),
+        ),
+        EthereumTransaction::EIP2930(t) => (
+            U256::from(t.odd_y_parity as u8),
+            U256::from(t.odd_y_parity as u8),
+            U256::from(t.r.as_bytes()),
+            U256::from(t.s.as_bytes()),
+        ),
+        EthereumTransaction::EIP1559(t) => (
+            U256::from(t.odd_y_parity as u8),
+            U256::from(t.odd_y_parity as u8),
+            U256::from(t.r.as_bytes()),
+            U256::from(t.s.as_bytes()),
+        ),
+    };
 
     Transaction {
-        hash: H256::from_slice(Keccak256::digest(&rlp::encode(&transaction)).as_slice()),
-        nonce: transaction.nonce,
+        hash: transaction.hash(),
+        nonce: transaction_data.nonce,
         block_hash: block.as_ref().map(|block| {
             H256::from_slice(Keccak256::digest(&rlp::encode(&block.header)).as_slice())
         }),
@@ -1196,41 +1352,167 @@ fn transaction_build(
         ),
         to: status.as_ref().map_or(
             {
-                match transaction.action {
+                match transaction_data.action {
                     ethereum::TransactionAction::Call(to) => Some(to),
                     _ => None,
                 }
             },
             |status| status.to,
         ),
-        value: transaction.value,
-        gas_price: transaction.gas_price,
-        gas: transaction.gas_limit,
-        input: Bytes(transaction.clone().input),
+        value: transaction_data.value,
+        gas_price,
+        max_fee_per_gas: transaction_data.max_fee_per_gas,
+        max_priority_fee_per_gas: transaction_data.max_priority_fee_per_gas,
+        gas: transaction_data.gas_limit,
+        input: Bytes(transaction_data.input.clone()),
         creates: status.as_ref().and_then(|status| status.contract_address),
-        raw: Bytes(rlp::encode(&transaction).to_vec()),
+        raw: Bytes(EnvelopedEncodable::encode(&transaction).to_vec()),
         public_key: pubkey.as_ref().map(H512::from),
-        chain_id: transaction.signature.chain_id().map(U64::from),
-        standard_v: U256::from(transaction.signature.standard_v()),
-        v: U256::from(transaction.signature.v()),
-        r: U256::from(transaction.signature.r().as_bytes()),
-        s: U256::from(transaction.signature.s().as_bytes()),
+        chain_id: transaction_data.chain_id.map(U64::from),
+        standard_v,
+        v,
+        r,
+        s,
+        transaction_type: transaction_type(&transaction),
+        access_list: match transaction {
+            EthereumTransaction::Legacy(_) => None,
+            _ => Some(
+                transaction_data
+                    .access_list
+                    .into_iter()
+                    .map(|(address, storage_keys)| AccessListItem {
+                        address,
+                        storage_keys,
+                    })
+                    .collect(),
+            ),
+        },
     }
 }
 
 pub fn public_key(transaction: &EthereumTransaction) -> ruc::Result<[u8; 64]> {
     let mut sig = [0u8; 65];
     let mut msg = [0u8; 32];
-    sig[0..32].copy_from_slice(&transaction.signature.r()[..]);
-    sig[32..64].copy_from_slice(&transaction.signature.s()[..]);
-    sig[64] = transaction.signature.standard_v();
-    msg.copy_from_slice(
-        &EthereumTransactionMessage::from(transaction.clone()).hash()[..],
-    );
+    match transaction {
+        EthereumTransaction::Legacy(t) => {
+            sig[0..32].copy_from_slice(&t.signature.r()[..]);
+            sig[32..64].copy_from_slice(&t.signature.s()[..]);
+            sig[64] = t.signature.standard_v();
+            msg.copy_from_slice(&LegacyTransactionMessage::from(t.clone()).hash()[..]);
+        }
+        EthereumTransaction::EIP2930(t) => {
+            sig[0..32].copy_from_slice(&t.r[..]);
+            sig[32..64].copy_from_slice(&t.s[..]);
+            sig[64] = t.odd_y_parity as u8;
+            msg.copy_from_slice(&EIP2930TransactionMessage::from(t.clone()).hash()[..]);
+        }
+        EthereumTransaction::EIP1559(t) => {
+            sig[0..32].copy_from_slice(&t.r[..]);
+            sig[32..64].copy_from_slice(&t.s[..]);
+            sig[64] = t.odd_y_parity as u8;
+            msg.copy_from_slice(&EIP1559TransactionMessage::from(t.clone()).hash()[..]);
+        }
+    }
 
     fp_types::crypto::secp256k1_ecdsa_recover(&sig, &msg)
 }
 
+/// Decode a raw transaction, either a legacy RLP transaction or an EIP-2718
+/// typed transaction envelope.
+fn decode_raw_transaction(bytes: &[u8]) -> Option<EthereumTransaction> {
+    let first = *bytes.first()?;
+    if first > 0x7f {
+        rlp::decode::<LegacyTransaction>(bytes)
+            .ok()
+            .map(EthereumTransaction::Legacy)
+    } else {
+        rlp::decode::<EthereumTransaction>(&rlp::encode(&bytes)).ok()
+    }
+}
+
+fn transaction_type(transaction: &EthereumTransaction) -> U64 {
+    match transaction {
+        EthereumTransaction::Legacy(_) => U64::zero(),
+        EthereumTransaction::EIP2930(_) => U64::one(),
+        EthereumTransaction::EIP1559(_) => U64::from(2),
+    }
+}
+
+/// Gas price of a call request, the EIP-1559 fees are resolved against the base fee.
+pub(crate) fn request_gas_price(
+    gas_price: Option<U256>,
+    max_fee_per_gas: Option<U256>,
+    max_priority_fee_per_gas: Option<U256>,
+) -> Option<U256> {
+    match (gas_price, max_fee_per_gas) {
+        (Some(gas_price), _) => Some(gas_price),
+        (None, Some(max_fee)) => {
+            let base_fee = module_ethereum::App::<BaseApp>::base_fee();
+            Some(max_fee.min(
+                base_fee.saturating_add(max_priority_fee_per_gas.unwrap_or_default()),
+            ))
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn request_access_list(
+    access_list: Option<Vec<AccessListItem>>,
+) -> Vec<(H160, Vec<H256>)> {
+    access_list
+        .unwrap_or_default()
+        .into_iter()
+        .map(|item| (item.address, item.storage_keys))
+        .collect()
+}
+
+/// Priority fees paid in the block at the given percentiles, weighted by gas used.
+fn block_rewards(
+    block: Option<EthereumBlock>,
+    receipts: Vec<ethereum::Receipt>,
+    base_fee: U256,
+    percentiles: &[f64],
+) -> Vec<U256> {
+    let block = match block {
+        Some(block) if !block.transactions.is_empty() => block,
+        _ => return vec![U256::zero(); percentiles.len()],
+    };
+
+    // the receipts record the gas used by each transaction rather than the cumulative one,
+    // see `transaction_receipt`
+    let mut rewards: Vec<(U256, U256)> = block
+        .transactions
+        .iter()
+        .zip(receipts.iter())
+        .map(|(transaction, receipt)| {
+            let price = TransactionData::from(transaction).effective_gas_price(base_fee);
+            (price.saturating_sub(base_fee), receipt.used_gas)
+        })
+        .collect();
+    rewards.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let total_gas = rewards
+        .iter()
+        .fold(U256::zero(), |acc, (_, gas)| acc.saturating_add(*gas));
+    percentiles
+        .iter()
+        .map(|p| {
+            let threshold = total_gas.low_u64() as f64 * p / 100.0;
+            let mut cumulative = 0u64;
+            for (reward, gas) in rewards.iter() {
+                cumulative = cumulative.saturating_add(gas.low_u64());
+                if cumulative as f64 >= threshold {
+                    return *reward;
+                }
+            }
+            rewards
+                .last()
+                .map(|(reward, _)| *reward)
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
 fn filter_range_logs(
     app: Arc<RwLock<BaseApp>>,
     ret: &mut Vec<Log>,
@@ -1,6 +1,6 @@
 use crate::{filter_block_logs, internal_err};
 use baseapp::BaseApp;
-use ethereum::BlockV0 as EthereumBlock;
+use ethereum::BlockV2 as EthereumBlock;
 use ethereum_types::{H256, U256};
 use fp_evm::{BlockId, TransactionStatus};
 use fp_rpc_core::types::{
@@ -71,6 +71,13 @@ impl EthFilterApiImpl {
 
     fn create_filter(&self, filter_type: FilterType) -> Result<U256> {
         let block_number = self.block_number()?;
+        // Pending transaction filters poll the mempool by sequence number.
+        let last_poll = match filter_type {
+            FilterType::PendingTransaction => {
+                self.account_base_app.read().pending_txs.read().sequence()
+            }
+            _ => block_number,
+        };
 
         let pool = self.filter_pool.clone();
         let response = if let Ok(locked) = &mut pool.lock() {
@@ -89,7 +96,7 @@ impl EthFilterApiImpl {
             locked.insert(
                 key,
                 FilterPoolItem {
-                    last_poll: BlockNumber::Num(block_number),
+                    last_poll: BlockNumber::Num(last_poll),
                     filter_type,
                     at_block: block_number,
                 },
@@ -217,9 +224,7 @@ impl EthFilterApi for EthFilterApiImpl {
     }
 
     fn new_pending_transaction_filter(&self) -> Result<U256> {
-        Err(internal_err(
-            "new_pending_transaction_filter method not available.",
-        ))
+        self.create_filter(FilterType::PendingTransaction)
     }
 
     fn filter_changes(&self, index: Index) -> Result<FilterChanges> {
@@ -298,8 +303,27 @@ impl EthFilterApi for EthFilterApiImpl {
                         );
                         Ok(FilterChanges::Logs(ret))
                     }
-                    // Should never reach here.
-                    _ => Err(internal_err("Method not available.")),
+                    // For each transaction entered the mempool since last poll,
+                    // get a vector of ethereum transaction hashes.
+                    FilterType::PendingTransaction => {
+                        let last = pool_item.last_poll.to_min_block_num().unwrap();
+                        let (hashes, next) = self
+                            .account_base_app
+                            .read()
+                            .pending_txs
+                            .read()
+                            .hashes_since(last);
+                        // Update filter `last_poll`.
+                        locked.insert(
+                            key,
+                            FilterPoolItem {
+                                last_poll: BlockNumber::Num(next),
+                                filter_type: pool_item.clone().filter_type,
+                                at_block: pool_item.at_block,
+                            },
+                        );
+                        Ok(FilterChanges::Hashes(hashes))
+                    }
                 }
             } else {
                 Err(internal_err(format!("Filter id {:?} does not exist.", key)))
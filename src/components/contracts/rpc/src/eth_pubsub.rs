@@ -1,9 +1,11 @@
+use crate::sync::SyncStatusProvider;
 use baseapp::BaseApp;
-use ethereum::{BlockV0 as EthereumBlock, Receipt};
+use ethereum::{BlockV2 as EthereumBlock, Receipt};
 use ethereum_types::{H256, U256};
 use fp_evm::BlockId;
 use fp_rpc_core::{
     types::{
+        pubsub::PubSubSyncStatus,
         pubsub::{Kind, Metadata, Params, Result as PubSubResult},
         Bytes, FilteredParams, Header, Log, Rich,
     },
@@ -44,13 +46,18 @@ impl Spawn for SubscriptionTaskExecutor {
 
 pub struct EthPubSubApiImpl {
     account_base_app: Arc<RwLock<BaseApp>>,
+    sync_status: Arc<SyncStatusProvider>,
     subscriptions: SubscriptionManager,
 }
 
 impl EthPubSubApiImpl {
-    pub fn new(account_base_app: Arc<RwLock<BaseApp>>) -> Self {
+    pub fn new(
+        account_base_app: Arc<RwLock<BaseApp>>,
+        sync_status: Arc<SyncStatusProvider>,
+    ) -> Self {
         Self {
             account_base_app,
+            sync_status,
             subscriptions: SubscriptionManager::new(Arc::new(SubscriptionTaskExecutor)),
         }
     }
@@ -160,10 +167,59 @@ impl EthPubSubApiT for EthPubSubApiImpl {
                 });
             }
             Kind::NewPendingTransactions => {
-                warn!(target: "eth_rpc", "subscribe NewPendingTransactions unimplemented");
+                self.subscriptions.add(subscriber, |sink| {
+                    let stream = self
+                        .account_base_app
+                        .read()
+                        .pending_tx_notify
+                        .notification_stream()
+                        .map(|hash| {
+                            debug!(target: "eth_rpc", "subscribe [NewPendingTransactions] received new transaction: {:?}", hash);
+                            Ok::<_, ()>(Ok(PubSubResult::TransactionHash(hash)))
+                        });
+                    stream
+                        .forward(
+                            sink.sink_map_err(|e| warn!(target: "eth_rpc", "Error sending notifications: {:?}", e)),
+                        )
+                        .map(|_| ())
+                });
             }
             Kind::Syncing => {
-                warn!(target: "eth_rpc", "subscribe Syncing unimplemented");
+                let sync_status = self.sync_status.clone();
+                // Only the transitions are pushed, starting with the current status.
+                let mut last_syncing = None;
+                self.subscriptions.add(subscriber, |sink| {
+                    let stream = self
+                        .account_base_app
+                        .read()
+                        .event_notify
+                        .notification_stream()
+                        .filter_map(move |block_id| {
+                            debug!(target: "eth_rpc", "subscribe [Syncing] received new block: {}", block_id);
+                            let syncing = match sync_status.is_syncing() {
+                                Ok(syncing) if last_syncing != Some(syncing) => {
+                                    last_syncing = Some(syncing);
+                                    Some(syncing)
+                                }
+                                Ok(_) => None,
+                                Err(e) => {
+                                    warn!(target: "eth_rpc", "subscribe [Syncing] status error: {:?}", e);
+                                    None
+                                }
+                            };
+                            futures::future::ready(syncing)
+                        })
+                        .map(|syncing| {
+                            Ok::<_, ()>(Ok(PubSubResult::SyncState(PubSubSyncStatus {
+                                syncing,
+                            })))
+                        });
+                    stream
+                        .forward(
+                            sink.sink_map_err(|e| warn!(target: "eth_rpc", "Error sending notifications: {:?}", e)),
+                        )
+                        .map(|_| ())
+                });
             }
         }
     }
@@ -209,6 +265,7 @@ impl SubscriptionResult {
                     Bytes(block.header.nonce.as_bytes().to_vec()),
                 ],
                 size: Some(U256::from(rlp::encode(&block).len() as u32)),
+                base_fee_per_gas: Some(module_ethereum::App::<BaseApp>::base_fee()),
             },
             extra_info: BTreeMap::new(),
         }))
@@ -226,12 +283,7 @@ impl SubscriptionResult {
         let mut log_index: u32 = 0;
         for (receipt_index, receipt) in receipts.into_iter().enumerate() {
             let transaction_hash: Option<H256> = if !receipt.logs.is_empty() {
-                Some(H256::from_slice(
-                    Keccak256::digest(&rlp::encode(
-                        &block.transactions[receipt_index as usize],
-                    ))
-                    .as_slice(),
-                ))
+                Some(block.transactions[receipt_index as usize].hash())
             } else {
                 None
             };
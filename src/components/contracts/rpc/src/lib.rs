@@ -1,18 +1,21 @@
 #![deny(warnings)]
 #![allow(missing_docs)]
 
+mod debug;
 mod eth;
 mod eth_filter;
 mod eth_pubsub;
 mod net;
+mod sync;
 mod web3;
 
-use baseapp::BaseApp;
+use baseapp::{AssetLookup, BaseApp};
 use eth::filter_block_logs;
 use evm::{ExitError, ExitReason};
 use fp_rpc_core::types::pubsub::Metadata;
 use fp_rpc_core::{
-    EthApiServer, EthFilterApiServer, EthPubSubApiServer, NetApiServer, Web3ApiServer,
+    DebugApiServer, EthApiServer, EthFilterApiServer, EthPubSubApiServer, NetApiServer,
+    Web3ApiServer,
 };
 use fp_rpc_server::{rpc_handler, start_http, start_ws, RpcHandler, RpcMiddleware};
 use fp_utils::ecdsa::SecpPair;
@@ -26,13 +29,20 @@ use std::sync::Arc;
 const MAX_PAST_LOGS: u32 = 10000;
 const MAX_STORED_FILTERS: usize = 500;
 
+/// Start the web3 http and ws services, the `debug` namespace replays
+/// historical blocks and is only served when `debug_api` is given.
 pub fn start_web3_service(
     evm_http: String,
     evm_ws: String,
     tendermint_rpc: String,
     app: Arc<RwLock<BaseApp>>,
+    debug_api: Option<AssetLookup>,
 ) -> Box<dyn std::any::Any + Send> {
     let app2 = Arc::new(RwLock::new(app.read().derive_app()));
+    let sync_status = Arc::new(sync::SyncStatusProvider::new(
+        tendermint_rpc.clone(),
+        app.clone(),
+    ));
 
     // PrivateKey: 9f7bebaa5c55464b10150bc2e0fd552e915e2bdbca95cc45ed1c909aca96e7f5
     // Address: 0xf6aca39539374993b37d29ccf0d93fa214ea0af1
@@ -46,6 +56,7 @@ pub fn start_web3_service(
                     tendermint_rpc.clone(),
                     app.clone(),
                     signers.clone(),
+                    sync_status.clone(),
                     MAX_PAST_LOGS,
                 )
                 .to_delegate(),
@@ -57,7 +68,22 @@ pub fn start_web3_service(
                 .to_delegate(),
                 net::NetApiImpl::new().to_delegate(),
                 web3::Web3ApiImpl::new().to_delegate(),
-                eth_pubsub::EthPubSubApiImpl::new(app2.clone()).to_delegate(),
+                eth_pubsub::EthPubSubApiImpl::new(app2.clone(), sync_status.clone())
+                    .to_delegate(),
+                debug_api.clone().map(|asset_of| {
+                    debug::DebugApiImpl::new(
+                        app.clone(),
+                        eth::EthApiImpl::new(
+                            tendermint_rpc.clone(),
+                            app.clone(),
+                            vec![],
+                            sync_status.clone(),
+                            MAX_PAST_LOGS,
+                        ),
+                        asset_of,
+                    )
+                    .to_delegate()
+                }),
             ),
             RpcMiddleware::new(),
         )
@@ -0,0 +1,108 @@
+use crate::{eth::RT, internal_err};
+use baseapp::BaseApp;
+use ethereum_types::U256;
+use fp_rpc_core::types::{SyncInfo, SyncStatus};
+use jsonrpc_core::Result;
+use parking_lot::{Mutex, RwLock};
+use std::{
+    sync::{mpsc, Arc},
+    time::{Duration, Instant},
+};
+use tendermint_rpc::{Client, HttpClient};
+
+/// How long the status of tendermint is cached for
+const STATUS_CACHE_SECS: u64 = 3;
+
+/// Compare the height of the local application with the latest height known
+/// by tendermint, to tell whether the node is still catching up.
+pub struct SyncStatusProvider {
+    account_base_app: Arc<RwLock<BaseApp>>,
+    tm_client: Arc<HttpClient>,
+    /// Height of the application when the web3 service started
+    starting_block: u64,
+    /// The last status of tendermint and when it was fetched
+    status_cache: Mutex<Option<(Instant, (u64, bool))>>,
+}
+
+impl SyncStatusProvider {
+    pub fn new(url: String, account_base_app: Arc<RwLock<BaseApp>>) -> Self {
+        let starting_block = account_base_app
+            .read()
+            .chain_state
+            .read()
+            .height()
+            .unwrap_or_default();
+        Self {
+            account_base_app,
+            tm_client: Arc::new(HttpClient::new(url.as_str()).unwrap()),
+            starting_block,
+            status_cache: Mutex::new(None),
+        }
+    }
+
+    fn current_block(&self) -> Result<u64> {
+        self.account_base_app
+            .read()
+            .chain_state
+            .read()
+            .height()
+            .map_err(internal_err)
+    }
+
+    /// Returns the latest block height of tendermint and whether it is catching up,
+    /// the status is fetched at most once every `STATUS_CACHE_SECS`.
+    fn tendermint_status(&self) -> Result<(u64, bool)> {
+        let mut cache = self.status_cache.lock();
+        if let Some((fetched_at, status)) = *cache {
+            if fetched_at.elapsed() < Duration::from_secs(STATUS_CACHE_SECS) {
+                return Ok(status);
+            }
+        }
+
+        let status = self.fetch_tendermint_status()?;
+        *cache = Some((Instant::now(), status));
+        Ok(status)
+    }
+
+    fn fetch_tendermint_status(&self) -> Result<(u64, bool)> {
+        let client = self.tm_client.clone();
+        let (tx, rx) = mpsc::channel();
+        RT.spawn(async move {
+            let resp = client.status().await;
+            tx.send(resp).unwrap();
+        });
+
+        let status = rx
+            .recv()
+            .map_err(internal_err)?
+            .map_err(|err| internal_err(format!("tendermint status error: {}", err)))?;
+        Ok((
+            status.sync_info.latest_block_height.value(),
+            status.sync_info.catching_up,
+        ))
+    }
+
+    pub fn sync_status(&self) -> Result<SyncStatus> {
+        let current_block = self.current_block()?;
+        let (latest_block, catching_up) = self.tendermint_status()?;
+
+        // the application may lag one block behind tendermint
+        // while the latest block is being committed
+        if !catching_up && current_block + 1 >= latest_block {
+            return Ok(SyncStatus::None);
+        }
+
+        Ok(SyncStatus::Info(SyncInfo {
+            starting_block: U256::from(self.starting_block.min(current_block)),
+            current_block: U256::from(current_block),
+            highest_block: U256::from(latest_block.max(current_block)),
+            warp_chunks_amount: None,
+            warp_chunks_processed: None,
+        }))
+    }
+
+    pub fn is_syncing(&self) -> Result<bool> {
+        self.sync_status()
+            .map(|status| matches!(status, SyncStatus::Info(_)))
+    }
+}
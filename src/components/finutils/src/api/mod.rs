@@ -3,8 +3,13 @@
 //!
 
 use {
-    ledger::staking::{
-        self, StakerMemo, TendermintAddr, MAX_POWER_PERCENT_PER_VALIDATOR,
+    ledger::{
+        data_model::SignatureRules,
+        staking::{
+            self,
+            proposal::{Proposal, TallyResult},
+            AutoCompound, BlockHeight, CommissionPolicy, StakerMemo, TendermintAddr,
+        },
     },
     serde::{Deserialize, Serialize},
 };
@@ -20,9 +25,13 @@ pub struct ValidatorList {
 
 impl ValidatorList {
     #[allow(missing_docs)]
-    pub fn new(cur_height: u64, validators: Vec<Validator>) -> Self {
+    pub fn new(
+        threshold: [u128; 2],
+        cur_height: u64,
+        validators: Vec<Validator>,
+    ) -> Self {
         ValidatorList {
-            threshold: MAX_POWER_PERCENT_PER_VALIDATOR,
+            threshold,
             validator_cnt: validators.len() as u64,
             cur_height,
             validators,
@@ -61,6 +70,18 @@ impl Validator {
     }
 }
 
+/// A governance proposal,
+/// along with the real-time tally if it is in the voting period.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ProposalDetail {
+    #[allow(missing_docs)]
+    pub proposal: Proposal,
+    /// current block height
+    pub cur_height: BlockHeight,
+    /// the tally by current bonded power, only for a proposal in voting
+    pub cur_tally: Option<TallyResult>,
+}
+
 /// The detail information of a validator which includes
 /// staking information, expected annulation, and voting power etc.
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
@@ -95,6 +116,27 @@ pub struct ValidatorDetail {
     pub validator_realtime_apy: [u128; 2],
     /// total number of its delegators
     pub delegator_cnt: u64,
+    /// blocks missed in the sliding window of jailing
+    #[serde(default)]
+    pub missed_block_cnt: u64,
+    /// if jailed, the height since which it can be unjailed
+    #[serde(default)]
+    pub jailed_until: Option<u64>,
+    /// if tombstoned for double-signing, the height at which it happened
+    #[serde(default)]
+    pub tombstoned_at: Option<u64>,
+    /// the limits of the commission rate declared when staking
+    #[serde(default)]
+    pub commission_policy: Option<CommissionPolicy>,
+    /// a new commission rate and the height at which it takes effect
+    #[serde(default)]
+    pub pending_commission_rate: Option<(u64, [u64; 2])>,
+    /// the operators of a multisig-controlled validator
+    #[serde(default)]
+    pub operators: Option<SignatureRules>,
+    /// the base64-formated withdrawal address of rewards and self-delegation
+    #[serde(default)]
+    pub reward_receiver: Option<String>,
 }
 
 #[allow(missing_docs)]
@@ -152,6 +194,25 @@ pub struct DelegationInfo {
     pub delegation_rwd_cnt: u64,
     /// how many times of proposing this validator has performed
     pub proposer_rwd_cnt: u64,
+    /// the settings of auto-compounding, if enabled
+    #[serde(default)]
+    pub auto_compound: Option<AutoCompound>,
+    /// pending partial undelegations
+    #[serde(default)]
+    pub unbonding_entries: Vec<UnbondingEntry>,
+}
+
+/// A pending partial undelegation.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct UnbondingEntry {
+    /// base64-formated id of this entry, use it to cancel the unbonding
+    pub id: String,
+    /// the address of the validator
+    pub validator: TendermintAddr,
+    /// FRA units to be released
+    pub amount: u64,
+    /// the block height at which the FRAs will be released
+    pub release_height: u64,
 }
 
 impl DelegationInfo {
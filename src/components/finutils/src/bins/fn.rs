@@ -15,6 +15,20 @@
 //! - claim
 //!     - "--amount=[Amout <Optional, default to 'all'>]"
 //! - unstake
+//! - unjail, rejoin the validator set after the jail period
+//! - reward-receiver
+//!     - "--address=[Wallet Address] | --reset"
+//! - multisig
+//!     - "--sign --file=[Exported Transaction]"
+//!     - "--send --file=[Exported Transaction]"
+//! - redelegate
+//!     - "--amount=[Amout]"
+//!     - "--src-validator=[Validator Address]"
+//!     - "--dst-validator=[Validator Address]"
+//! - proposal
+//!     - "--submit --title=[Title] --deposit=[Amount] --kind=[Kind]"
+//!     - "--vote --id=[Proposal ID] --option=[yes | no | abstain | veto]"
+//!     - "--show --id=[Proposal ID <Optional, default to all>]"
 //! - show, query real-time state of your staking
 //! - setup
 //!     - "--serv-addr=[URL/IP]"
@@ -32,7 +46,10 @@ use {
     globutils::wallet,
     ledger::{
         data_model::{AssetTypeCode, FRA_DECIMALS},
-        staking::StakerMemo,
+        staking::{
+            proposal::{ProposalContent, VoteOption},
+            StakerMemo,
+        },
     },
     ruc::*,
     std::{fmt, fs},
@@ -90,8 +107,16 @@ fn run() -> Result<()> {
         let amount = m.value_of("amount");
         let validator = m.value_of("validator");
         let show_info = m.is_present("info");
+        let auto_compound = m.value_of("auto-compound");
 
-        if amount.is_some() && validator.is_some() {
+        if let Some(itv) = auto_compound {
+            common::auto_compound(
+                seckey.as_deref(),
+                itv.parse::<u64>()
+                    .c(d!("'auto-compound' must be an integer"))?,
+            )
+            .c(d!())?;
+        } else if amount.is_some() && validator.is_some() {
             common::delegate(
                 seckey.as_deref(),
                 amount.unwrap().parse::<u64>().c(d!())?,
@@ -112,6 +137,10 @@ fn run() -> Result<()> {
         };
         let amount = m.value_of("amount");
         let validator = m.value_of("validator");
+        if let Some(id) = m.value_of("cancel") {
+            common::cancel_unbonding(seckey.as_deref(), id).c(d!())?;
+            return Ok(());
+        }
         if (amount.is_none() && validator.is_some())
             || (amount.is_some() && validator.is_none())
         {
@@ -124,6 +153,98 @@ fn run() -> Result<()> {
             None
         };
         common::undelegate(seckey.as_deref(), param).c(d!())?;
+    } else if let Some(m) = matches.subcommand_matches("redelegate") {
+        let seckey = match m.value_of("seckey") {
+            Some(path) => {
+                Some(fs::read_to_string(path).c(d!("Failed to read seckey file"))?)
+            }
+            None => None,
+        };
+        let amount = m.value_of("amount").c(d!())?;
+        let src_validator = m.value_of("src-validator").c(d!())?;
+        let dst_validator = m.value_of("dst-validator").c(d!())?;
+        common::redelegate(
+            seckey.as_deref(),
+            amount.parse::<u64>().c(d!("'amount' must be an integer"))?,
+            src_validator,
+            dst_validator,
+        )
+        .c(d!())?;
+    } else if let Some(m) = matches.subcommand_matches("proposal") {
+        let seckey = match m.value_of("seckey") {
+            Some(path) => {
+                Some(fs::read_to_string(path).c(d!("Failed to read seckey file"))?)
+            }
+            None => None,
+        };
+        let id = if let Some(id) = m.value_of("id") {
+            Some(id.parse::<u64>().c(d!("'id' must be an integer"))?)
+        } else {
+            None
+        };
+        if m.is_present("submit") {
+            let (title, deposit) = match (m.value_of("title"), m.value_of("deposit")) {
+                (Some(t), Some(d)) => (t, d),
+                _ => {
+                    println!("{}", m.usage());
+                    return Ok(());
+                }
+            };
+            let content = match m.value_of("kind").unwrap_or("text") {
+                "param-change" => {
+                    let path = m.value_of("params").c(d!("'params' is missing"))?;
+                    let params = fs::read_to_string(path)
+                        .c(d!("Failed to read params file"))
+                        .and_then(|s| serde_json::from_str(&s).c(d!()))?;
+                    ProposalContent::ParamChange(params)
+                }
+                "treasury-spend" => {
+                    let receiver = m
+                        .value_of("receiver")
+                        .c(d!("'receiver' is missing"))
+                        .and_then(|addr| wallet::public_key_from_bech32(addr).c(d!()))?;
+                    let amount = m
+                        .value_of("amount")
+                        .c(d!("'amount' is missing"))?
+                        .parse::<u64>()
+                        .c(d!("'amount' must be an integer"))?;
+                    ProposalContent::TreasurySpend { receiver, amount }
+                }
+                _ => ProposalContent::Text,
+            };
+            common::submit_proposal(
+                seckey.as_deref(),
+                title,
+                m.value_of("description").unwrap_or_default(),
+                content,
+                deposit
+                    .parse::<u64>()
+                    .c(d!("'deposit' must be an integer"))?,
+            )
+            .c(d!())?;
+        } else if m.is_present("vote") {
+            let option = match m.value_of("option") {
+                Some("yes") => VoteOption::Yes,
+                Some("no") => VoteOption::No,
+                Some("abstain") => VoteOption::Abstain,
+                Some("veto") => VoteOption::NoWithVeto,
+                _ => {
+                    println!("{}", m.usage());
+                    return Ok(());
+                }
+            };
+            common::vote_proposal(
+                seckey.as_deref(),
+                id.c(d!("'id' is missing"))?,
+                option,
+            )
+            .c(d!())?;
+        } else if m.is_present("show") {
+            common::show_proposal(id).c(d!())?;
+        } else {
+            let help = "fn proposal [--submit | --vote | --show]";
+            println!("{}", help);
+        }
     } else if let Some(m) = matches.subcommand_matches("asset") {
         if m.is_present("create") {
             let seckey = match m.value_of("seckey") {
@@ -232,7 +353,7 @@ fn run() -> Result<()> {
                 "Tips: to update the information of your node, please specify commission-rate or memo"
             );
         } else {
-            common::staker_update(cr, vm).c(d!())?;
+            common::staker_update(cr, vm, m.value_of("export")).c(d!())?;
         }
     } else if let Some(m) = matches.subcommand_matches("stake") {
         let am = m.value_of("amount");
@@ -247,11 +368,20 @@ fn run() -> Result<()> {
             if am.is_none() {
                 println!("{}", m.usage());
             } else {
-                common::stake_append(am.unwrap(), staker.as_deref(), td_addr).c(d!())?;
+                common::stake_append(
+                    am.unwrap(),
+                    staker.as_deref(),
+                    td_addr,
+                    m.value_of("export"),
+                )
+                .c(d!())?;
             }
         } else {
             let cr = m.value_of("commission-rate");
             let vm = m.value_of("validator-memo");
+            let max_cr = m.value_of("max-commission-rate");
+            let max_cc = m.value_of("max-commission-change");
+            let operators = m.value_of("operators");
             let force = m.is_present("force");
             if am.is_none() || cr.is_none() {
                 println!("{}", m.usage());
@@ -259,7 +389,17 @@ fn run() -> Result<()> {
                     "Tips: if you want to raise the power of your node, please use `fn stake --append [OPTIONS]`"
                 );
             } else {
-                common::stake(am.unwrap(), cr.unwrap(), vm, force).c(d!())?;
+                common::stake(
+                    am.unwrap(),
+                    cr.unwrap(),
+                    vm,
+                    max_cr,
+                    max_cc,
+                    operators,
+                    force,
+                    m.value_of("export"),
+                )
+                .c(d!())?;
             }
         }
     } else if let Some(m) = matches.subcommand_matches("unstake") {
@@ -271,7 +411,15 @@ fn run() -> Result<()> {
             None => None,
         };
         let td_addr = m.value_of("validator-td-addr");
-        common::unstake(am, staker.as_deref(), td_addr).c(d!())?;
+        common::unstake(am, staker.as_deref(), td_addr, m.value_of("export")).c(d!())?;
+    } else if let Some(m) = matches.subcommand_matches("unjail") {
+        let staker = match m.value_of("staker-priv-key") {
+            Some(path) => {
+                Some(fs::read_to_string(path).c(d!("Failed to read seckey file"))?)
+            }
+            None => None,
+        };
+        common::unjail(staker.as_deref()).c(d!())?;
     } else if let Some(m) = matches.subcommand_matches("claim") {
         let am = m.value_of("amount");
         let seckey = match m.value_of("seckey") {
@@ -280,7 +428,40 @@ fn run() -> Result<()> {
             }
             None => None,
         };
-        common::claim(am, seckey.as_deref()).c(d!())?;
+        common::claim(am, seckey.as_deref(), m.value_of("export")).c(d!())?;
+    } else if let Some(m) = matches.subcommand_matches("reward-receiver") {
+        let seckey = match m.value_of("seckey") {
+            Some(path) => {
+                Some(fs::read_to_string(path).c(d!("Failed to read seckey file"))?)
+            }
+            None => None,
+        };
+        let address = m.value_of("address");
+        if address.is_none() && !m.is_present("reset") {
+            println!("{}", m.usage());
+        } else {
+            common::set_reward_receiver(
+                seckey.as_deref(),
+                address,
+                m.value_of("export"),
+            )
+            .c(d!())?;
+        }
+    } else if let Some(m) = matches.subcommand_matches("multisig") {
+        let file = m.value_of("file").c(d!())?;
+        if m.is_present("sign") {
+            let seckey = match m.value_of("seckey") {
+                Some(path) => {
+                    Some(fs::read_to_string(path).c(d!("Failed to read seckey file"))?)
+                }
+                None => None,
+            };
+            common::multisig_sign(seckey.as_deref(), file).c(d!())?;
+        } else if m.is_present("send") {
+            common::multisig_send(file).c(d!())?;
+        } else {
+            println!("{}", m.usage());
+        }
     } else if let Some(m) = matches.subcommand_matches("show") {
         let basic = m.is_present("basic");
         common::show(basic).c(d!())?;
@@ -390,12 +571,14 @@ fn run() -> Result<()> {
     } else if let Some(m) = matches.subcommand_matches("contract-deposit") {
         let amount = m.value_of("amount").c(d!())?;
         let address = m.value_of("addr");
-        transfer_to_account(amount.parse::<u64>().c(d!())?, address)?
+        let asset = parse_asset(m.value_of("asset"))?;
+        transfer_to_account(amount.parse::<u64>().c(d!())?, address, asset)?
     } else if let Some(m) = matches.subcommand_matches("contract-withdraw") {
         let amount = m.value_of("amount").c(d!())?;
         let address = m.value_of("addr");
         let eth_key = m.value_of("eth-key");
-        transfer_from_account(amount.parse::<u64>().c(d!())?, address, eth_key)?
+        let asset = parse_asset(m.value_of("asset"))?;
+        transfer_from_account(amount.parse::<u64>().c(d!())?, address, eth_key, asset)?
     } else if let Some(m) = matches.subcommand_matches("replace_staker") {
         let target = m
             .value_of("target")
@@ -431,6 +614,16 @@ fn run() -> Result<()> {
     Ok(())
 }
 
+/// The asset code given on the command line, `None` for FRA.
+fn parse_asset(asset: Option<&str>) -> Result<Option<AssetTypeCode>> {
+    match asset {
+        Some(asset) if asset.to_uppercase() != "FRA" => {
+            AssetTypeCode::new_from_base64(asset).c(d!()).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
 fn tip_fail(e: impl fmt::Display) {
     eprintln!("\n\x1b[31;01mFAIL !!!\x1b[00m");
     eprintln!(
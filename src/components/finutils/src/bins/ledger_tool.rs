@@ -0,0 +1,225 @@
+//!
+//! # ledger-tool
+//!
+//! Inspect the ledger data of a stopped node without starting any service,
+//! all outputs are printed as JSON lines.
+//!
+//! ## Usage
+//!
+//! ```shell
+//! ledger-tool --ledger-dir=[Path] [SUBCOMMAND]
+//!
+//! - blocks
+//!     - "--from=[Height] --to=[Height]"
+//! - txns
+//!     - "--from=[TxnSID] --to=[TxnSID]"
+//! - utxos
+//!     - "--owner=[Wallet Address]"
+//! - asset-types
+//! - staking
+//! - check-merkle, verify the Merkle logs and the utxo bitmap of the latest height
+//! - verify, verify blocks against the Merkle logs and the commitment history
+//!     - "--from=[Height] --to=[Height]"
+//!     - "--reference=[URL], such as: http://127.0.0.1:8668"
+//! ```
+//!
+
+#![deny(warnings)]
+
+use {
+    clap::{crate_authors, App, ArgMatches, SubCommand},
+    globutils::{wallet, HashOf},
+    ledger::{
+        data_model::{StateCommitmentData, TxnSID},
+        store::LedgerState,
+    },
+    ruc::*,
+    serde_json::json,
+    std::{env, fs},
+};
+
+fn main() {
+    pnk!(run());
+}
+
+fn run() -> Result<()> {
+    let subcmd_blocks = SubCommand::with_name("blocks")
+        .about("dump blocks, height starts from 1")
+        .arg_from_usage("--from=[Height] 'default to 1'")
+        .arg_from_usage("--to=[Height] 'default to the latest one'");
+    let subcmd_txns = SubCommand::with_name("txns")
+        .about("dump transactions")
+        .arg_from_usage("--from=[TxnSID] 'default to 0'")
+        .arg_from_usage("--to=[TxnSID] 'default to the latest one'");
+    let subcmd_utxos = SubCommand::with_name("utxos")
+        .about("dump the unspent utxos of an owner")
+        .arg_from_usage("-o, --owner=<Address> 'wallet address of the owner'");
+    let subcmd_asset_types =
+        SubCommand::with_name("asset-types").about("dump all asset types");
+    let subcmd_staking =
+        SubCommand::with_name("staking").about("dump the staking state");
+    let subcmd_check_merkle = SubCommand::with_name("check-merkle")
+        .about("verify the Merkle logs and the utxo bitmap of the latest height");
+    let subcmd_verify = SubCommand::with_name("verify")
+        .about("verify blocks against the Merkle logs and the commitment history")
+        .arg_from_usage("--from=[Height] 'default to 1'")
+        .arg_from_usage("--to=[Height] 'default to the latest one'")
+        .arg_from_usage("-r, --reference=[URL] 'ledger service of a reference node, such as: http://127.0.0.1:8668'");
+
+    let matches = App::new("ledger-tool")
+        .version(env!("VERGEN_SHA"))
+        .author(crate_authors!())
+        .about("Offline inspection of a findora ledger.")
+        .arg_from_usage("-d, --ledger-dir=[Path] 'the ledger directory of a stopped node, default to ${LEDGER_DIR}'")
+        .subcommand(subcmd_blocks)
+        .subcommand(subcmd_txns)
+        .subcommand(subcmd_utxos)
+        .subcommand(subcmd_asset_types)
+        .subcommand(subcmd_staking)
+        .subcommand(subcmd_check_merkle)
+        .subcommand(subcmd_verify)
+        .get_matches();
+
+    let ld = matches
+        .value_of("ledger-dir")
+        .map(|v| v.to_owned())
+        .or_else(|| env::var("LEDGER_DIR").ok())
+        .c(d!("ledger dir is missing"))?;
+
+    // must be set before any data is opened,
+    // and it must exist, or an empty one will be created
+    let bnc_dir = format!("{}/__bnc__", &ld);
+    fs::metadata(&bnc_dir).c(d!(bnc_dir))?;
+    env::set_var("BNC_DATA_DIR", &bnc_dir);
+    let ledger = LedgerState::load_existing(&ld).c(d!())?;
+
+    if let Some(m) = matches.subcommand_matches("blocks") {
+        let (from, to) = get_height_range(&ledger, m).c(d!())?;
+        for h in from..=to {
+            let block = ledger.blocks.get(h as usize - 1).c(d!())?;
+            print_line(&json!({ "height": h, "block": block })).c(d!())?;
+        }
+    } else if let Some(m) = matches.subcommand_matches("txns") {
+        let from = parse_arg(m, "from").c(d!())?.unwrap_or(0);
+        let to = parse_arg(m, "to")
+            .c(d!())?
+            .unwrap_or_else(|| ledger.get_transaction_count().saturating_sub(1));
+        for sid in from..=to {
+            // pruned transactions are skipped
+            if let Ok(tx) = ledger.get_transaction_light(TxnSID(sid)) {
+                print_line(&tx).c(d!())?;
+            }
+        }
+    } else if let Some(m) = matches.subcommand_matches("utxos") {
+        let owner = m
+            .value_of("owner")
+            .c(d!())
+            .and_then(|o| wallet::public_key_from_bech32(o).c(d!()))?;
+        for (sid, (utxo, owner_memo)) in ledger.get_owned_utxos(&owner).c(d!())? {
+            print_line(&json!({ "sid": sid, "utxo": utxo, "owner_memo": owner_memo }))
+                .c(d!())?;
+        }
+    } else if matches.is_present("asset-types") {
+        for (code, asset_type) in ledger.iter_asset_types() {
+            print_line(&json!({ "code": code.to_base64(), "asset_type": asset_type }))
+                .c(d!())?;
+        }
+    } else if matches.is_present("staking") {
+        print_line(ledger.get_staking()).c(d!())?;
+    } else if matches.is_present("check-merkle") {
+        let res = ledger.check_merkle_logs();
+        let status = match res.as_ref() {
+            Ok(_) => "ok".to_owned(),
+            Err(e) => e.to_string(),
+        };
+        print_line(&json!({ "height": ledger.get_block_count(), "status": status }))
+            .c(d!())?;
+        res.c(d!())?;
+    } else if let Some(m) = matches.subcommand_matches("verify") {
+        let (from, to) = get_height_range(&ledger, m).c(d!())?;
+        verify(&ledger, from, to, m.value_of("reference")).c(d!())?;
+    } else {
+        println!("{}", matches.usage());
+    }
+
+    Ok(())
+}
+
+fn verify(
+    ledger: &LedgerState,
+    from: u64,
+    to: u64,
+    reference: Option<&str>,
+) -> Result<()> {
+    let mut divergence = None;
+
+    for h in from..=to {
+        let (commitment, mut status) = match ledger.verify_block_commitment(h) {
+            Ok(c) => (Some(c), "ok".to_owned()),
+            Err(e) => (None, e.to_string()),
+        };
+
+        if let (Some(c), Some(url)) = (commitment.as_ref(), reference) {
+            match get_reference_commitment(url, h) {
+                Ok(Some(rc)) if rc == *c => {}
+                Ok(_) => status = "diverged from the reference node".to_owned(),
+                Err(e) => status = e.to_string(),
+            }
+        }
+
+        if "ok" != status && divergence.is_none() {
+            divergence = Some(h);
+        }
+
+        print_line(&json!({ "height": h, "commitment": commitment, "status": status }))
+            .c(d!())?;
+    }
+
+    if let Some(h) = divergence {
+        Err(eg!(format!("first divergence at height {}", h)))
+    } else {
+        Ok(())
+    }
+}
+
+fn get_reference_commitment(
+    url: &str,
+    height: u64,
+) -> Result<Option<HashOf<Option<StateCommitmentData>>>> {
+    let url = format!("{}/global_state_version/{}", url, height);
+
+    attohttpc::get(&url)
+        .send()
+        .c(d!())?
+        .error_for_status()
+        .c(d!())?
+        .bytes()
+        .c(d!())
+        .and_then(|b| serde_json::from_slice(&b).c(d!()))
+}
+
+fn get_height_range(ledger: &LedgerState, m: &ArgMatches) -> Result<(u64, u64)> {
+    let from = parse_arg(m, "from").c(d!())?.unwrap_or(1);
+    let to = parse_arg(m, "to")
+        .c(d!())?
+        .unwrap_or(ledger.get_block_count() as u64);
+
+    if 0 == from || from > to || (ledger.get_block_count() as u64) < to {
+        return Err(eg!(format!(
+            "invalid height range, available: [1, {}]",
+            ledger.get_block_count()
+        )));
+    }
+
+    Ok((from, to))
+}
+
+fn parse_arg<T: std::str::FromStr>(m: &ArgMatches, name: &str) -> Result<Option<T>> {
+    m.value_of(name)
+        .map(|v| v.parse::<T>().map_err(|_| eg!(format!("invalid {}", name))))
+        .transpose()
+}
+
+fn print_line<T: serde::Serialize + ?Sized>(v: &T) -> Result<()> {
+    serde_json::to_string(v).c(d!()).map(|s| println!("{}", s))
+}
@@ -20,8 +20,8 @@ use fp_types::{
 };
 use fp_utils::ecdsa::SecpPair;
 use fp_utils::tx::EvmRawTxWrapper;
-use ledger::data_model::ASSET_TYPE_FRA;
 use ledger::data_model::BLACK_HOLE_PUBKEY_STAKING;
+use ledger::data_model::{AssetTypeCode, ASSET_TYPE_FRA};
 use ruc::*;
 use std::str::FromStr;
 use tendermint::block::Height;
@@ -30,15 +30,20 @@ use tendermint_rpc::{Client, HttpClient};
 use tokio::runtime::Runtime;
 use zei::xfr::{asset_record::AssetRecordType, sig::XfrKeyPair};
 
-/// transfer utxo assets to account(ed25519 or ecdsa address) balance.
-pub fn transfer_to_account(amount: u64, address: Option<&str>) -> Result<()> {
+/// transfer utxo assets to account(ed25519 or ecdsa address) balance,
+/// FRA if `asset` is not set.
+pub fn transfer_to_account(
+    amount: u64,
+    address: Option<&str>,
+    asset: Option<AssetTypeCode>,
+) -> Result<()> {
     let mut builder = utils::new_tx_builder()?;
 
     let kp = get_keypair()?;
     let transfer_op = utils::gen_transfer_op(
         &kp,
         vec![(&BLACK_HOLE_PUBKEY_STAKING, amount)],
-        None,
+        asset,
         false,
         false,
         Some(AssetRecordType::NonConfidentialAmount_NonConfidentialAssetType),
@@ -50,7 +55,7 @@ pub fn transfer_to_account(amount: u64, address: Option<&str>) -> Result<()> {
 
     builder
         .add_operation(transfer_op)
-        .add_operation_convert_account(&kp, target_address, amount)?
+        .add_operation_convert_account(&kp, target_address, amount, asset)?
         .sign(&kp);
     utils::send_tx(&builder.take_transaction())?;
     Ok(())
@@ -74,11 +79,13 @@ impl Keypair {
     }
 }
 
-/// transfer to uxto assets from account(ed25519 or ecdsa address) balance.
+/// transfer to uxto assets from account(ed25519 or ecdsa address) balance,
+/// FRA if `asset` is not set.
 pub fn transfer_from_account(
     amount: u64,
     address: Option<&str>,
     eth_phrase: Option<&str>,
+    asset: Option<AssetTypeCode>,
 ) -> Result<()> {
     let fra_kp = get_keypair()?;
 
@@ -96,7 +103,7 @@ pub fn transfer_from_account(
     let output = NonConfidentialOutput {
         target,
         amount,
-        asset: ASSET_TYPE_FRA,
+        asset: asset.map(|code| code.val).unwrap_or(ASSET_TYPE_FRA),
     };
 
     let (signer, kp) = if let Some(key_path) = eth_phrase {
@@ -10,18 +10,20 @@ pub mod evm;
 pub mod utils;
 
 use {
-    crate::api::DelegationInfo,
+    crate::{api::DelegationInfo, txn_builder::TransactionBuilder},
     globutils::wallet,
     lazy_static::lazy_static,
     ledger::{
         data_model::{
-            gen_random_keypair, AssetRules, AssetTypeCode, Transaction,
+            gen_random_keypair, AssetRules, AssetTypeCode, SignatureRules, Transaction,
             BLACK_HOLE_PUBKEY_STAKING,
         },
         staking::{
-            check_delegation_amount, td_addr_to_bytes, td_pubkey_to_td_addr,
-            td_pubkey_to_td_addr_bytes, PartialUnDelegation, StakerMemo,
-            TendermintAddrRef,
+            check_delegation_amount,
+            proposal::{ProposalContent, ProposalId, VoteOption},
+            td_addr_to_bytes, td_pubkey_to_td_addr, td_pubkey_to_td_addr_bytes,
+            CommissionPolicy, PartialUnDelegation, StakerMemo, TendermintAddrRef,
+            COMMISSION_POLICY_DEFAULT,
         },
     },
     ruc::*,
@@ -56,7 +58,11 @@ lazy_static! {
 }
 
 /// Updating the information of a staker includes commission_rate and staker_memo
-pub fn staker_update(cr: Option<&str>, memo: Option<StakerMemo>) -> Result<()> {
+pub fn staker_update(
+    cr: Option<&str>,
+    memo: Option<StakerMemo>,
+    export: Option<&str>,
+) -> Result<()> {
     let addr = get_td_pubkey().map(|i| td_pubkey_to_td_addr(&i)).c(d!())?;
     let vd = get_validator_detail(&addr).c(d!())?;
 
@@ -83,16 +89,27 @@ pub fn staker_update(cr: Option<&str>, memo: Option<StakerMemo>) -> Result<()> {
         .c(d!())
         .map(|op| builder.add_operation(op))?;
 
-    utils::send_tx(&builder.take_transaction()).c(d!())
+    send_or_export_tx(&builder.take_transaction(), export).c(d!())
 }
 
 /// Perform a staking operation to add current tendermint node to validator list
 /// The cli tool user will be alert if the block height of local node is too small
+///
+/// The commission policy is declared only if `max_rate` or `max_change_rate` is set,
+/// the missing one will be filled with the default policy.
+///
+/// If `operators` is set, the validator will be controlled by the operators
+/// declared in the file, see `multisig_sign` for collecting their signatures.
+#[allow(clippy::too_many_arguments)]
 pub fn stake(
     amount: &str,
     commission_rate: &str,
     memo: Option<&str>,
+    max_rate: Option<&str>,
+    max_change_rate: Option<&str>,
+    operators: Option<&str>,
     force: bool,
+    export: Option<&str>,
 ) -> Result<()> {
     let am = amount.parse::<u64>().c(d!("'amount' must be an integer"))?;
     check_delegation_amount(am, false).c(d!())?;
@@ -100,6 +117,34 @@ pub fn stake(
         .parse::<f64>()
         .c(d!("commission rate must be a float number"))
         .and_then(|cr| convert_commission_rate(cr).c(d!()))?;
+
+    let parse_rate = |r: Option<&str>, default: [u64; 2]| {
+        r.map_or(Ok(default), |s| {
+            s.parse::<f64>()
+                .c(d!("commission rate must be a float number"))
+                .and_then(|r| convert_commission_rate(r).c(d!()))
+        })
+    };
+    let policy = if max_rate.is_some() || max_change_rate.is_some() {
+        Some(CommissionPolicy {
+            max_rate: parse_rate(max_rate, COMMISSION_POLICY_DEFAULT.max_rate)
+                .c(d!())?,
+            max_change_rate: parse_rate(
+                max_change_rate,
+                COMMISSION_POLICY_DEFAULT.max_change_rate,
+            )
+            .c(d!())?,
+        })
+    } else {
+        None
+    };
+    let operators = operators
+        .map(|path| {
+            fs::read_to_string(path)
+                .c(d!("Failed to read operators file"))
+                .and_then(|s| serde_json::from_str::<SignatureRules>(&s).c(d!()))
+        })
+        .transpose()?;
     let td_pubkey = get_td_pubkey().c(d!())?;
 
     let kp = get_keypair().c(d!())?;
@@ -133,7 +178,16 @@ pub fn stake(
 
     let mut builder = utils::new_tx_builder().c(d!())?;
     builder
-        .add_operation_staking(&kp, am, &vkp, td_pubkey, cr, memo.map(|m| m.to_owned()))
+        .add_operation_staking(
+            &kp,
+            am,
+            &vkp,
+            td_pubkey,
+            cr,
+            memo.map(|m| m.to_owned()),
+            policy,
+            operators,
+        )
         .c(d!())?;
     utils::gen_transfer_op(
         &kp,
@@ -146,7 +200,7 @@ pub fn stake(
     .c(d!())
     .map(|principal_op| builder.add_operation(principal_op))?;
 
-    utils::send_tx(&builder.take_transaction()).c(d!())
+    send_or_export_tx(&builder.take_transaction(), export).c(d!())
 }
 
 /// Append more FRA token to the specified tendermint node
@@ -154,6 +208,7 @@ pub fn stake_append(
     amount: &str,
     staker: Option<&str>,
     td_addr: Option<TendermintAddrRef>,
+    export: Option<&str>,
 ) -> Result<()> {
     let am = amount.parse::<u64>().c(d!("'amount' must be an integer"))?;
     check_delegation_amount(am, true).c(d!())?;
@@ -182,7 +237,7 @@ pub fn stake_append(
     .c(d!())
     .map(|principal_op| builder.add_operation(principal_op))?;
 
-    utils::send_tx(&builder.take_transaction()).c(d!())
+    send_or_export_tx(&builder.take_transaction(), export).c(d!())
 }
 
 /// Withdraw Fra token from findora network for a staker
@@ -190,6 +245,7 @@ pub fn unstake(
     am: Option<&str>,
     staker: Option<&str>,
     td_addr: Option<TendermintAddrRef>,
+    export: Option<&str>,
 ) -> Result<()> {
     let am = if let Some(i) = am {
         Some(i.parse::<u64>().c(d!("'amount' must be an integer"))?)
@@ -229,11 +285,31 @@ pub fn unstake(
         }
     })?;
 
+    send_or_export_tx(&builder.take_transaction(), export).c(d!())
+}
+
+/// Release a jailed validator, it will rejoin the validator set
+pub fn unjail(staker: Option<&str>) -> Result<()> {
+    let kp = staker
+        .c(d!())
+        .and_then(|sk| wallet::restore_keypair_from_mnemonic_default(sk).c(d!()))
+        .or_else(|_| get_keypair().c(d!()))?;
+
+    let mut builder = utils::new_tx_builder().c(d!())?;
+    utils::gen_fee_op(&kp).c(d!()).map(|op| {
+        builder.add_operation(op);
+        builder.add_operation_unjail(&kp);
+    })?;
+
     utils::send_tx(&builder.take_transaction()).c(d!())
 }
 
 /// Claim rewards from findora network
-pub fn claim(am: Option<&str>, sk_str: Option<&str>) -> Result<()> {
+pub fn claim(
+    am: Option<&str>,
+    sk_str: Option<&str>,
+    export: Option<&str>,
+) -> Result<()> {
     let am = if let Some(i) = am {
         Some(i.parse::<u64>().c(d!("'amount' must be an integer"))?)
     } else {
@@ -249,7 +325,71 @@ pub fn claim(am: Option<&str>, sk_str: Option<&str>) -> Result<()> {
         builder.add_operation_claim(&kp, am);
     })?;
 
-    utils::send_tx(&builder.take_transaction()).c(d!())
+    send_or_export_tx(&builder.take_transaction(), export).c(d!())
+}
+
+/// Set the withdrawal address of a validator, the rewards and self-delegation
+/// will be paid to it, reset it to the validator key itself if `receiver` is `None`.
+pub fn set_reward_receiver(
+    sk_str: Option<&str>,
+    receiver: Option<&str>,
+    export: Option<&str>,
+) -> Result<()> {
+    let kp = restore_keypair_from_str_with_default(sk_str).c(d!())?;
+    let receiver = receiver
+        .map(|addr| wallet::public_key_from_bech32(addr).c(d!("invalid address")))
+        .transpose()?;
+
+    let mut builder = utils::new_tx_builder().c(d!())?;
+    utils::gen_fee_op(&kp).c(d!()).map(|op| {
+        builder.add_operation(op);
+        builder.add_operation_reward_receiver(&kp, receiver);
+    })?;
+
+    send_or_export_tx(&builder.take_transaction(), export).c(d!())
+}
+
+/// Attach the signature of an operator to a transaction exported by
+/// `stake`, `unstake`, `staker_update`, `claim` or `set_reward_receiver`
+/// of a multisig-controlled validator.
+///
+/// The transaction will expire after about 128 blocks,
+/// so all the signatures should be collected in time.
+pub fn multisig_sign(sk_str: Option<&str>, file: &str) -> Result<()> {
+    let kp = restore_keypair_from_str_with_default(sk_str).c(d!())?;
+    let tx = read_tx_file(file).c(d!())?;
+
+    let mut builder = TransactionBuilder::from_transaction(tx);
+    builder.add_operator_signature(&kp);
+
+    export_tx(&builder.take_transaction(), file).c(d!())
+}
+
+/// Send a transaction after all the signatures of operators have been collected.
+pub fn multisig_send(file: &str) -> Result<()> {
+    read_tx_file(file)
+        .c(d!())
+        .and_then(|tx| utils::send_tx(&tx).c(d!()))
+}
+
+fn send_or_export_tx(tx: &Transaction, export: Option<&str>) -> Result<()> {
+    if let Some(file) = export {
+        export_tx(tx, file).c(d!())
+    } else {
+        utils::send_tx(tx).c(d!())
+    }
+}
+
+fn export_tx(tx: &Transaction, file: &str) -> Result<()> {
+    serde_json::to_vec_pretty(tx).c(d!()).and_then(|bytes| {
+        fs::write(file, bytes).c(d!("Failed to write transaction file"))
+    })
+}
+
+fn read_tx_file(file: &str) -> Result<Transaction> {
+    fs::read(file)
+        .c(d!("Failed to read transaction file"))
+        .and_then(|bytes| serde_json::from_slice(&bytes).c(d!("Invalid transaction")))
 }
 
 /// Show information of current node, including following sections:
@@ -588,6 +728,35 @@ pub fn delegate_x(kp: &XfrKeyPair, amount: u64, validator: &str) -> Result<()> {
         .and_then(|tx| utils::send_tx(&tx).c(d!()))
 }
 
+/// Re-bond a pending partial undelegation by its base64-formated id.
+pub fn cancel_unbonding(sk_str: Option<&str>, id: &str) -> Result<()> {
+    let kp = restore_keypair_from_str_with_default(sk_str).c(d!())?;
+    let id = wallet::public_key_from_base64(id).c(d!("invalid unbonding id"))?;
+
+    let mut builder = utils::new_tx_builder().c(d!())?;
+    utils::gen_fee_op(&kp).c(d!()).map(|op| {
+        builder.add_operation(op);
+        builder.add_operation_cancel_unbonding(&kp, id);
+    })?;
+
+    utils::send_tx(&builder.take_transaction()).c(d!())
+}
+
+/// Enable auto-compounding of delegation rewards with an interval in blocks,
+/// `0` means disabling it.
+pub fn auto_compound(sk_str: Option<&str>, interval: u64) -> Result<()> {
+    let kp = restore_keypair_from_str_with_default(sk_str).c(d!())?;
+
+    let mut builder = utils::new_tx_builder().c(d!())?;
+    utils::gen_fee_op(&kp).c(d!()).map(|op| {
+        builder.add_operation(op);
+        builder
+            .add_operation_auto_compound(&kp, alt!(0 == interval, None, Some(interval)));
+    })?;
+
+    utils::send_tx(&builder.take_transaction()).c(d!())
+}
+
 #[inline(always)]
 #[allow(missing_docs)]
 pub fn undelegate(sk_str: Option<&str>, param: Option<(u64, &str)>) -> Result<()> {
@@ -604,6 +773,85 @@ pub fn undelegate_x(kp: &XfrKeyPair, param: Option<(u64, &str)>) -> Result<()> {
         .and_then(|tx| utils::send_tx(&tx).c(d!()))
 }
 
+#[inline(always)]
+#[allow(missing_docs)]
+pub fn redelegate(
+    sk_str: Option<&str>,
+    amount: u64,
+    src_validator: &str,
+    dst_validator: &str,
+) -> Result<()> {
+    restore_keypair_from_str_with_default(sk_str)
+        .c(d!())
+        .and_then(|kp| {
+            gen_redelegate_tx(&kp, amount, src_validator, dst_validator).c(d!())
+        })
+        .and_then(|tx| utils::send_tx(&tx).c(d!()))
+}
+
+/// Submit a governance proposal, the deposit will be paid along with it.
+pub fn submit_proposal(
+    sk_str: Option<&str>,
+    title: &str,
+    description: &str,
+    content: ProposalContent,
+    deposit: u64,
+) -> Result<()> {
+    let kp = restore_keypair_from_str_with_default(sk_str).c(d!())?;
+
+    let mut builder = utils::new_tx_builder().c(d!())?;
+    utils::gen_transfer_op(
+        &kp,
+        vec![(&BLACK_HOLE_PUBKEY_STAKING, deposit)],
+        None,
+        false,
+        false,
+        Some(AssetRecordType::NonConfidentialAmount_NonConfidentialAssetType),
+    )
+    .c(d!())
+    .map(|deposit_op| {
+        builder.add_operation(deposit_op);
+        builder.add_operation_proposal(
+            &kp,
+            title.to_owned(),
+            description.to_owned(),
+            content,
+            deposit,
+        );
+    })?;
+
+    utils::send_tx(&builder.take_transaction()).c(d!())
+}
+
+/// Vote on a governance proposal.
+pub fn vote_proposal(
+    sk_str: Option<&str>,
+    id: ProposalId,
+    option: VoteOption,
+) -> Result<()> {
+    let kp = restore_keypair_from_str_with_default(sk_str).c(d!())?;
+
+    let mut builder = utils::new_tx_builder().c(d!())?;
+    utils::gen_fee_op(&kp).c(d!()).map(|op| {
+        builder.add_operation(op);
+    })?;
+    builder.add_operation_vote(&kp, id, option);
+
+    utils::send_tx(&builder.take_transaction()).c(d!())
+}
+
+/// Display a governance proposal, or all of them if no id provided.
+pub fn show_proposal(id: Option<ProposalId>) -> Result<()> {
+    let info = if let Some(id) = id {
+        serde_json::to_string_pretty(&utils::get_proposal(id).c(d!())?).c(d!())?
+    } else {
+        serde_json::to_string_pretty(&utils::get_proposal_list().c(d!())?).c(d!())?
+    };
+    println!("{}", info);
+
+    Ok(())
+}
+
 /// Display delegation information of a findora account
 pub fn show_delegations(sk_str: Option<&str>) -> Result<()> {
     let pk = restore_keypair_from_str_with_default(sk_str)?.get_pk();
@@ -644,6 +892,30 @@ fn gen_undelegate_tx(
     Ok(builder.take_transaction())
 }
 
+fn gen_redelegate_tx(
+    owner_kp: &XfrKeyPair,
+    amount: u64,
+    src_validator: &str,
+    dst_validator: &str,
+) -> Result<Transaction> {
+    check_delegation_amount(amount, true).c(d!())?;
+
+    let mut builder = utils::new_tx_builder().c(d!())?;
+    utils::gen_fee_op(owner_kp).c(d!()).map(|op| {
+        builder.add_operation(op);
+    })?;
+    builder
+        .add_operation_redelegation(
+            owner_kp,
+            src_validator.to_owned(),
+            dst_validator.to_owned(),
+            amount,
+        )
+        .c(d!())?;
+
+    Ok(builder.take_transaction())
+}
+
 fn gen_delegate_tx(
     owner_kp: &XfrKeyPair,
     amount: u64,
@@ -4,7 +4,7 @@
 
 use {
     crate::{
-        api::{DelegationInfo, ValidatorDetail},
+        api::{DelegationInfo, ProposalDetail, ValidatorDetail},
         common::get_serv_addr,
         txn_builder::{TransactionBuilder, TransferOperationBuilder},
     },
@@ -15,7 +15,11 @@ use {
             Transaction, TransferType, TxoRef, TxoSID, Utxo, ASSET_TYPE_FRA,
             BLACK_HOLE_PUBKEY, TX_FEE_MIN,
         },
-        staking::{init::get_inital_validators, TendermintAddrRef, FRA_TOTAL_AMOUNT},
+        staking::{
+            init::get_inital_validators,
+            proposal::{Proposal, ProposalId},
+            TendermintAddrRef, FRA_TOTAL_AMOUNT,
+        },
     },
     ruc::*,
     serde::{self, Deserialize, Serialize},
@@ -529,6 +533,34 @@ pub fn get_validator_detail(td_addr: TendermintAddrRef) -> Result<ValidatorDetai
         .and_then(|b| serde_json::from_slice::<ValidatorDetail>(&b).c(d!()))
 }
 
+/// Get all the governance proposals, without votes.
+pub fn get_proposal_list() -> Result<Vec<Proposal>> {
+    let url = format!("{}:8668/proposal_list", get_serv_addr().c(d!())?);
+
+    attohttpc::get(&url)
+        .send()
+        .c(d!())?
+        .error_for_status()
+        .c(d!())?
+        .bytes()
+        .c(d!())
+        .and_then(|b| serde_json::from_slice::<Vec<Proposal>>(&b).c(d!()))
+}
+
+/// Get a governance proposal along with its votes.
+pub fn get_proposal(id: ProposalId) -> Result<ProposalDetail> {
+    let url = format!("{}:8668/proposal/{}", get_serv_addr().c(d!())?, id);
+
+    attohttpc::get(&url)
+        .send()
+        .c(d!())?
+        .error_for_status()
+        .c(d!())?
+        .bytes()
+        .c(d!())
+        .and_then(|b| serde_json::from_slice::<ProposalDetail>(&b).c(d!()))
+}
+
 #[allow(missing_docs)]
 #[derive(Serialize, Deserialize)]
 pub struct ValidatorKey {
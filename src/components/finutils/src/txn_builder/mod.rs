@@ -15,25 +15,35 @@ use {
         data_model::{
             AssetRules, AssetTypeCode, ConfidentialMemo, DefineAsset, DefineAssetBody,
             IndexedSignature, IssueAsset, IssueAssetBody, IssuerKeyPair,
-            IssuerPublicKey, Memo, NoReplayToken, Operation, Transaction,
-            TransactionBody, TransferAsset, TransferAssetBody, TransferType, TxOutput,
-            TxoRef, UpdateMemo, UpdateMemoBody, ASSET_TYPE_FRA, BLACK_HOLE_PUBKEY,
-            TX_FEE_MIN,
+            IssuerPublicKey, Memo, NoReplayToken, Operation, SignatureRules,
+            Transaction, TransactionBody, TransferAsset, TransferAssetBody,
+            TransferType, TxOutput, TxoRef, UpdateMemo, UpdateMemoBody, ASSET_TYPE_FRA,
+            BLACK_HOLE_PUBKEY, TX_FEE_MIN,
         },
         staking::{
             is_valid_tendermint_addr,
             ops::{
+                auto_compound::AutoCompoundOps,
+                cancel_unbonding::CancelUnbondingOps,
                 claim::ClaimOps,
                 delegation::DelegationOps,
                 fra_distribution::FraDistributionOps,
                 governance::{ByzantineKind, GovernanceOps},
+                proposal::ProposalOps,
+                redelegation::RedelegationOps,
                 replace_staker::ReplaceStakerOps,
+                reward_receiver::RewardReceiverOps,
                 undelegation::UnDelegationOps,
+                unjail::UnjailOps,
+                update_params::UpdateParamsOps,
                 update_staker::UpdateStakerOps,
                 update_validator::UpdateValidatorOps,
+                vote::VoteOps,
             },
-            td_addr_to_string, BlockHeight, PartialUnDelegation, StakerMemo,
-            TendermintAddr, Validator,
+            params::StakingParams,
+            proposal::{ProposalContent, ProposalId, VoteOption},
+            td_addr_to_string, BlockHeight, CommissionPolicy, PartialUnDelegation,
+            StakerMemo, TendermintAddr, Validator,
         },
     },
     rand_chacha::ChaChaRng,
@@ -285,6 +295,17 @@ impl TransactionBuilder {
         }
     }
 
+    /// Create a instance from an existing transaction,
+    /// eg.. a transaction waiting for the signatures of validator operators
+    pub fn from_transaction(txn: Transaction) -> Self {
+        let no_replay_token = txn.body.no_replay_token;
+        TransactionBuilder {
+            txn,
+            outputs: 0,
+            no_replay_token,
+        }
+    }
+
     #[allow(missing_docs)]
     pub fn get_seq_id(&self) -> u64 {
         self.no_replay_token.get_seq_id()
@@ -526,6 +547,8 @@ impl TransactionBuilder {
         td_pubkey: Vec<u8>,
         commission_rate: [u64; 2],
         memo: Option<String>,
+        commission_policy: Option<CommissionPolicy>,
+        operators: Option<SignatureRules>,
     ) -> Result<&mut Self> {
         let v_id = keypair.get_pk();
 
@@ -535,7 +558,10 @@ impl TransactionBuilder {
             Default::default()
         };
 
-        let v = Validator::new_staker(td_pubkey, v_id, commission_rate, memo).c(d!())?;
+        let mut v =
+            Validator::new_staker(td_pubkey, v_id, commission_rate, memo).c(d!())?;
+        v.commission_policy = commission_policy;
+        v.operators = operators;
         let vaddr = td_addr_to_string(&v.td_addr);
 
         if !is_valid_tendermint_addr(&vaddr) {
@@ -554,6 +580,26 @@ impl TransactionBuilder {
         Ok(self.add_operation(Operation::Delegation(op)))
     }
 
+    /// Attach the signature of an operator to all the operations
+    /// which need the approval of the operators of a multisig-controlled validator,
+    /// the signatures can be collected offline one by one.
+    pub fn add_operator_signature(&mut self, keypair: &XfrKeyPair) -> &mut Self {
+        self.txn.body.operations.iter_mut().for_each(|op| match op {
+            Operation::Delegation(i) => i.sign_as_operator(keypair),
+            Operation::UnDelegation(i) => i.sign_as_operator(keypair),
+            Operation::UpdateStaker(i) => i.sign_as_operator(keypair),
+            Operation::Claim(i) => i.sign_as_operator(keypair),
+            Operation::RewardReceiver(i) => i.sign_as_operator(keypair),
+            Operation::ReplaceStaker(i) => i.sign_as_operator(keypair),
+            Operation::Unjail(i) => i.sign_as_operator(keypair),
+            Operation::AutoCompound(i) => i.sign_as_operator(keypair),
+            Operation::CancelUnbonding(i) => i.sign_as_operator(keypair),
+            Operation::Redelegation(i) => i.sign_as_operator(keypair),
+            _ => {}
+        });
+        self
+    }
+
     /// Add a operation to reduce delegation amount of a findora account.
     /// If no validator address and FRA amount provided, it will be a full un-delegation
     /// Otherwise, it will withdraw some FRA from the validator
@@ -566,6 +612,109 @@ impl TransactionBuilder {
         self.add_operation(Operation::UnDelegation(Box::new(op)))
     }
 
+    /// Add a operation to move some delegated FRA from a validator to another one,
+    /// without going through the unbonding period.
+    pub fn add_operation_redelegation(
+        &mut self,
+        keypair: &XfrKeyPair,
+        src_validator: TendermintAddr,
+        dst_validator: TendermintAddr,
+        amount: u64,
+    ) -> Result<&mut Self> {
+        if !is_valid_tendermint_addr(&src_validator)
+            || !is_valid_tendermint_addr(&dst_validator)
+        {
+            return Err(eg!("invalid validator address"));
+        }
+
+        let op = RedelegationOps::new(
+            keypair,
+            self.txn.body.no_replay_token,
+            src_validator,
+            dst_validator,
+            amount,
+        );
+        Ok(self.add_operation(Operation::Redelegation(op)))
+    }
+
+    /// Add a operation to submit a governance proposal.
+    /// The transfer operation of the deposit to BLACK_HOLE_PUBKEY_STAKING
+    /// should be sent along with.
+    pub fn add_operation_proposal(
+        &mut self,
+        keypair: &XfrKeyPair,
+        title: String,
+        description: String,
+        content: ProposalContent,
+        deposit: u64,
+    ) -> &mut Self {
+        let op = ProposalOps::new(
+            keypair,
+            title,
+            description,
+            content,
+            deposit,
+            self.txn.body.no_replay_token,
+        );
+        self.add_operation(Operation::Proposal(op))
+    }
+
+    /// Add a operation to vote on a governance proposal
+    pub fn add_operation_vote(
+        &mut self,
+        keypair: &XfrKeyPair,
+        proposal_id: ProposalId,
+        option: VoteOption,
+    ) -> &mut Self {
+        let op =
+            VoteOps::new(keypair, proposal_id, option, self.txn.body.no_replay_token);
+        self.add_operation(Operation::Vote(op))
+    }
+
+    /// Add a operation to release a jailed validator
+    pub fn add_operation_unjail(&mut self, keypair: &XfrKeyPair) -> &mut Self {
+        let op = UnjailOps::new(keypair, self.txn.body.no_replay_token);
+        self.add_operation(Operation::Unjail(op))
+    }
+
+    /// Add a operation to re-bond a pending partial undelegation,
+    /// `unbonding_id` is the `new_delegator_id` of the `PartialUnDelegation`
+    pub fn add_operation_cancel_unbonding(
+        &mut self,
+        keypair: &XfrKeyPair,
+        unbonding_id: XfrPublicKey,
+    ) -> &mut Self {
+        let op = CancelUnbondingOps::new(
+            keypair,
+            unbonding_id,
+            self.txn.body.no_replay_token,
+        );
+        self.add_operation(Operation::CancelUnbonding(op))
+    }
+
+    /// Add a operation to enable auto-compounding with an interval in blocks,
+    /// or disable it with `None`
+    pub fn add_operation_auto_compound(
+        &mut self,
+        keypair: &XfrKeyPair,
+        interval: Option<u64>,
+    ) -> &mut Self {
+        let op = AutoCompoundOps::new(keypair, interval, self.txn.body.no_replay_token);
+        self.add_operation(Operation::AutoCompound(op))
+    }
+
+    /// Add a operation to set the withdrawal address of a validator,
+    /// or reset it to the validator key with `None`
+    pub fn add_operation_reward_receiver(
+        &mut self,
+        keypair: &XfrKeyPair,
+        receiver: Option<XfrPublicKey>,
+    ) -> &mut Self {
+        let op =
+            RewardReceiverOps::new(keypair, receiver, self.txn.body.no_replay_token);
+        self.add_operation(Operation::RewardReceiver(op))
+    }
+
     /// Add a operation to claim all the rewards
     pub fn add_operation_claim(
         &mut self,
@@ -618,6 +767,18 @@ impl TransactionBuilder {
             .map(move |op| self.add_operation(Operation::UpdateValidator(op)))
     }
 
+    /// Add a operation update the staking parameters at specified block height.
+    pub fn add_operation_update_params(
+        &mut self,
+        kps: &[&XfrKeyPair],
+        h: BlockHeight,
+        params: StakingParams,
+    ) -> Result<&mut Self> {
+        UpdateParamsOps::new(kps, h, params, self.txn.body.no_replay_token)
+            .c(d!())
+            .map(move |op| self.add_operation(Operation::UpdateParams(op)))
+    }
+
     /// Add an operation to replace the staker of validator.
     pub fn add_operation_replace_staker(
         &mut self,
@@ -635,18 +796,21 @@ impl TransactionBuilder {
         Ok(self)
     }
 
-    /// Add a operation convert utxo asset to account balance.
+    /// Add a operation convert utxo asset to account balance,
+    /// FRA if `asset` is not set.
     pub fn add_operation_convert_account(
         &mut self,
         kp: &XfrKeyPair,
         addr: MultiSigner,
         amount: u64,
+        asset: Option<AssetTypeCode>,
     ) -> Result<&mut Self> {
         self.add_operation(Operation::ConvertAccount(ConvertAccount {
             signer: kp.get_pk(),
             nonce: self.txn.body.no_replay_token,
             receiver: addr,
             value: amount,
+            asset_type: asset.map(|code| code.val),
         }));
         Ok(self)
     }
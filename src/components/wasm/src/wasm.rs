@@ -47,10 +47,12 @@ use {
     globutils::{wallet, HashOf},
     ledger::{
         data_model::{
-            gen_random_keypair, AssetTypeCode, AuthenticatedTransaction, Operation,
-            TransferType, TxOutput, ASSET_TYPE_FRA, BLACK_HOLE_PUBKEY,
+            gen_random_keypair, AssetTypeCode, AuthenticatedBlock,
+            AuthenticatedTransaction, AuthenticatedUtxo, AuthenticatedUtxoStatus,
+            Operation, TransferType, TxOutput, ASSET_TYPE_FRA, BLACK_HOLE_PUBKEY,
             BLACK_HOLE_PUBKEY_STAKING, TX_FEE_MIN,
         },
+        light_client::LightClient as PlatformLightClient,
         staking::{
             td_addr_to_bytes, PartialUnDelegation, TendermintAddr,
             MAX_DELEGATION_AMOUNT, MIN_DELEGATION_AMOUNT,
@@ -60,6 +62,11 @@ use {
     rand_core::SeedableRng,
     ruc::{d, err::RucResult},
     std::str::FromStr,
+    tendermint::{
+        block::{Commit, Header},
+        validator::Set as ValidatorSet,
+        Time,
+    },
     wasm_bindgen::prelude::*,
     zei::{
         serialization::ZeiFromToBytes,
@@ -133,6 +140,153 @@ pub fn verify_authenticated_txn(
     Ok(authenticated_txn.is_valid(state_commitment))
 }
 
+#[wasm_bindgen]
+/// Light client that verifies query server responses against tendermint headers.
+pub struct LightClient {
+    inner: PlatformLightClient,
+}
+
+impl LightClient {
+    /// Initializes a light client from a header obtained from a trusted source.
+    /// @param {string} header - JSON-encoded tendermint header.
+    /// @param {string} commit - JSON-encoded tendermint commit of the header.
+    /// @param {string} validators - JSON-encoded validator set of the header.
+    /// @param {string} next_validators - JSON-encoded next validator set of the header.
+    /// @throws Will throw an error if any argument fails to deserialize or the commit is not trusted.
+    pub fn new(
+        header: String,
+        commit: String,
+        validators: String,
+        next_validators: String,
+    ) -> Result<LightClient, JsValue> {
+        let (header, commit, validators, next_validators) =
+            parse_light_block(&header, &commit, &validators, &next_validators)?;
+        PlatformLightClient::new(header, commit, validators, next_validators)
+            .c(d!())
+            .map_err(error_to_jsvalue)
+            .map(|inner| LightClient { inner })
+    }
+
+    /// Moves the light client forward to a newer header.
+    /// @param {string} header - JSON-encoded tendermint header.
+    /// @param {string} commit - JSON-encoded tendermint commit of the header.
+    /// @param {string} validators - JSON-encoded validator set of the header.
+    /// @param {string} next_validators - JSON-encoded next validator set of the header.
+    /// @param {string} now - Current time in RFC 3339 format, such as `new Date().toISOString()`.
+    /// @throws Will throw an error if any argument fails to deserialize, the header is not trusted,
+    /// or the latest trusted header is older than the trusting period.
+    pub fn update(
+        &mut self,
+        header: String,
+        commit: String,
+        validators: String,
+        next_validators: String,
+        now: String,
+    ) -> Result<(), JsValue> {
+        let (header, commit, validators, next_validators) =
+            parse_light_block(&header, &commit, &validators, &next_validators)?;
+        let now = Time::parse_from_rfc3339(&now)
+            .c(d!())
+            .map_err(error_to_jsvalue)?;
+        self.inner
+            .update(header, commit, validators, next_validators, now)
+            .c(d!())
+            .map_err(error_to_jsvalue)
+    }
+
+    /// Returns the height of the latest trusted header.
+    pub fn height(&self) -> u64 {
+        self.inner.height()
+    }
+
+    /// Checks a ledger state commitment against the app hash of the latest trusted header.
+    /// @param {string} state_commitment - String representing the state commitment.
+    /// @param {string} evm_state_hash - Hex-encoded EVM state hash, empty if not included in the app hash.
+    /// @see {@link module:Network~Network#getStateCommitment|Network.getStateCommitment}
+    /// for instructions on fetching a ledger state commitment.
+    /// @throws Will throw an error if the state commitment does not match the app hash.
+    pub fn verify_state_commitment(
+        &mut self,
+        state_commitment: String,
+        evm_state_hash: String,
+    ) -> Result<(), JsValue> {
+        let state_commitment = serde_json::from_str::<HashOf<_>>(&state_commitment)
+            .c(d!())
+            .map_err(error_to_jsvalue)?;
+        let evm_state_hash = hex::decode(evm_state_hash)
+            .c(d!())
+            .map_err(error_to_jsvalue)?;
+        self.inner
+            .verify_state_commitment(state_commitment, &evm_state_hash)
+            .c(d!())
+            .map_err(error_to_jsvalue)
+    }
+
+    /// Checks an authenticated UTXO against the verified state commitment.
+    /// @param {string} authenticated_utxo - JSON-encoded response of the `utxo_sid/{sid}` route.
+    /// @throws Will throw an error if the UTXO fails to deserialize or is invalid.
+    pub fn verify_utxo(&self, authenticated_utxo: String) -> Result<(), JsValue> {
+        serde_json::from_str::<AuthenticatedUtxo>(&authenticated_utxo)
+            .c(d!())
+            .and_then(|utxo| self.inner.verify_utxo(&utxo).c(d!()))
+            .map_err(error_to_jsvalue)
+    }
+
+    /// Checks an authenticated transaction against the verified state commitment.
+    /// @param {string} authenticated_txn - JSON-encoded authenticated transaction.
+    /// @throws Will throw an error if the transaction fails to deserialize or is invalid.
+    pub fn verify_transaction(&self, authenticated_txn: String) -> Result<(), JsValue> {
+        serde_json::from_str::<AuthenticatedTransaction>(&authenticated_txn)
+            .c(d!())
+            .and_then(|txn| self.inner.verify_transaction(&txn).c(d!()))
+            .map_err(error_to_jsvalue)
+    }
+
+    /// Checks an authenticated block against the verified state commitment.
+    /// @param {string} authenticated_block - JSON-encoded authenticated block.
+    /// @throws Will throw an error if the block fails to deserialize or is invalid.
+    pub fn verify_block(&self, authenticated_block: String) -> Result<(), JsValue> {
+        serde_json::from_str::<AuthenticatedBlock>(&authenticated_block)
+            .c(d!())
+            .and_then(|block| self.inner.verify_block(&block).c(d!()))
+            .map_err(error_to_jsvalue)
+    }
+
+    /// Checks an authenticated UTXO status against the verified state commitment.
+    /// @param {string} authenticated_status - JSON-encoded authenticated UTXO status.
+    /// @throws Will throw an error if the status fails to deserialize or is invalid.
+    pub fn verify_utxo_status(
+        &self,
+        authenticated_status: String,
+    ) -> Result<(), JsValue> {
+        serde_json::from_str::<AuthenticatedUtxoStatus>(&authenticated_status)
+            .c(d!())
+            .and_then(|status| self.inner.verify_utxo_status(&status).c(d!()))
+            .map_err(error_to_jsvalue)
+    }
+}
+
+fn parse_light_block(
+    header: &str,
+    commit: &str,
+    validators: &str,
+    next_validators: &str,
+) -> Result<(Header, Commit, ValidatorSet, ValidatorSet), JsValue> {
+    let header = serde_json::from_str(header)
+        .c(d!())
+        .map_err(error_to_jsvalue)?;
+    let commit = serde_json::from_str(commit)
+        .c(d!())
+        .map_err(error_to_jsvalue)?;
+    let validators = serde_json::from_str(validators)
+        .c(d!())
+        .map_err(error_to_jsvalue)?;
+    let next_validators = serde_json::from_str(next_validators)
+        .c(d!())
+        .map_err(error_to_jsvalue)?;
+    Ok((header, commit, validators, next_validators))
+}
+
 /// ...
 pub fn get_null_pk() -> XfrPublicKey {
     XfrPublicKey::zei_from_bytes(&[0; 32]).unwrap()
@@ -499,7 +653,7 @@ impl TransactionBuilder {
             return Err(error_to_jsvalue("Invalid Ethereum address"));
         }
         self.get_builder_mut()
-            .add_operation_convert_account(keypair, ea, amount)
+            .add_operation_convert_account(keypair, ea, amount, None)
             .c(d!())
             .map_err(error_to_jsvalue)?;
         Ok(self)
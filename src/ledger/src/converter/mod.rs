@@ -1,14 +1,15 @@
 //! Multi Signer operation for transaction.
 
 use crate::data_model::{
-    NoReplayToken, Operation, Transaction, ASSET_TYPE_FRA, BLACK_HOLE_PUBKEY_STAKING,
+    AssetTypeCode, NoReplayToken, Operation, Transaction, ASSET_TYPE_FRA,
+    BLACK_HOLE_PUBKEY_STAKING,
 };
 use fp_types::crypto::MultiSigner;
 use ruc::*;
 use serde::{Deserialize, Serialize};
 use zei::xfr::{
     sig::XfrPublicKey,
-    structs::{XfrAmount, XfrAssetType},
+    structs::{AssetType, XfrAmount, XfrAssetType},
 };
 
 /// Use this operation to transfer.
@@ -25,6 +26,9 @@ pub struct ConvertAccount {
     /// convert UTXOs value
     #[serde(with = "serde_strz")]
     pub value: u64,
+    /// asset type of the converted UTXOs, FRA if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_type: Option<AssetType>,
 }
 
 #[allow(missing_docs)]
@@ -40,6 +44,10 @@ impl ConvertAccount {
     pub fn get_related_address(&self) -> XfrPublicKey {
         self.signer
     }
+
+    pub fn get_asset_type(&self) -> AssetType {
+        self.asset_type.unwrap_or(ASSET_TYPE_FRA)
+    }
 }
 
 #[allow(missing_docs)]
@@ -55,10 +63,12 @@ pub fn is_convert_account(tx: &Transaction) -> bool {
         )
 }
 
-#[allow(missing_docs)]
-pub fn check_convert_account(tx: &Transaction) -> Result<(MultiSigner, u64)> {
+/// Check a transaction converting UTXOs to an EVM account balance, returns the
+/// receiver, the asset type and the converted value.
+pub fn check_convert_account(tx: &Transaction) -> Result<(MultiSigner, AssetType, u64)> {
     let signer;
     let target;
+    let asset;
     let expected_value;
 
     if let Some(Operation::ConvertAccount(ca)) = tx.body.operations.last() {
@@ -76,6 +86,7 @@ pub fn check_convert_account(tx: &Transaction) -> Result<(MultiSigner, u64)> {
 
         signer = ca.signer;
         target = ca.receiver.clone();
+        asset = ca.get_asset_type();
         expected_value = ca.value;
     } else {
         return Err(eg!(
@@ -99,9 +110,7 @@ pub fn check_convert_account(tx: &Transaction) -> Result<(MultiSigner, u64)> {
                     ));
             }
             if let XfrAssetType::NonConfidential(ty) = o.record.asset_type {
-                if o.record.public_key == *BLACK_HOLE_PUBKEY_STAKING
-                    && ty == ASSET_TYPE_FRA
-                {
+                if o.record.public_key == *BLACK_HOLE_PUBKEY_STAKING && ty == asset {
                     if let XfrAmount::NonConfidential(amount) = o.record.amount {
                         convert_amount += amount;
                     }
@@ -117,5 +126,16 @@ pub fn check_convert_account(tx: &Transaction) -> Result<(MultiSigner, u64)> {
         ));
     }
 
-    Ok((target, expected_value))
+    Ok((target, asset, expected_value))
+}
+
+/// The custom asset converted by a transaction, if any.
+pub fn get_convert_account_asset(tx: &Transaction) -> Option<AssetTypeCode> {
+    if let Some(Operation::ConvertAccount(ca)) = tx.body.operations.last() {
+        ca.asset_type
+            .filter(|ty| *ty != ASSET_TYPE_FRA)
+            .map(|val| AssetTypeCode { val })
+    } else {
+        None
+    }
 }
@@ -8,10 +8,14 @@ use {
         staking::{
             self,
             ops::{
+                auto_compound::AutoCompoundOps, cancel_unbonding::CancelUnbondingOps,
                 claim::ClaimOps, delegation::DelegationOps,
                 fra_distribution::FraDistributionOps, governance::GovernanceOps,
-                replace_staker::ReplaceStakerOps, undelegation::UnDelegationOps,
-                update_staker::UpdateStakerOps, update_validator::UpdateValidatorOps,
+                proposal::ProposalOps, redelegation::RedelegationOps,
+                replace_staker::ReplaceStakerOps, reward_receiver::RewardReceiverOps,
+                undelegation::UnDelegationOps, unjail::UnjailOps,
+                update_params::UpdateParamsOps, update_staker::UpdateStakerOps,
+                update_validator::UpdateValidatorOps, vote::VoteOps,
             },
         },
     },
@@ -81,6 +85,20 @@ pub struct TxnEffect {
     /// Staking operations
     pub undelegations: Vec<UnDelegationOps>,
     /// Staking operations
+    pub redelegations: Vec<RedelegationOps>,
+    /// Staking operations
+    pub proposals: Vec<ProposalOps>,
+    /// Staking operations
+    pub votes: Vec<VoteOps>,
+    /// Staking operations
+    pub unjails: Vec<UnjailOps>,
+    /// Staking operations
+    pub auto_compounds: Vec<AutoCompoundOps>,
+    /// Staking operations
+    pub cancel_unbondings: Vec<CancelUnbondingOps>,
+    /// Staking operations
+    pub reward_receivers: Vec<RewardReceiverOps>,
+    /// Staking operations
     pub claims: Vec<ClaimOps>,
     /// Staking operations
     pub governances: Vec<GovernanceOps>,
@@ -89,6 +107,8 @@ pub struct TxnEffect {
     /// Staking operations
     pub fra_distributions: Vec<FraDistributionOps>,
     /// Staking operations
+    pub update_params: Vec<UpdateParamsOps>,
+    /// Staking operations
     pub update_stakers: Vec<UpdateStakerOps>,
     /// replace staker operations
     pub replace_stakers: Vec<ReplaceStakerOps>,
@@ -155,6 +175,41 @@ impl TxnEffect {
                     i.verify().c(d!())?;
                     te.undelegations.push(i.as_ref().clone());
                 }
+                Operation::Redelegation(i) => {
+                    check_nonce!(i);
+                    i.verify().c(d!())?;
+                    te.redelegations.push(i.clone());
+                }
+                Operation::Proposal(i) => {
+                    check_nonce!(i);
+                    i.verify().c(d!())?;
+                    te.proposals.push(i.clone());
+                }
+                Operation::Vote(i) => {
+                    check_nonce!(i);
+                    i.verify().c(d!())?;
+                    te.votes.push(i.clone());
+                }
+                Operation::Unjail(i) => {
+                    check_nonce!(i);
+                    i.verify().c(d!())?;
+                    te.unjails.push(i.clone());
+                }
+                Operation::AutoCompound(i) => {
+                    check_nonce!(i);
+                    i.verify().c(d!())?;
+                    te.auto_compounds.push(i.clone());
+                }
+                Operation::CancelUnbonding(i) => {
+                    check_nonce!(i);
+                    i.verify().c(d!())?;
+                    te.cancel_unbondings.push(i.clone());
+                }
+                Operation::RewardReceiver(i) => {
+                    check_nonce!(i);
+                    i.verify().c(d!())?;
+                    te.reward_receivers.push(i.clone());
+                }
                 Operation::UpdateStaker(i) => {
                     check_nonce!(i);
                     i.verify().c(d!())?;
@@ -193,6 +248,10 @@ impl TxnEffect {
                     check_nonce!(i);
                     te.fra_distributions.push(i.clone());
                 }
+                Operation::UpdateParams(i) => {
+                    check_nonce!(i);
+                    te.update_params.push(i.clone());
+                }
                 Operation::ConvertAccount(i) => {
                     check_nonce!(i)
                 }
@@ -690,6 +749,36 @@ impl BlockEffect {
                 .c(d!())?;
         }
 
+        for i in txn_effect.redelegations.iter() {
+            i.check_run(&mut self.staking_simulator, &txn_effect.txn)
+                .c(d!())?;
+        }
+
+        for i in txn_effect.proposals.iter() {
+            i.check_run(&mut self.staking_simulator, &txn_effect.txn)
+                .c(d!())?;
+        }
+
+        for i in txn_effect.votes.iter() {
+            i.check_run(&mut self.staking_simulator).c(d!())?;
+        }
+
+        for i in txn_effect.unjails.iter() {
+            i.check_run(&mut self.staking_simulator).c(d!())?;
+        }
+
+        for i in txn_effect.auto_compounds.iter() {
+            i.check_run(&mut self.staking_simulator).c(d!())?;
+        }
+
+        for i in txn_effect.cancel_unbondings.iter() {
+            i.check_run(&mut self.staking_simulator).c(d!())?;
+        }
+
+        for i in txn_effect.reward_receivers.iter() {
+            i.check_run(&mut self.staking_simulator).c(d!())?;
+        }
+
         for i in txn_effect.claims.iter() {
             i.check_run(&mut self.staking_simulator).c(d!())?;
         }
@@ -702,6 +791,10 @@ impl BlockEffect {
             i.check_run(&mut self.staking_simulator).c(d!())?;
         }
 
+        for i in txn_effect.update_params.iter() {
+            i.check_run(&mut self.staking_simulator).c(d!())?;
+        }
+
         for i in txn_effect.fra_distributions.iter() {
             i.check_run(&mut self.staking_simulator, &txn_effect.txn)
                 .c(d!())?;
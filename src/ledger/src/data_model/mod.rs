@@ -17,10 +17,14 @@ use {
     crate::converter::ConvertAccount,
     crate::staking::{
         ops::{
+            auto_compound::AutoCompoundOps, cancel_unbonding::CancelUnbondingOps,
             claim::ClaimOps, delegation::DelegationOps,
             fra_distribution::FraDistributionOps, governance::GovernanceOps,
-            mint_fra::MintFraOps, undelegation::UnDelegationOps,
+            mint_fra::MintFraOps, proposal::ProposalOps, redelegation::RedelegationOps,
+            reward_receiver::RewardReceiverOps, undelegation::UnDelegationOps,
+            unjail::UnjailOps, update_params::UpdateParamsOps,
             update_staker::UpdateStakerOps, update_validator::UpdateValidatorOps,
+            vote::VoteOps,
         },
         Staking,
     },
@@ -1251,6 +1255,22 @@ pub enum Operation {
     ConvertAccount(ConvertAccount),
     ///replace staker.
     ReplaceStaker(ReplaceStakerOps),
+    /// Move delegated FRA token from a validator to another one
+    Redelegation(RedelegationOps),
+    /// Update the staking parameters at a future height
+    UpdateParams(UpdateParamsOps),
+    /// Submit a governance proposal along with a deposit
+    Proposal(ProposalOps),
+    /// Vote on a governance proposal
+    Vote(VoteOps),
+    /// Release a jailed validator
+    Unjail(UnjailOps),
+    /// Enable or disable auto-compounding of delegation rewards
+    AutoCompound(AutoCompoundOps),
+    /// Re-bond a pending partial undelegation
+    CancelUnbonding(CancelUnbondingOps),
+    /// Set or rotate the withdrawal address of a validator
+    RewardReceiver(RewardReceiverOps),
 }
 
 fn set_no_replay_token(op: &mut Operation, no_replay_token: NoReplayToken) {
@@ -1264,6 +1284,9 @@ fn set_no_replay_token(op: &mut Operation, no_replay_token: NoReplayToken) {
         Operation::UnDelegation(i) => {
             i.set_nonce(no_replay_token);
         }
+        Operation::Redelegation(i) => {
+            i.set_nonce(no_replay_token);
+        }
         Operation::Claim(i) => {
             i.set_nonce(no_replay_token);
         }
@@ -1276,6 +1299,27 @@ fn set_no_replay_token(op: &mut Operation, no_replay_token: NoReplayToken) {
         Operation::Governance(i) => {
             i.set_nonce(no_replay_token);
         }
+        Operation::UpdateParams(i) => {
+            i.set_nonce(no_replay_token);
+        }
+        Operation::Proposal(i) => {
+            i.set_nonce(no_replay_token);
+        }
+        Operation::Vote(i) => {
+            i.set_nonce(no_replay_token);
+        }
+        Operation::Unjail(i) => {
+            i.set_nonce(no_replay_token);
+        }
+        Operation::AutoCompound(i) => {
+            i.set_nonce(no_replay_token);
+        }
+        Operation::CancelUnbonding(i) => {
+            i.set_nonce(no_replay_token);
+        }
+        Operation::RewardReceiver(i) => {
+            i.set_nonce(no_replay_token);
+        }
         Operation::UpdateMemo(i) => i.body.no_replay_token = no_replay_token,
         Operation::ConvertAccount(i) => i.set_nonce(no_replay_token),
         _ => {}
@@ -9,6 +9,7 @@
 #[macro_use]
 pub mod data_model;
 pub mod converter;
+pub mod light_client;
 pub mod staking;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod store;
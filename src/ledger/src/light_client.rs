@@ -0,0 +1,601 @@
+//!
+//! # Light Client
+//!
+//! Verifies the responses of the query server without trusting it.
+//!
+//! A `LightClient` tracks a trusted tendermint header together with
+//! its validator set, and moves forward only on commits signed by
+//! enough voting power. The app hash of a verified header is then
+//! used to check a ledger state commitment, and the verified state
+//! commitment is used to check authenticated query responses.
+//!
+//! NOTE: the header at height `H + 1` carries the app hash of the state after `H`.
+//!
+
+use {
+    crate::data_model::{
+        AuthenticatedBlock, AuthenticatedTransaction, AuthenticatedUtxo,
+        AuthenticatedUtxoStatus, StateCommitmentData,
+    },
+    globutils::HashOf,
+    ruc::*,
+    serde::{Deserialize, Serialize},
+    sha2::Digest as _,
+    std::{convert::TryFrom, time::Duration},
+    tendermint::{
+        block::{Commit, CommitSig, Header},
+        chain,
+        validator::Set as ValidatorSet,
+        vote::{SignedVote, Type as VoteType, ValidatorIndex, Vote},
+        Time,
+    },
+};
+
+/// The trusted voting power of the old validator set
+/// required when skipping over heights, `1/3`.
+pub const TRUST_THRESHOLD: [u64; 2] = [1, 3];
+
+/// The voting power of the new validator set required
+/// for a commit to be accepted, `2/3`.
+pub const COMMIT_THRESHOLD: [u64; 2] = [2, 3];
+
+/// How long a trusted header can be used to move forward, about 14 days,
+/// it must be shorter than the 21-day unbonding period,
+/// or the validators who signed it could have unbonded without being punishable.
+pub const TRUSTING_PERIOD: Duration = Duration::from_secs(3600 * 24 * 14);
+
+/// A light client following the tendermint chain of findora.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LightClient {
+    header: Header,
+    validators: ValidatorSet,
+    next_validators: ValidatorSet,
+    state_commitment: Option<HashOf<Option<StateCommitmentData>>>,
+}
+
+impl LightClient {
+    /// Initialize a light client from a header obtained from a trusted source,
+    /// the commit of this header must be signed by more than 2/3 of the validators.
+    pub fn new(
+        header: Header,
+        commit: Commit,
+        validators: ValidatorSet,
+        next_validators: ValidatorSet,
+    ) -> Result<Self> {
+        check_validator_sets(&header, &validators, &next_validators).c(d!())?;
+        check_commit(&header, &commit, &validators, COMMIT_THRESHOLD).c(d!())?;
+
+        Ok(LightClient {
+            header,
+            validators,
+            next_validators,
+            state_commitment: None,
+        })
+    }
+
+    /// The height of the latest trusted header.
+    #[inline(always)]
+    pub fn height(&self) -> u64 {
+        self.header.height.value()
+    }
+
+    /// The latest trusted header.
+    #[inline(always)]
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// The validator set of the latest trusted header.
+    #[inline(always)]
+    pub fn validators(&self) -> &ValidatorSet {
+        &self.validators
+    }
+
+    /// The state commitment verified against the latest trusted header, if any.
+    #[inline(always)]
+    pub fn state_commitment(&self) -> Option<&HashOf<Option<StateCommitmentData>>> {
+        self.state_commitment.as_ref()
+    }
+
+    /// Move the light client forward to a newer header.
+    ///
+    /// An adjacent header must be produced by the trusted next validator set,
+    /// a non-adjacent header must be signed by more than 1/3 of the trusted validators.
+    /// The trusted header must be within the `TRUSTING_PERIOD` before `now`.
+    pub fn update(
+        &mut self,
+        header: Header,
+        commit: Commit,
+        validators: ValidatorSet,
+        next_validators: ValidatorSet,
+        now: Time,
+    ) -> Result<()> {
+        if now
+            .duration_since(self.header.time)
+            .map_or(false, |age| TRUSTING_PERIOD <= age)
+        {
+            return Err(eg!("trusted header has expired"));
+        }
+        if header.chain_id != self.header.chain_id {
+            return Err(eg!("chain id mismatch"));
+        }
+        if header.height.value() <= self.height() {
+            return Err(eg!("header is not newer than the trusted one"));
+        }
+        if header.time <= self.header.time {
+            return Err(eg!("header time is not monotonic"));
+        }
+
+        check_validator_sets(&header, &validators, &next_validators).c(d!())?;
+
+        if header.height.value() == 1 + self.height() {
+            if header.validators_hash != self.header.next_validators_hash {
+                return Err(eg!("unexpected validator set"));
+            }
+        } else {
+            check_commit(&header, &commit, &self.next_validators, TRUST_THRESHOLD)
+                .c(d!("not enough trusted voting power"))?;
+        }
+
+        check_commit(&header, &commit, &validators, COMMIT_THRESHOLD).c(d!())?;
+
+        self.header = header;
+        self.validators = validators;
+        self.next_validators = next_validators;
+        self.state_commitment = None;
+
+        Ok(())
+    }
+
+    /// Check a ledger state commitment against the app hash of the trusted header,
+    /// `evm_state_hash` is the state hash of the EVM chain at the same height,
+    /// use an empty slice if it is not included in the app hash.
+    pub fn verify_state_commitment(
+        &mut self,
+        state_commitment: HashOf<Option<StateCommitmentData>>,
+        evm_state_hash: &[u8],
+    ) -> Result<()> {
+        let app_hash = app_hash(state_commitment.0.as_ref(), evm_state_hash);
+        if app_hash != self.header.app_hash.value() {
+            return Err(eg!("app hash mismatch"));
+        }
+
+        self.state_commitment = Some(state_commitment);
+        Ok(())
+    }
+
+    /// Check an authenticated UTXO against the verified state commitment.
+    pub fn verify_utxo(&self, utxo: &AuthenticatedUtxo) -> Result<()> {
+        if utxo.is_valid(self.get_state_commitment().c(d!())?) {
+            Ok(())
+        } else {
+            Err(eg!("invalid authenticated utxo"))
+        }
+    }
+
+    /// Check an authenticated transaction against the verified state commitment.
+    pub fn verify_transaction(&self, txn: &AuthenticatedTransaction) -> Result<()> {
+        if txn.is_valid(self.get_state_commitment().c(d!())?) {
+            Ok(())
+        } else {
+            Err(eg!("invalid authenticated transaction"))
+        }
+    }
+
+    /// Check an authenticated block against the verified state commitment.
+    pub fn verify_block(&self, block: &AuthenticatedBlock) -> Result<()> {
+        if block.is_valid(self.get_state_commitment().c(d!())?) {
+            Ok(())
+        } else {
+            Err(eg!("invalid authenticated block"))
+        }
+    }
+
+    /// Check an authenticated UTXO status against the verified state commitment.
+    pub fn verify_utxo_status(&self, status: &AuthenticatedUtxoStatus) -> Result<()> {
+        if status.is_valid(self.get_state_commitment().c(d!())?) {
+            Ok(())
+        } else {
+            Err(eg!("invalid authenticated utxo status"))
+        }
+    }
+
+    #[inline(always)]
+    fn get_state_commitment(&self) -> Result<HashOf<Option<StateCommitmentData>>> {
+        self.state_commitment
+            .clone()
+            .c(d!("state commitment has not been verified"))
+    }
+}
+
+/// Compute the app hash in the same way as `abciapp`.
+pub fn app_hash(la_hash: &[u8], evm_state_hash: &[u8]) -> Vec<u8> {
+    // append ONLY non-empty EVM chain state hash
+    if evm_state_hash.is_empty() {
+        la_hash.to_vec()
+    } else {
+        sha2::Sha256::digest(&[la_hash, evm_state_hash].concat()).to_vec()
+    }
+}
+
+fn check_validator_sets(
+    header: &Header,
+    validators: &ValidatorSet,
+    next_validators: &ValidatorSet,
+) -> Result<()> {
+    if header.validators_hash != validators.hash() {
+        return Err(eg!("validator set hash mismatch"));
+    }
+    if header.next_validators_hash != next_validators.hash() {
+        return Err(eg!("next validator set hash mismatch"));
+    }
+    Ok(())
+}
+
+// Sum up the voting power of `validators` that signed the commit,
+// and check it against `threshold` of their total power.
+fn check_commit(
+    header: &Header,
+    commit: &Commit,
+    validators: &ValidatorSet,
+    threshold: [u64; 2],
+) -> Result<()> {
+    if commit.height != header.height {
+        return Err(eg!("commit height mismatch"));
+    }
+    if commit.block_id.hash != header.hash() {
+        return Err(eg!("commit is not for this header"));
+    }
+
+    let mut signed_power = 0;
+    let mut seen = vec![];
+
+    for (idx, sig) in commit.signatures.iter().enumerate() {
+        if let CommitSig::BlockIdFlagCommit {
+            validator_address, ..
+        } = sig
+        {
+            // signers unknown to the given set are simply ignored,
+            // they are expected when checking against an older set
+            let v = match validators.validator(*validator_address) {
+                Some(v) => v,
+                None => continue,
+            };
+            if seen.contains(validator_address) {
+                return Err(eg!("duplicate signature"));
+            }
+            seen.push(*validator_address);
+
+            let vote = commit_vote(&header.chain_id, commit, idx, sig).c(d!())?;
+            v.verify_signature(&vote.sign_bytes(), vote.signature())
+                .c(d!("invalid signature"))?;
+
+            signed_power += v.power();
+        }
+    }
+
+    let total_power = validators.total_voting_power().value();
+    if signed_power * threshold[1] > total_power * threshold[0] {
+        Ok(())
+    } else {
+        Err(eg!(format!(
+            "insufficient voting power: {}/{}",
+            signed_power, total_power
+        )))
+    }
+}
+
+// The precommit vote of the `idx`-th signature of a commit.
+fn commit_vote(
+    chain_id: &chain::Id,
+    commit: &Commit,
+    idx: usize,
+    sig: &CommitSig,
+) -> Result<SignedVote> {
+    if let CommitSig::BlockIdFlagCommit {
+        validator_address,
+        timestamp,
+        signature,
+    } = sig
+    {
+        let vote = Vote {
+            vote_type: VoteType::Precommit,
+            height: commit.height,
+            round: commit.round,
+            block_id: Some(commit.block_id),
+            timestamp: Some(*timestamp),
+            validator_address: *validator_address,
+            validator_index: ValidatorIndex::try_from(idx).c(d!())?,
+            signature: signature.clone(),
+        };
+        Ok(SignedVote::new(
+            vote,
+            chain_id.clone(),
+            *validator_address,
+            signature.clone(),
+        ))
+    } else {
+        Err(eg!("not a signature for the block"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{
+            data_model::TxnEffect,
+            store::{utils::fra_gen_initial_tx, LedgerState},
+        },
+        ed25519_dalek::{Keypair, Signer},
+        rand_chacha::ChaChaRng,
+        rand_core::SeedableRng,
+        serde_json::json,
+        tendermint::{validator::Info, vote::Power, PublicKey},
+        zei::xfr::sig::XfrKeyPair,
+    };
+
+    const HASH: &str =
+        "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855";
+
+    type TestValidator = (Keypair, Info);
+
+    fn gen_validators(powers: &[u64]) -> Vec<TestValidator> {
+        let mut prng = ChaChaRng::from_entropy();
+        powers
+            .iter()
+            .map(|power| {
+                let keypair = Keypair::generate(&mut prng);
+                let pk = PublicKey::from_raw_ed25519(keypair.public.as_bytes()).unwrap();
+                let info = Info::new(pk, Power::try_from(*power).unwrap());
+                (keypair, info)
+            })
+            .collect()
+    }
+
+    fn validator_set(validators: &[&TestValidator]) -> ValidatorSet {
+        ValidatorSet::without_proposer(
+            validators.iter().map(|(_, info)| info.clone()).collect(),
+        )
+    }
+
+    fn time(day: u32) -> Time {
+        Time::parse_from_rfc3339(&format!("2021-01-{:02}T00:00:00Z", day)).unwrap()
+    }
+
+    fn gen_header(
+        height: u64,
+        day: u32,
+        validators: &ValidatorSet,
+        next_validators: &ValidatorSet,
+        app_hash: &[u8],
+    ) -> Header {
+        let mut header: Header = serde_json::from_value(json!({
+            "version": { "block": "11", "app": "0" },
+            "chain_id": "test-chain",
+            "height": height.to_string(),
+            "time": time(day),
+            "last_block_id": { "hash": HASH, "parts": { "total": 1, "hash": HASH } },
+            "last_commit_hash": HASH,
+            "data_hash": HASH,
+            "validators_hash": HASH,
+            "next_validators_hash": HASH,
+            "consensus_hash": HASH,
+            "app_hash": hex::encode(app_hash).to_uppercase(),
+            "last_results_hash": HASH,
+            "evidence_hash": HASH,
+            "proposer_address": "0000000000000000000000000000000000000000",
+        }))
+        .unwrap();
+        header.validators_hash = validators.hash();
+        header.next_validators_hash = next_validators.hash();
+        header
+    }
+
+    fn sign_commit(header: &Header, signers: &[&TestValidator]) -> Commit {
+        let mut commit: Commit = serde_json::from_value(json!({
+            "height": header.height.value().to_string(),
+            "round": 0,
+            "block_id": {
+                "hash": header.hash().to_string(),
+                "parts": { "total": 1, "hash": HASH },
+            },
+            "signatures": [],
+        }))
+        .unwrap();
+
+        for (idx, (keypair, info)) in signers.iter().enumerate() {
+            let commit_sig = |signature: &[u8]| -> CommitSig {
+                serde_json::from_value(json!({
+                    "block_id_flag": 2,
+                    "validator_address": info.address.to_string(),
+                    "timestamp": header.time,
+                    "signature": base64::encode(signature),
+                }))
+                .unwrap()
+            };
+            let vote =
+                commit_vote(&header.chain_id, &commit, idx, &commit_sig(&[0; 64]))
+                    .unwrap();
+            let signature = keypair.sign(&vote.sign_bytes()).to_bytes();
+            commit.signatures.push(commit_sig(&signature));
+        }
+
+        commit
+    }
+
+    #[test]
+    fn light_client_app_hash() {
+        let la_hash = [1u8; 32];
+        assert_eq!(app_hash(&la_hash, &[]), la_hash.to_vec());
+
+        let evm_state_hash = [2u8; 32];
+        let mut data = la_hash.to_vec();
+        data.extend_from_slice(&evm_state_hash);
+        assert_eq!(
+            app_hash(&la_hash, &evm_state_hash),
+            sha2::Sha256::digest(&data).to_vec()
+        );
+    }
+
+    #[test]
+    fn light_client_check_commit() {
+        let vs = gen_validators(&[10, 10, 10]);
+        let vs = vs.iter().collect::<Vec<_>>();
+        let set = validator_set(&vs);
+        let header = gen_header(1, 1, &set, &set, &[1; 32]);
+
+        // more than 2/3 of the power
+        let commit = sign_commit(&header, &vs);
+        assert!(check_commit(&header, &commit, &set, COMMIT_THRESHOLD).is_ok());
+
+        // exactly 2/3 of the power
+        let commit = sign_commit(&header, &vs[..2]);
+        assert!(check_commit(&header, &commit, &set, COMMIT_THRESHOLD).is_err());
+        assert!(check_commit(&header, &commit, &set, TRUST_THRESHOLD).is_ok());
+
+        // exactly 1/3 of the power
+        let commit = sign_commit(&header, &vs[..1]);
+        assert!(check_commit(&header, &commit, &set, TRUST_THRESHOLD).is_err());
+
+        // signers out of the set are not counted
+        let others = gen_validators(&[10, 10]);
+        let signers = [vs[0], vs[1], &others[0], &others[1]];
+        let commit = sign_commit(&header, &signers);
+        assert!(check_commit(&header, &commit, &set, COMMIT_THRESHOLD).is_err());
+
+        // a commit for another header
+        let other_header = gen_header(1, 1, &set, &set, &[2; 32]);
+        let other_commit = sign_commit(&other_header, &vs);
+        assert!(check_commit(&header, &other_commit, &set, COMMIT_THRESHOLD).is_err());
+
+        // a bad signature
+        let mut commit = sign_commit(&header, &vs);
+        commit.signatures[0] = other_commit.signatures[0].clone();
+        assert!(check_commit(&header, &commit, &set, COMMIT_THRESHOLD).is_err());
+
+        // a duplicate signer
+        let mut commit = sign_commit(&header, &vs[..2]);
+        commit.signatures.push(commit.signatures[0].clone());
+        assert!(check_commit(&header, &commit, &set, COMMIT_THRESHOLD).is_err());
+    }
+
+    #[test]
+    fn light_client_update() {
+        let vs = gen_validators(&[10, 10, 10]);
+        let vs = vs.iter().collect::<Vec<_>>();
+        let set = validator_set(&vs);
+
+        let header = gen_header(1, 1, &set, &set, &[1; 32]);
+        let commit = sign_commit(&header, &vs);
+        let mut lc = LightClient::new(header, commit, set.clone(), set.clone()).unwrap();
+
+        // an adjacent header of the trusted next validator set
+        let header = gen_header(2, 2, &set, &set, &[2; 32]);
+        let commit = sign_commit(&header, &vs);
+        lc.update(header, commit, set.clone(), set.clone(), time(2))
+            .unwrap();
+        assert_eq!(2, lc.height());
+
+        // an adjacent header of another validator set
+        let others = gen_validators(&[10, 10, 10]);
+        let others = others.iter().collect::<Vec<_>>();
+        let other_set = validator_set(&others);
+        let header = gen_header(3, 3, &other_set, &other_set, &[3; 32]);
+        let commit = sign_commit(&header, &others);
+        assert!(lc
+            .update(header, commit, other_set.clone(), other_set, time(3))
+            .is_err());
+        assert_eq!(2, lc.height());
+
+        // skipping with 1/3 of the trusted power
+        let signers = [vs[0], others[0], others[1]];
+        let new_set = validator_set(&signers);
+        let header = gen_header(10, 4, &new_set, &new_set, &[4; 32]);
+        let commit = sign_commit(&header, &signers);
+        assert!(lc
+            .update(header, commit, new_set.clone(), new_set, time(4))
+            .is_err());
+        assert_eq!(2, lc.height());
+
+        // skipping with more than 1/3 of the trusted power
+        let signers = [vs[0], vs[1], others[0]];
+        let new_set = validator_set(&signers);
+        let header = gen_header(10, 4, &new_set, &new_set, &[4; 32]);
+        let commit = sign_commit(&header, &signers);
+        lc.update(header, commit, new_set.clone(), new_set.clone(), time(4))
+            .unwrap();
+        assert_eq!(10, lc.height());
+
+        // the trusted header of day 4 expires on day 18
+        let header = gen_header(20, 18, &new_set, &new_set, &[5; 32]);
+        let commit = sign_commit(&header, &signers);
+        assert!(lc
+            .update(
+                header.clone(),
+                commit.clone(),
+                new_set.clone(),
+                new_set.clone(),
+                time(18)
+            )
+            .is_err());
+        assert_eq!(10, lc.height());
+        lc.update(header, commit, new_set.clone(), new_set, time(17))
+            .unwrap();
+        assert_eq!(20, lc.height());
+    }
+
+    #[test]
+    fn light_client_verify_utxo() {
+        let mut ledger = LedgerState::tmp_ledger();
+        let fra_owner_kp = XfrKeyPair::generate(&mut ledger.get_prng());
+        let tx = fra_gen_initial_tx(&fra_owner_kp);
+        let effect = TxnEffect::compute_effect(tx).unwrap();
+        let mut block = ledger.start_block().unwrap();
+        let temp_sid = ledger.apply_transaction(&mut block, effect).unwrap();
+        let (_, txos) = ledger
+            .finish_block(block)
+            .unwrap()
+            .remove(&temp_sid)
+            .unwrap();
+        let (state_commitment, _) = ledger.get_state_commitment();
+        let utxo = ledger.get_utxo(txos[0]).unwrap();
+
+        let vs = gen_validators(&[10]);
+        let vs = vs.iter().collect::<Vec<_>>();
+        let set = validator_set(&vs);
+        let evm_state_hash = [9u8; 32];
+        let header = gen_header(
+            2,
+            1,
+            &set,
+            &set,
+            &app_hash(state_commitment.0.as_ref(), &evm_state_hash),
+        );
+        let commit = sign_commit(&header, &vs);
+        let mut lc = LightClient::new(header, commit, set.clone(), set).unwrap();
+
+        // nothing can be verified before the state commitment
+        assert!(lc.verify_utxo(&utxo).is_err());
+
+        // the state commitment along with the evm state hash must match the app hash
+        assert!(lc
+            .verify_state_commitment(state_commitment.clone(), &[])
+            .is_err());
+        assert!(lc
+            .verify_state_commitment(HashOf::new(&None), &evm_state_hash)
+            .is_err());
+        assert!(lc.state_commitment().is_none());
+        lc.verify_state_commitment(state_commitment.clone(), &evm_state_hash)
+            .unwrap();
+        assert_eq!(Some(&state_commitment), lc.state_commitment());
+
+        lc.verify_utxo(&utxo).unwrap();
+
+        // a utxo authenticated against a newer state
+        let block = ledger.start_block().unwrap();
+        ledger.finish_block(block).unwrap();
+        let utxo = ledger.get_utxo(txos[0]).unwrap();
+        assert!(lc.verify_utxo(&utxo).is_err());
+    }
+}
@@ -1,20 +1,21 @@
 //!
 //! # CoSignature
 //!
-//! Aka Multi-Signature, it is originally used to support `Governance` and `ValidatorUpdate`.
+//! Aka Multi-Signature, it is originally used to support `Governance` and `ValidatorUpdate`,
+//! and also used to control a validator with the keys of its operators.
 //!
 
 use {
     super::MAX_TOTAL_POWER,
     crate::{
-        data_model::NoReplayToken,
+        data_model::{NoReplayToken, SignatureRules},
         staking::{Staking, ValidatorData},
     },
     cryptohash::sha256::{self, Digest},
     ruc::*,
     serde::{Deserialize, Serialize},
     std::{
-        collections::BTreeMap,
+        collections::{BTreeMap, HashSet},
         fmt::{self, Debug},
     },
     zei::xfr::sig::{XfrKeyPair, XfrPublicKey, XfrSignature},
@@ -180,6 +181,77 @@ impl CoSigRule {
     }
 }
 
+/// The partial signatures of the operators of a multisig-controlled validator,
+/// each of them signs the same message as the one signed by the validator key.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct OperatorCoSigs(BTreeMap<XfrPublicKey, CoSig>);
+
+impl OperatorCoSigs {
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Attach a new signature, an old one of the same key will be replaced.
+    #[inline(always)]
+    pub fn sign(&mut self, kp: &XfrKeyPair, msg: &[u8]) {
+        let k = kp.get_pk();
+        self.0.insert(k, CoSig::new(k, kp.sign(msg)));
+    }
+
+    /// Check if all the signatures are valid and made by the keys they are
+    /// filed under, without considering the rules of any validator.
+    pub fn verify(&self, msg: &[u8]) -> Result<()> {
+        if self
+            .0
+            .iter()
+            .any(|(k, sig)| *k != sig.pk || k.verify(msg, &sig.sig).is_err())
+        {
+            Err(eg!(CoSigErr::SigInvalid))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check if the signers satisfy the rules of a validator.
+    pub fn check_rules(&self, rules: &SignatureRules) -> Result<()> {
+        if self
+            .0
+            .keys()
+            .any(|k| !rules.weights.iter().any(|(pk, _)| pk == k))
+        {
+            return Err(eg!(CoSigErr::KeyUnknown));
+        }
+
+        let keyset = self
+            .0
+            .keys()
+            .map(|k| k.as_bytes().to_vec())
+            .collect::<HashSet<_>>();
+        rules
+            .check_signature_set(&keyset)
+            .map_err(|_| eg!(CoSigErr::WeightInsufficient))
+    }
+}
+
+/// Check if the operator rules declared by a validator are reachable.
+pub fn operator_rules_is_valid(rules: &SignatureRules) -> bool {
+    let keys = rules
+        .weights
+        .iter()
+        .map(|(pk, _)| pk.as_bytes())
+        .collect::<HashSet<_>>();
+    let total = rules
+        .weights
+        .iter()
+        .try_fold(0u64, |acc, (_, w)| acc.checked_add(*w));
+
+    0 < rules.threshold
+        && keys.len() == rules.weights.len()
+        && total.map_or(false, |t| rules.threshold <= t)
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub(crate) struct CoSig {
     pk: XfrPublicKey,
@@ -297,4 +369,28 @@ mod test {
         });
         assert!(data.check_cosigs(&vd).is_err());
     }
+
+    #[test]
+    fn staking_operator_cosigs_forged_key() {
+        let kps = gen_keypairs(3);
+        let rules = SignatureRules {
+            threshold: 2,
+            weights: kps.iter().map(|kp| (kp.get_pk(), 1)).collect(),
+        };
+        let msg = b"operation body";
+
+        let mut sigs = OperatorCoSigs::default();
+        sigs.sign(&kps[0], msg);
+        sigs.sign(&kps[1], msg);
+        pnk!(sigs.verify(msg));
+        pnk!(sigs.check_rules(&rules));
+
+        // valid signatures of a foreign key filed under the keys of the operators
+        let forger = gen_keypairs(1).remove(0);
+        let forged = CoSig::new(forger.get_pk(), forger.sign(msg));
+        let sigs =
+            OperatorCoSigs(kps.iter().map(|kp| (kp.get_pk(), forged.clone())).collect());
+        assert!(sigs.check_rules(&rules).is_ok());
+        assert!(sigs.verify(msg).is_err());
+    }
 }
@@ -54,6 +54,12 @@ impl TryFrom<ValidatorStr> for Validator {
             kind: v.kind.unwrap_or(ValidatorKind::Initiator),
             signed_last_block: false,
             signed_cnt: 0,
+            missed_blocks: Default::default(),
+            jailed_until: None,
+            tombstoned_at: None,
+            commission_policy: None,
+            pending_commission_rate: None,
+            operators: None,
             delegators: IndexMap::new(),
         })
     }
@@ -18,16 +18,19 @@ use {num_bigint::BigUint, std::convert::TryFrom};
 pub mod cosig;
 pub mod init;
 pub mod ops;
+pub mod params;
+pub mod proposal;
 
 use {
     crate::{
         data_model::{
-            ConsensusRng, Operation, Transaction, TransferAsset, TxoRef, FRA_DECIMALS,
+            ConsensusRng, Operation, SignatureRules, Transaction, TransferAsset, TxoRef,
+            FRA_DECIMALS,
         },
         SNAPSHOT_ENTRIES_DIR,
     },
     config::abci::global_cfg::CFG,
-    cosig::CoSigRule,
+    cosig::{operator_rules_is_valid, CoSigRule, OperatorCoSigs},
     cryptohash::sha256::{self, Digest},
     fbnc::{new_mapx, Mapx},
     globutils::wallet,
@@ -35,14 +38,22 @@ use {
     lazy_static::lazy_static,
     ops::{
         fra_distribution::FraDistributionOps,
+        governance::ByzantineKind,
         mint_fra::{MintKind, MINT_AMOUNT_LIMIT},
     },
+    params::{StakingParams, DEFAULT_STAKING_PARAMS},
     parking_lot::Mutex,
+    proposal::{
+        Proposal, ProposalContent, ProposalId, ProposalInfo, ProposalStatus,
+        TallyResult, VoteOption, PROPOSAL_DESC_LEN_MAX, PROPOSAL_MIN_DEPOSIT,
+        PROPOSAL_TITLE_LEN_MAX, PROPOSAL_VOTING_BLOCK_CNT,
+    },
     rand::random,
     ruc::*,
     serde::{Deserialize, Serialize},
     sha2::Digest as _,
     std::{
+        cmp,
         collections::{BTreeMap, BTreeSet},
         env, mem,
         sync::{
@@ -168,6 +179,35 @@ pub const MAX_POWER_PERCENT_PER_VALIDATOR: [u128; 2] = [1, 5];
 /// Block time interval, in seconds.
 pub const BLOCK_INTERVAL: u64 = 15 + 1;
 
+/// The minimum interval between two redelegations of a delegator, about 7 days.
+pub const REDELEGATION_COOLDOWN_BLOCK_CNT: u64 = 3600 * 24 * 7 / BLOCK_INTERVAL;
+
+/// The sliding window in which the missed blocks of a validator are counted.
+pub const JAIL_WINDOW_BLOCK_CNT: u64 = 1000;
+
+/// A validator will be jailed if it missed more blocks than this in the window.
+pub const JAIL_MISSED_BLOCK_MAX: u64 = 500;
+
+/// The minimum time a validator stays in jail, about 1 day.
+pub const JAIL_MIN_BLOCK_CNT: u64 = 3600 * 24 / BLOCK_INTERVAL;
+
+/// The minimum interval of auto-compounding, about 1 day.
+pub const AUTO_COMPOUND_MIN_BLOCK_CNT: u64 = 3600 * 24 / BLOCK_INTERVAL;
+
+/// Evidences older than this will not be accepted by tendermint,
+/// aka the default `max_age_num_blocks` of it.
+pub const EVIDENCE_MAX_AGE_BLOCK_CNT: u64 = 100000;
+
+/// The delay before a new commission rate takes effect, about 1 day.
+pub const COMMISSION_CHANGE_DELAY_BLOCK_CNT: u64 = 3600 * 24 / BLOCK_INTERVAL;
+
+/// The commission policy of the validators who did not declare one,
+/// the rate is not capped, but can only change by 5% per day.
+pub const COMMISSION_POLICY_DEFAULT: CommissionPolicy = CommissionPolicy {
+    max_rate: [1, 1],
+    max_change_rate: [5, 100],
+};
+
 /// The lock time after the delegation expires, about 21 days.
 //pub const UNBOND_BLOCK_CNT: u64 = 3600 * 24 * 21 / BLOCK_INTERVAL;
 
@@ -224,6 +264,25 @@ pub struct Staking {
     // FRA CoinBase.
     coinbase: CoinBase,
     cr: ConsensusRng,
+    // the height at which they take effect => staking parameters,
+    // `DEFAULT_STAKING_PARAMS` is used before the first entry
+    #[serde(rename = "pm", default, skip_serializing_if = "BTreeMap::is_empty")]
+    params: BTreeMap<BlockHeight, StakingParams>,
+    // governance proposals, including the finished ones
+    #[serde(rename = "gp", default, skip_serializing_if = "ProposalInfo::is_empty")]
+    proposal_info: ProposalInfo,
+    // infraction height => the handled evidences of it,
+    // entries older than `EVIDENCE_MAX_AGE_BLOCK_CNT` are removed
+    #[serde(rename = "ev", default, skip_serializing_if = "BTreeMap::is_empty")]
+    evidence_handled: BTreeMap<BlockHeight, BTreeSet<(TendermintAddr, ByzantineKind)>>,
+    // tendermint addresses of all the double-signers,
+    // kept forever so they can not come back as new validators
+    #[serde(rename = "ts", default, skip_serializing_if = "BTreeSet::is_empty")]
+    tombstoned: BTreeSet<TendermintAddr>,
+    // validator => its bonded amounts and the changes of them,
+    // used to slash the evidences of the last `EVIDENCE_MAX_AGE_BLOCK_CNT` blocks
+    #[serde(rename = "bh", default, skip_serializing_if = "BTreeMap::is_empty")]
+    bonded_hist: BTreeMap<XfrPublicKey, BondedHist>,
 }
 
 impl Default for Staking {
@@ -256,6 +315,11 @@ impl Staking {
             cur_height: 0,
             coinbase: CoinBase::gen(),
             cr: ConsensusRng::default(),
+            params: BTreeMap::new(),
+            proposal_info: ProposalInfo::default(),
+            evidence_handled: BTreeMap::new(),
+            tombstoned: BTreeSet::new(),
+            bonded_hist: BTreeMap::new(),
         }
     }
 
@@ -437,9 +501,49 @@ impl Staking {
 
             // clean old data before current height
             self.validator_clean_before_height(h.saturating_sub(1));
+
+            self.validator_record_bonded(h);
         }
     }
 
+    // Record the bonded amounts of the validators at a height,
+    // the evidences found later are slashed from them.
+    fn validator_record_bonded(&mut self, h: BlockHeight) {
+        let vd = if let Some(vd) = self.validator_info.get(&h) {
+            vd
+        } else {
+            return;
+        };
+
+        vd.body.iter().for_each(|(pk, v)| {
+            let am = BondedAmounts {
+                td_power: v.td_power,
+                delegators: v.delegators.iter().map(|(k, am)| (*k, *am)).collect(),
+            };
+            self.bonded_hist
+                .entry(*pk)
+                .or_insert_with(|| BondedHist::new(h))
+                .record(h, am);
+        });
+        self.bonded_hist
+            .iter_mut()
+            .filter(|(pk, _)| !vd.body.contains_key(pk))
+            .for_each(|(_, hist)| hist.record(h, BondedAmounts::default()));
+
+        let expired = h.saturating_sub(EVIDENCE_MAX_AGE_BLOCK_CNT);
+        self.bonded_hist.retain(|_, hist| hist.prune(expired));
+    }
+
+    // Get the bonded amounts of a validator at a height,
+    // `None` if it was not bonded or the height is out of the tracked history.
+    fn validator_get_bonded_at_height(
+        &self,
+        pk: &XfrPublicKey,
+        h: BlockHeight,
+    ) -> Option<BondedAmounts> {
+        self.bonded_hist.get(pk).and_then(|hist| hist.get(h))
+    }
+
     // Clean validator-info older than the specified height.
     #[inline(always)]
     fn validator_clean_before_height(&mut self, h: BlockHeight) {
@@ -450,13 +554,14 @@ impl Staking {
     // after they have been removed from tendermint core.
     fn validator_clean_invalid_items(&mut self) {
         let h = self.cur_height;
+        let unbond_block_cnt = self.params().unbond_block_cnt;
 
-        if CFG.checkpoint.unbond_block_cnt > h {
+        if unbond_block_cnt > h {
             return;
         }
 
         if let Some(old) = self
-            .validator_get_effective_at_height(h - CFG.checkpoint.unbond_block_cnt)
+            .validator_get_effective_at_height(h - unbond_block_cnt)
             .map(|ovd| {
                 ovd.body
                     .iter()
@@ -554,6 +659,60 @@ impl Staking {
             })
     }
 
+    /// Update the memo of a staker immediately,
+    /// and let its new commission rate take effect
+    /// after `COMMISSION_CHANGE_DELAY_BLOCK_CNT` blocks.
+    pub fn update_staker_delayed(&mut self, new: &Validator) -> Result<()> {
+        let h = self.cur_height;
+        let v = self
+            .validator_get_current_mut_one_by_id(&new.id)
+            .c(d!("Cannot update staker"))?;
+
+        v.memo = new.memo.clone();
+        if v.commission_rate != new.commission_rate {
+            v.pending_commission_rate =
+                Some((h + COMMISSION_CHANGE_DELAY_BLOCK_CNT, new.commission_rate));
+        }
+
+        Ok(())
+    }
+
+    /// Check the operator signatures attached to an operation signed by `id`,
+    /// they are required if `id` is a multisig-controlled validator,
+    /// and not allowed otherwise.
+    ///
+    /// NOTE: the signatures themselves should have been verified.
+    pub fn validator_check_operator_sigs(
+        &self,
+        id: &XfrPublicKey,
+        sigs: &OperatorCoSigs,
+    ) -> Result<()> {
+        match self
+            .validator_get_current_one_by_id(id)
+            .and_then(|v| v.operators.as_ref())
+        {
+            Some(rules) => sigs.check_rules(rules).c(d!()),
+            None if sigs.is_empty() => Ok(()),
+            None => Err(eg!("not a multisig-controlled validator")),
+        }
+    }
+
+    /// Apply the pending commission rates which are due,
+    /// call this function in `EndBlock`.
+    pub fn validator_apply_commission_changes(&mut self) {
+        let h = self.cur_height;
+        if let Some(vd) = self.validator_get_current_mut() {
+            vd.body.values_mut().for_each(|v| {
+                if let Some((end, rate)) = v.pending_commission_rate {
+                    if end <= h {
+                        v.commission_rate = rate;
+                        v.pending_commission_rate = None;
+                    }
+                }
+            });
+        }
+    }
+
     #[inline(always)]
     /// update staker
     pub fn update_staker(&mut self, new: &Validator) -> Result<()> {
@@ -684,12 +843,11 @@ impl Staking {
             return Err(eg!("global power overflow"));
         }
 
+        let percent = self.params().max_power_percent_per_validator;
         if ((power + new_power) as u128)
-            .checked_mul(MAX_POWER_PERCENT_PER_VALIDATOR[1])
+            .checked_mul(percent[1])
             .c(d!())?
-            > MAX_POWER_PERCENT_PER_VALIDATOR[0]
-                .checked_mul(global_power as u128)
-                .c(d!())?
+            > percent[0].checked_mul(global_power as u128).c(d!())?
         {
             return Err(eg!("validator power overflow"));
         }
@@ -711,6 +869,57 @@ impl Staking {
             .unwrap_or(0)
     }
 
+    /// Get the staking parameters effective at current height.
+    #[inline(always)]
+    pub fn params(&self) -> &StakingParams {
+        self.params_at_height(self.cur_height)
+    }
+
+    /// Get the staking parameters effective at a specified height.
+    #[inline(always)]
+    pub fn params_at_height(&self, h: BlockHeight) -> &StakingParams {
+        self.params
+            .range(0..=h)
+            .next_back()
+            .map(|(_, p)| p)
+            .unwrap_or(&DEFAULT_STAKING_PARAMS)
+    }
+
+    /// Get the staking parameters that will take effect after current height.
+    #[inline(always)]
+    pub fn params_get_pending(&self) -> BTreeMap<BlockHeight, StakingParams> {
+        self.params
+            .range(self.cur_height + 1..)
+            .map(|(h, p)| (*h, p.clone()))
+            .collect()
+    }
+
+    /// Set the staking parameters that will take effect at a specified height,
+    /// the pending ones at the same height will be replaced.
+    pub fn params_set_at_height(
+        &mut self,
+        h: BlockHeight,
+        params: StakingParams,
+    ) -> Result<()> {
+        if h <= self.cur_height {
+            return Err(eg!("the effective height must be in the future"));
+        }
+        params.check().c(d!())?;
+
+        // clean the out-dated versions
+        if let Some(effective) = self
+            .params
+            .range(0..=self.cur_height)
+            .next_back()
+            .map(|(h, _)| *h)
+        {
+            self.params = self.params.split_off(&effective);
+        }
+
+        self.params.insert(h, params);
+        Ok(())
+    }
+
     #[inline(always)]
     #[allow(missing_docs)]
     pub fn set_custom_block_height(&mut self, h: BlockHeight) {
@@ -731,6 +940,10 @@ impl Staking {
         validator: TendermintAddrRef,
         am: Amount,
     ) -> Result<()> {
+        if self.tombstoned.contains(validator) {
+            return Err(eg!("validator has been tombstoned"));
+        }
+
         let validator = self.validator_td_addr_to_app_pk(validator).c(d!())?;
         let end_height = BLOCK_HEIGHT_MAX;
 
@@ -743,6 +956,12 @@ impl Staking {
             }
             check_delegation_amount(am, true).c(d!())?;
             self.validator_check_power(am, &validator).c(d!())?;
+            if self
+                .validator_get_current_one_by_id(&validator)
+                .map_or(false, |v| v.is_tombstoned())
+            {
+                return Err(eg!("validator has been tombstoned"));
+            }
         }
 
         let h = self.cur_height;
@@ -757,6 +976,8 @@ impl Staking {
             rwd_amount: 0,
             delegation_rwd_cnt: 0,
             proposer_rwd_cnt: 0,
+            auto_compound: None,
+            reward_receiver: None,
         };
 
         let d = self
@@ -833,6 +1054,7 @@ impl Staking {
         }
 
         let h = self.cur_height;
+        let unbond_block_cnt = self.params().unbond_block_cnt;
         let mut orig_h = None;
         let mut is_validator = false;
 
@@ -844,8 +1066,9 @@ impl Staking {
             if BLOCK_HEIGHT_MAX == d.end_height {
                 if d.end_height != h {
                     orig_h = Some(d.end_height);
-                    d.end_height = h + CFG.checkpoint.unbond_block_cnt;
+                    d.end_height = h + unbond_block_cnt;
                 }
+                d.auto_compound = None;
             } else {
                 return Err(eg!("delegator is not bonded"));
             }
@@ -898,7 +1121,7 @@ impl Staking {
                 .map(|set| set.remove(addr));
             self.delegation_info
                 .end_height_map
-                .entry(h + CFG.checkpoint.unbond_block_cnt)
+                .entry(h + unbond_block_cnt)
                 .or_insert_with(BTreeSet::new)
                 .insert(*addr);
         }
@@ -920,6 +1143,8 @@ impl Staking {
 
         let new_tmp_delegator;
         let h = self.cur_height;
+        let unbond_block_cnt = self.params().unbond_block_cnt;
+        let validator_min_power = self.params().validator_min_power;
         let is_validator = self.addr_is_validator(addr);
 
         let target_validator = self
@@ -932,9 +1157,7 @@ impl Staking {
             .global_delegation_records_map
             .get_mut(addr)
         {
-            if is_validator
-                && STAKING_VALIDATOR_MIN_POWER > d.amount().saturating_sub(pu.am)
-            {
+            if is_validator && validator_min_power > d.amount().saturating_sub(pu.am) {
                 return Err(eg!("Requested amount exceeds limits"));
             }
 
@@ -961,11 +1184,13 @@ impl Staking {
                     receiver_pk: Some(d.id),
                     tmp_delegators: map! {B},
                     start_height: d.start_height,
-                    end_height: h + CFG.checkpoint.unbond_block_cnt,
+                    end_height: h + unbond_block_cnt,
                     state: DelegationState::Bond,
                     rwd_amount: 0,
                     delegation_rwd_cnt: 0,
                     proposer_rwd_cnt: 0,
+                    auto_compound: None,
+                    reward_receiver: d.reward_receiver,
                 };
                 // record per-block-height self-delegation amount for a validator
                 if target_validator == *addr && *KEEP_HIST {
@@ -990,7 +1215,7 @@ impl Staking {
             .insert(pu.new_delegator_id, new_tmp_delegator);
         self.delegation_info
             .end_height_map
-            .entry(h + CFG.checkpoint.unbond_block_cnt)
+            .entry(h + unbond_block_cnt)
             .or_insert_with(BTreeSet::new)
             .insert(pu.new_delegator_id);
 
@@ -1009,6 +1234,212 @@ impl Staking {
         Ok(())
     }
 
+    /// The pending partial undelegations of `owner`,
+    /// in the form of `(unbonding id, temporary delegation)`.
+    pub fn delegation_get_unbonding_entries(
+        &self,
+        owner: &XfrPublicKey,
+    ) -> Vec<(XfrPublicKey, &Delegation)> {
+        self.delegation_get(owner)
+            .map(|d| {
+                d.tmp_delegators
+                    .keys()
+                    .filter_map(|id| self.delegation_get(id).map(|tmp| (*id, tmp)))
+                    .filter(|(_, tmp)| DelegationState::Bond == tmp.state)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Re-bond a pending partial undelegation to its original validator,
+    /// its rewards will be moved to the original delegation.
+    ///
+    /// NOTE: the vote power of the validator is not changed,
+    /// it will only be decreased when the unbonding matures.
+    pub fn cancel_unbonding(
+        &mut self,
+        owner: &XfrPublicKey,
+        id: &XfrPublicKey,
+    ) -> Result<()> {
+        let h = self.cur_height;
+
+        let d = self.delegation_get(owner).c(d!("delegator not found"))?;
+        if DelegationState::Bond != d.state || BLOCK_HEIGHT_MAX != d.end_height {
+            return Err(eg!("delegator is out of bond"));
+        }
+        if !d.tmp_delegators.contains_key(id) {
+            return Err(eg!("unbonding entry not found"));
+        }
+
+        let tmp = self.delegation_get(id).c(d!("unbonding entry not found"))?;
+        if DelegationState::Bond != tmp.state || tmp.end_height <= h {
+            return Err(eg!("unbonding has matured"));
+        }
+        let (vid, am) = tmp
+            .delegations
+            .iter()
+            .next()
+            .map(|(vid, am)| (*vid, *am))
+            .c(d!())?;
+        let tmp_end_height = tmp.end_height;
+        let tmp_rwd_amount = tmp.rwd_amount;
+
+        let v = self
+            .validator_get_current_mut_one_by_id(&vid)
+            .c(d!("validator not found"))?;
+        if v.is_tombstoned() {
+            return Err(eg!("validator has been tombstoned"));
+        }
+        v.delegators.remove(id);
+        if *owner != vid {
+            *v.delegators.entry(*owner).or_insert(0) += am;
+        }
+        v.delegators.sort_by(|_, v1, _, v2| v2.cmp(&v1));
+
+        self.delegation_info
+            .global_delegation_records_map
+            .remove(id);
+        if let Some(addrs) = self.delegation_info.end_height_map.get_mut(&tmp_end_height)
+        {
+            addrs.remove(id);
+            if addrs.is_empty() {
+                self.delegation_info.end_height_map.remove(&tmp_end_height);
+            }
+        }
+
+        let d = self.delegation_get_mut(owner).c(d!())?;
+        d.tmp_delegators.remove(id);
+        *d.delegations.entry(vid).or_insert(0) += am;
+        d.rwd_amount = d.rwd_amount.saturating_add(tmp_rwd_amount);
+
+        // record per-block-height self-delegation amount for a validator
+        if vid == *owner && *KEEP_HIST {
+            CHAN_V_SELF_D_HIST
+                .0
+                .lock()
+                .send((d.id, h, d.delegations.values().sum()))
+                .unwrap();
+        }
+
+        Ok(())
+    }
+
+    /// Move a bonded amount of `owner` from a validator to another one,
+    /// without going through the unbonding period.
+    /// - decrease the vote power of the source validator
+    /// - increase the vote power of the destination validator
+    ///
+    /// A delegator can redelegate once every `REDELEGATION_COOLDOWN_BLOCK_CNT` blocks,
+    /// and the self-delegation of a validator can not be redelegated.
+    pub fn redelegate(
+        &mut self,
+        owner: &XfrPublicKey,
+        src: TendermintAddrRef,
+        dst: TendermintAddrRef,
+        am: Amount,
+    ) -> Result<()> {
+        let src = self
+            .validator_td_addr_to_app_pk(src)
+            .c(d!("Invalid source validator"))?;
+        let dst = self
+            .validator_td_addr_to_app_pk(dst)
+            .c(d!("Invalid destination validator"))?;
+        let h = self.cur_height;
+
+        // check everything in advance before changing the data
+        {
+            if src == dst {
+                return Err(eg!("source and destination validators are the same"));
+            }
+            if self.addr_is_validator(owner) {
+                return Err(eg!("validator can not redelegate"));
+            }
+            if self.validator_get_current_one_by_id(&src).is_none() {
+                return Err(eg!("source validator not found"));
+            }
+            if !self.delegation_has_addr(&dst) {
+                return Err(eg!("self-delegation has not been finished"));
+            }
+            if self
+                .validator_get_current_one_by_id(&dst)
+                .map_or(true, |v| v.is_jailed() || v.is_tombstoned())
+            {
+                return Err(eg!("destination validator is jailed or tombstoned"));
+            }
+            if let Some(last) = self.delegation_info.redelegation_height_map.get(owner) {
+                if h < last + REDELEGATION_COOLDOWN_BLOCK_CNT {
+                    return Err(eg!(format!(
+                        "redelegation is cooling down until height {}",
+                        last + REDELEGATION_COOLDOWN_BLOCK_CNT
+                    )));
+                }
+            }
+
+            let d = self.delegation_get(owner).c(d!("delegator not found"))?;
+            if BLOCK_HEIGHT_MAX != d.end_height || DelegationState::Bond != d.state {
+                return Err(eg!("delegator is not bonded"));
+            }
+
+            check_delegation_amount(am, true).c(d!())?;
+            if d.validator_entry(&src).unwrap_or(0) < am {
+                return Err(eg!("Amount exceeds the delegation to source validator"));
+            }
+            self.validator_check_power(am, &dst).c(d!())?;
+        }
+
+        // unwrap is safe here
+        let d = self.delegation_get_mut(owner).unwrap();
+        if let Some(src_am) = d.delegations.get_mut(&src) {
+            *src_am -= am;
+            if 0 == *src_am {
+                d.delegations.remove(&src);
+            }
+        }
+        *d.delegations.entry(dst).or_insert(0) += am;
+
+        // update delegator entries for both validators
+        if let Some(v) = self.validator_get_current_mut_one_by_id(&src) {
+            if let Some(v_am) = v.delegators.get_mut(owner) {
+                *v_am = v_am.saturating_sub(am);
+                if 0 == *v_am {
+                    v.delegators.shift_remove(owner);
+                }
+            }
+            v.delegators.sort_by(|_, v1, _, v2| v2.cmp(&v1));
+            if *KEEP_HIST {
+                CHAN_D_AMOUNT_HIST
+                    .0
+                    .lock()
+                    .send((v.id, h, v.delegators.values().sum()))
+                    .unwrap();
+            }
+        }
+        if let Some(v) = self.validator_get_current_mut_one_by_id(&dst) {
+            *v.delegators.entry(*owner).or_insert(0) += am;
+            v.delegators.sort_by(|_, v1, _, v2| v2.cmp(&v1));
+            if *KEEP_HIST {
+                CHAN_D_AMOUNT_HIST
+                    .0
+                    .lock()
+                    .send((v.id, h, v.delegators.values().sum()))
+                    .unwrap();
+            }
+        }
+
+        // There should be no failure here !!
+        //
+        // increase the destination first,
+        // the power check is the same as the one done above
+        pnk!(self.validator_change_power(&dst, am, false));
+        pnk!(self.validator_change_power(&src, am, true));
+
+        let redelegations = &mut self.delegation_info.redelegation_height_map;
+        redelegations.retain(|_, last| h < *last + REDELEGATION_COOLDOWN_BLOCK_CNT);
+        redelegations.insert(*owner, h);
+
+        Ok(())
+    }
+
     #[inline(always)]
     fn delegation_clean_paid(
         &mut self,
@@ -1143,7 +1574,7 @@ impl Staking {
     ) -> BTreeMap<XfrPublicKey, (Amount, Option<XfrPublicKey>)> {
         self.delegation_get_freed_before_height(h)
             .into_iter()
-            .map(|(k, d)| (k, (d.amount(), d.receiver_pk)))
+            .map(|(k, d)| (k, (d.amount(), d.reward_receiver.or(d.receiver_pk))))
             .collect()
     }
 
@@ -1215,7 +1646,8 @@ impl Staking {
             .collect()
     }
 
-    /// Clean delegation states along with each new block.
+    /// Clean delegation states along with each new block,
+    /// and compound the rewards of the delegations who enabled it.
     #[inline(always)]
     pub fn delegation_process(&mut self) {
         let h = self.cur_height;
@@ -1270,6 +1702,8 @@ impl Staking {
         self.delegation_process_finished_before_height(h);
 
         self.validator_clean_invalid_items();
+
+        self.delegation_auto_compound();
     }
 
     // call this when:
@@ -1301,6 +1735,57 @@ impl Staking {
             });
     }
 
+    /// Count the blocks missed by the validators in the sliding window,
+    /// and jail the ones who missed too many.
+    ///
+    /// @param offline: validators that should have signed the last block but did not
+    pub fn validator_handle_missed_blocks(
+        &mut self,
+        offline: &BTreeSet<&Vec<u8>>,
+    ) -> Result<()> {
+        let h = self.cur_height;
+
+        self.validator_get_current_mut()
+            .c(d!())?
+            .body
+            .values_mut()
+            .filter(|v| !v.is_jailed())
+            .for_each(|v| {
+                v.missed_blocks.record(h, offline.contains(&v.td_addr));
+                if JAIL_MISSED_BLOCK_MAX < v.missed_blocks.count() {
+                    v.missed_blocks = MissedBlocks::default();
+                    v.jailed_until = Some(h + JAIL_MIN_BLOCK_CNT);
+                }
+            });
+
+        Ok(())
+    }
+
+    /// Release a jailed validator after its jail period,
+    /// then it will rejoin the validator set of tendermint.
+    pub fn validator_unjail(&mut self, id: &XfrPublicKey) -> Result<()> {
+        let h = self.cur_height;
+
+        if !self.delegation_has_addr(id) {
+            return Err(eg!("self-delegation has not been finished"));
+        }
+
+        let v = self
+            .validator_get_current_mut_one_by_id(id)
+            .c(d!("validator not found"))?;
+        if v.is_tombstoned() {
+            return Err(eg!("validator has been tombstoned"));
+        }
+        match v.jailed_until {
+            None => Err(eg!("validator is not jailed")),
+            Some(end) if h < end => Err(eg!("still in the jail period")),
+            Some(_) => {
+                v.jailed_until = None;
+                Ok(())
+            }
+        }
+    }
+
     /// Penalize the FRAs by a specified address.
     #[inline(always)]
     pub fn governance_penalty(
@@ -1353,6 +1838,108 @@ impl Staking {
         Ok(())
     }
 
+    /// Handle the evidence of a byzantine behavior reported by tendermint,
+    /// - duplicate evidences are ignored
+    /// - only the stake bonded at the infraction height is slashed
+    /// - evidences out of the tracked validator history are rejected
+    /// - double-signers are tombstoned, they will never validate again
+    pub fn governance_handle_evidence(
+        &mut self,
+        addr: TendermintAddrRef,
+        height: BlockHeight,
+        kind: ByzantineKind,
+    ) -> Result<()> {
+        let expired = self.cur_height.saturating_sub(EVIDENCE_MAX_AGE_BLOCK_CNT);
+        self.evidence_handled = self.evidence_handled.split_off(&expired);
+        if height < expired {
+            return Err(eg!("evidence is too old"));
+        }
+        let pk = self.validator_td_addr_to_app_pk(addr).c(d!())?;
+        if self.validator_get_bonded_at_height(&pk, height).is_none() {
+            return Err(eg!("validator is not bonded at the infraction height"));
+        }
+        if !self
+            .evidence_handled
+            .entry(height)
+            .or_insert_with(BTreeSet::new)
+            .insert((addr.to_owned(), kind.clone()))
+        {
+            return Err(eg!("duplicate evidence"));
+        }
+
+        let h = self.cur_height;
+        let v = self
+            .validator_get_current_mut_one_by_id(&pk)
+            .c(d!("validator not found"))?;
+
+        // a tombstoned validator has been punished for its first infraction
+        if v.is_tombstoned() {
+            return Ok(());
+        }
+        if ByzantineKind::DuplicateVote == kind {
+            v.tombstoned_at = Some(h);
+            self.tombstoned.insert(addr.to_owned());
+        }
+
+        // Any validator who has unstaked itself should not be punished,
+        // its delegators should not be punished also.
+        if BLOCK_HEIGHT_MAX != self.delegation_get(&pk).c(d!())?.end_height {
+            return Ok(());
+        }
+
+        let percent = self
+            .params()
+            .penalty_rules
+            .get(&kind)
+            .map(|rule| rule.gen_penalty_percent())
+            .c(d!())?;
+        self.governance_penalty_at_height(&pk, percent, height)
+            .c(d!())
+    }
+
+    // Like `governance_penalty_by_pubkey`,
+    // but the amounts are calculated from the bonded amounts at the infraction height.
+    fn governance_penalty_at_height(
+        &mut self,
+        addr: &XfrPublicKey,
+        percent: [u64; 2],
+        height: BlockHeight,
+    ) -> Result<()> {
+        if 0 == percent[1] || percent[1] > i64::MAX as Amount || percent[0] > percent[1]
+        {
+            return Err(eg!());
+        }
+        let calc = |am: Amount| {
+            (am as u128 * percent[0] as u128 / percent[1] as u128) as Amount
+        };
+
+        let v = self
+            .validator_get_bonded_at_height(addr, height)
+            .c(d!("validator not bonded at the infraction height"))?;
+        let self_bonded = v.td_power.saturating_sub(v.delegators.values().sum());
+        let bonded_power = v.td_power;
+        let pl = v
+            .delegators
+            .iter()
+            .map(|(pk, am)| (*pk, calc(*am)))
+            .collect::<Vec<_>>();
+
+        // punish itself
+        self.governance_penalty_sub_amount(addr, calc(self_bonded))
+            .c(d!())?;
+
+        // punish related delegators
+        pl.into_iter().for_each(|(pk, p_am)| {
+            ruc::info_omit!(self.governance_penalty_sub_amount(&pk, p_am));
+        });
+
+        // punish its vote power
+        self.validator_get_power(addr).c(d!()).and_then(|power| {
+            self.validator_change_power(addr, power.min(calc(bonded_power)), true)
+                .c(d!())
+        })
+    }
+
     #[inline(always)]
     fn governance_penalty_sub_amount(
         &mut self,
@@ -1374,6 +1961,7 @@ impl Staking {
                 bond: d.amount(),
                 amount: 0,
                 penalty_amount: am,
+                compounded_amount: 0,
                 return_rate: None,
                 commission_rate: None,
                 global_delegation_percent: None,
@@ -1406,41 +1994,268 @@ impl Staking {
         Ok(())
     }
 
-    /// Look up the `XfrPublicKey`
-    /// co-responding to a specified 'tendermint node address'.
-    #[inline(always)]
-    pub fn validator_td_addr_to_app_pk(
-        &self,
-        addr: TendermintAddrRef,
-    ) -> Result<XfrPublicKey> {
-        self.validator_get_current()
-            .c(d!("No validators at current height"))
-            .and_then(|vd| {
-                vd.addr_td_to_app
-                    .get(addr)
-                    .copied()
-                    .c(d!(format!("Failed to get pk {}", addr)))
-            })
-    }
+    /// Submit a new governance proposal,
+    /// the deposit should have been paid to `BLACK_HOLE_PUBKEY_STAKING`.
+    pub fn proposal_submit(
+        &mut self,
+        proposer: XfrPublicKey,
+        title: String,
+        description: String,
+        content: ProposalContent,
+        deposit: Amount,
+    ) -> Result<ProposalId> {
+        if PROPOSAL_MIN_DEPOSIT > deposit {
+            return Err(eg!("deposit is too small"));
+        }
 
-    /// Lookup up the 'tendermint node address' by the 'XfrPublicKey'
-    #[inline(always)]
-    pub fn validator_app_pk_to_td_addr(
-        &self,
-        key: &XfrPublicKey,
-    ) -> Result<TendermintAddr> {
-        self.validator_get_current().c(d!()).and_then(|vd| {
-            vd.body
-                .get(key)
-                .c(d!())
-                .map(|v| td_addr_to_string(&v.td_addr))
-        })
-    }
+        if title.is_empty()
+            || PROPOSAL_TITLE_LEN_MAX < title.len()
+            || PROPOSAL_DESC_LEN_MAX < description.len()
+        {
+            return Err(eg!("invalid title or description"));
+        }
 
-    /// Generate sha256 digest.
-    #[inline(always)]
-    pub fn hash(&self) -> Result<Digest> {
-        bincode::serialize(self)
+        match &content {
+            ProposalContent::Text => {}
+            ProposalContent::ParamChange(params) => {
+                params.check().c(d!())?;
+            }
+            ProposalContent::TreasurySpend { amount, .. } => {
+                if 0 == *amount {
+                    return Err(eg!("invalid amount"));
+                }
+            }
+        }
+
+        let h = self.cur_height;
+        let id = self.proposal_info.next_id;
+        let voting_end_height = h + PROPOSAL_VOTING_BLOCK_CNT;
+
+        self.proposal_info.next_id += 1;
+        self.proposal_info.proposals.insert(
+            id,
+            Proposal {
+                id,
+                proposer,
+                title,
+                description,
+                content,
+                deposit,
+                deposit_refunded: false,
+                submit_height: h,
+                voting_end_height,
+                status: ProposalStatus::Voting,
+                votes: BTreeMap::new(),
+                tally: None,
+            },
+        );
+        self.proposal_info
+            .end_height_map
+            .entry(voting_end_height)
+            .or_insert_with(BTreeSet::new)
+            .insert(id);
+
+        Ok(id)
+    }
+
+    /// Vote on a proposal in its voting period,
+    /// only validators and bonded delegators can vote,
+    /// a later vote replaces the previous one of the same voter.
+    pub fn proposal_vote(
+        &mut self,
+        voter: &XfrPublicKey,
+        id: ProposalId,
+        option: VoteOption,
+    ) -> Result<()> {
+        let h = self.cur_height;
+        let is_bonded = self.addr_is_validator(voter)
+            || self
+                .delegation_get(voter)
+                .map(|d| DelegationState::Bond == d.state && 0 < d.amount())
+                .unwrap_or(false);
+        if !is_bonded {
+            return Err(eg!("voter is neither a validator nor a bonded delegator"));
+        }
+
+        let p = self
+            .proposal_info
+            .proposals
+            .get_mut(&id)
+            .c(d!("proposal not found"))?;
+        if ProposalStatus::Voting != p.status || h > p.voting_end_height {
+            return Err(eg!("proposal is not in the voting period"));
+        }
+
+        p.votes.insert(*voter, option);
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn proposal_get(&self, id: ProposalId) -> Option<&Proposal> {
+        self.proposal_info.proposals.get(&id)
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn proposal_get_all(&self) -> &BTreeMap<ProposalId, Proposal> {
+        &self.proposal_info.proposals
+    }
+
+    /// Count the votes of a proposal by the current bonded power,
+    /// a delegator who has not voted inherits the vote of its validator.
+    pub fn proposal_tally(&self, p: &Proposal) -> TallyResult {
+        let mut res = TallyResult::default();
+
+        if let Some(vd) = self.validator_get_current() {
+            vd.body.values().filter(|v| 0 < v.td_power).for_each(|v| {
+                let v_vote = p.votes.get(&v.id).copied();
+                let mut delegated: Amount = 0;
+                v.delegators.iter().for_each(|(pk, am)| {
+                    delegated = delegated.saturating_add(*am);
+                    if let Some(option) = p.votes.get(pk).copied().or(v_vote) {
+                        res.add(option, *am);
+                    }
+                });
+                if let Some(option) = v_vote {
+                    res.add(option, v.td_power.saturating_sub(delegated));
+                }
+                res.global_power = res.global_power.saturating_add(v.td_power);
+            });
+        }
+
+        res
+    }
+
+    /// Tally the proposals whose voting period has ended,
+    /// then execute the passed ones and refund the deposits.
+    ///
+    /// A proposal is finalized in the block after its `voting_end_height`,
+    /// so the votes in the last block of the voting period are always counted.
+    pub fn proposal_process(&mut self) {
+        let h = self.cur_height;
+
+        let ids = self
+            .proposal_info
+            .end_height_map
+            .range(..h)
+            .flat_map(|(_, ids)| ids.iter().copied())
+            .collect::<Vec<_>>();
+        if ids.is_empty() {
+            return;
+        }
+        self.proposal_info.end_height_map =
+            self.proposal_info.end_height_map.split_off(&h);
+
+        ids.into_iter().for_each(|id| {
+            ruc::info_omit!(self.proposal_finish(id));
+        });
+    }
+
+    fn proposal_finish(&mut self, id: ProposalId) -> Result<()> {
+        let p = self.proposal_get(id).c(d!("proposal not found"))?;
+        let tally = self.proposal_tally(p);
+        let proposer = p.proposer;
+        let deposit = p.deposit;
+        let content = p.content.clone();
+
+        let (status, refund) = if !tally.is_quorum_reached() || tally.is_vetoed() {
+            (ProposalStatus::Rejected, false)
+        } else if tally.is_passed() {
+            let status = if ruc::info!(self.proposal_execute(content)).is_ok() {
+                ProposalStatus::Passed
+            } else {
+                ProposalStatus::Failed
+            };
+            (status, true)
+        } else {
+            (ProposalStatus::Rejected, true)
+        };
+
+        if refund {
+            self.coinbase_add_payment(proposer, deposit).c(d!())?;
+        }
+
+        let p = self
+            .proposal_info
+            .proposals
+            .get_mut(&id)
+            .c(d!("proposal not found"))?;
+        p.status = status;
+        p.deposit_refunded = refund;
+        p.tally = Some(tally);
+
+        Ok(())
+    }
+
+    fn proposal_execute(&mut self, content: ProposalContent) -> Result<()> {
+        match content {
+            ProposalContent::Text => Ok(()),
+            ProposalContent::ParamChange(params) => self
+                .params_set_at_height(self.cur_height + 1, params)
+                .c(d!()),
+            ProposalContent::TreasurySpend { receiver, amount } => {
+                let planned = self
+                    .coinbase
+                    .distribution_plan
+                    .values()
+                    .fold(0, |acc: Amount, am| acc.saturating_add(*am));
+                if self.coinbase.balance.saturating_sub(planned) < amount {
+                    return Err(eg!("insufficient coinbase balance"));
+                }
+                let v = self.coinbase.distribution_plan.entry(receiver).or_insert(0);
+                *v = v.checked_add(amount).c(d!("overflow"))?;
+                Ok(())
+            }
+        }
+    }
+
+    // Pay back FRA that was not minted by the coinbase,
+    // e.g. the deposit of a proposal.
+    fn coinbase_add_payment(&mut self, pk: XfrPublicKey, am: Amount) -> Result<()> {
+        let v = self.coinbase.distribution_plan.entry(pk).or_insert(0);
+        *v = v.checked_add(am).c(d!("overflow"))?;
+        self.coinbase.balance =
+            self.coinbase.balance.checked_add(am).c(d!("overflow"))?;
+        Ok(())
+    }
+
+    /// Look up the `XfrPublicKey`
+    /// co-responding to a specified 'tendermint node address'.
+    #[inline(always)]
+    pub fn validator_td_addr_to_app_pk(
+        &self,
+        addr: TendermintAddrRef,
+    ) -> Result<XfrPublicKey> {
+        self.validator_get_current()
+            .c(d!("No validators at current height"))
+            .and_then(|vd| {
+                vd.addr_td_to_app
+                    .get(addr)
+                    .copied()
+                    .c(d!(format!("Failed to get pk {}", addr)))
+            })
+    }
+
+    /// Lookup up the 'tendermint node address' by the 'XfrPublicKey'
+    #[inline(always)]
+    pub fn validator_app_pk_to_td_addr(
+        &self,
+        key: &XfrPublicKey,
+    ) -> Result<TendermintAddr> {
+        self.validator_get_current().c(d!()).and_then(|vd| {
+            vd.body
+                .get(key)
+                .c(d!())
+                .map(|v| td_addr_to_string(&v.td_addr))
+        })
+    }
+
+    /// Generate sha256 digest.
+    #[inline(always)]
+    pub fn hash(&self) -> Result<Digest> {
+        bincode::serialize(self)
             .c(d!())
             .map(|bytes| sha256::hash(&bytes))
     }
@@ -1639,6 +2454,167 @@ impl Staking {
         Err(eg!(@vote_percent))
     }
 
+    /// Enable auto-compounding with an interval in blocks,
+    /// or disable it with `None`.
+    pub fn delegation_set_auto_compound(
+        &mut self,
+        pk: &XfrPublicKey,
+        interval: Option<u64>,
+    ) -> Result<()> {
+        let h = self.cur_height;
+
+        if let Some(itv) = interval {
+            if AUTO_COMPOUND_MIN_BLOCK_CNT > itv || BLOCK_HEIGHT_MAX < itv {
+                return Err(eg!("invalid interval"));
+            }
+        }
+
+        let d = self.delegation_get_mut(pk).c(d!("delegation not found"))?;
+        if DelegationState::Bond != d.state || BLOCK_HEIGHT_MAX != d.end_height {
+            return Err(eg!("delegation is not bonded"));
+        }
+        d.auto_compound = interval.map(|itv| AutoCompound {
+            interval: itv,
+            next_height: h + itv,
+        });
+
+        Ok(())
+    }
+
+    // Compound the delegations whose intervals have arrived,
+    // those no longer bonded are turned off.
+    fn delegation_auto_compound(&mut self) {
+        let h = self.cur_height;
+
+        self.delegation_info
+            .global_delegation_records_map
+            .iter()
+            .filter(|(_, d)| d.auto_compound.map_or(false, |ac| ac.next_height <= h))
+            .map(|(pk, _)| *pk)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .for_each(|pk| {
+                if let Some(d) = self.delegation_get_mut(&pk) {
+                    if DelegationState::Bond != d.state
+                        || BLOCK_HEIGHT_MAX != d.end_height
+                    {
+                        d.auto_compound = None;
+                        return;
+                    }
+                }
+                ruc::info_omit!(self.delegation_compound(&pk));
+                if let Some(ac) = self
+                    .delegation_get_mut(&pk)
+                    .and_then(|d| d.auto_compound.as_mut())
+                {
+                    ac.next_height = h + ac.interval;
+                }
+            });
+    }
+
+    /// Move the rewards of a delegation into its bonded principal,
+    /// the rewards are shared among its validators by the delegated amounts.
+    pub fn delegation_compound(&mut self, pk: &XfrPublicKey) -> Result<()> {
+        let d = self.delegation_get(pk).c(d!("delegation not found"))?;
+        if DelegationState::Bond != d.state || BLOCK_HEIGHT_MAX != d.end_height {
+            return Err(eg!("delegation is not bonded"));
+        }
+
+        // rewards are paid from the coinbase
+        let rwd = d.rwd_amount.min(self.coinbase.balance);
+        let total = d.amount();
+        if 0 == rwd || 0 == total {
+            return Ok(());
+        }
+
+        let mut shares = d
+            .delegations
+            .iter()
+            .filter(|(_, am)| 0 < **am)
+            .map(|(vid, am)| {
+                (*vid, (rwd as u128 * *am as u128 / total as u128) as Amount)
+            })
+            .collect::<Vec<_>>();
+        // the remainder goes to the biggest entry
+        let remainder = rwd - shares.iter().map(|(_, am)| am).sum::<Amount>();
+        if let Some(i) = shares.iter_mut().max_by_key(|(_, am)| *am) {
+            i.1 += remainder;
+        }
+
+        let mut compounded = 0;
+        for (vid, am) in shares.into_iter().filter(|(_, am)| 0 < *am) {
+            let td_addr = self
+                .validator_get_current_one_by_id(&vid)
+                .map(|v| td_addr_to_string(&v.td_addr))
+                .c(d!("validator not found"))?;
+            self.delegate(*pk, &td_addr, am).c(d!())?;
+            self.coinbase.balance -= am;
+            self.delegation_get_mut(pk).c(d!())?.rwd_amount -= am;
+            compounded += am;
+        }
+
+        if *KEEP_HIST {
+            let d = self.delegation_get(pk).c(d!())?;
+            let r = DelegationRwdDetail {
+                bond: d.amount(),
+                amount: 0,
+                penalty_amount: 0,
+                compounded_amount: compounded,
+                return_rate: None,
+                commission_rate: None,
+                global_delegation_percent: None,
+                block_height: self.cur_height,
+            };
+            CHAN_D_RWD_HIST
+                .0
+                .lock()
+                .send((d.id, self.cur_height, r))
+                .unwrap();
+        }
+
+        Ok(())
+    }
+
+    /// Set or rotate the withdrawal address of a validator,
+    /// its rewards and self-delegation will be paid to this address,
+    /// including the pending partial undelegations.
+    ///
+    /// `None` means paying to the validator key itself.
+    pub fn validator_set_reward_receiver(
+        &mut self,
+        id: &XfrPublicKey,
+        receiver: Option<XfrPublicKey>,
+    ) -> Result<()> {
+        if !self.addr_is_validator(id) {
+            return Err(eg!("not a validator"));
+        }
+
+        let receiver = receiver.filter(|r| r != id);
+        let d = self.delegation_get_mut(id).c(d!("delegation not found"))?;
+        if DelegationState::Paid == d.state {
+            return Err(eg!("delegation has been paid"));
+        }
+        d.reward_receiver = receiver;
+
+        let tmps = d.tmp_delegators.keys().copied().collect::<Vec<_>>();
+        tmps.iter().for_each(|k| {
+            if let Some(tmp) = self.delegation_get_mut(k) {
+                tmp.reward_receiver = receiver;
+            }
+        });
+
+        Ok(())
+    }
+
+    /// The withdrawal address which the rewards of `pk` will be paid to, if any.
+    #[inline(always)]
+    pub fn delegation_get_reward_receiver(
+        &self,
+        pk: &XfrPublicKey,
+    ) -> Option<XfrPublicKey> {
+        self.delegation_get(pk).and_then(|d| d.reward_receiver)
+    }
+
     /// Claim delegation rewards.
     pub fn claim(&mut self, pk: XfrPublicKey, am: Option<Amount>) -> Result<()> {
         let am = self.delegation_get_mut(&pk).c(d!()).and_then(|d| {
@@ -1664,6 +2640,10 @@ impl Staking {
 
     /// new validators from public staking operations
     pub fn validator_add_staker(&mut self, h: BlockHeight, v: Validator) -> Result<()> {
+        if self.tombstoned.contains(&td_addr_to_string(&v.td_addr)) {
+            return Err(eg!("validator has been tombstoned"));
+        }
+
         if let Some(vd) = self.validator_get_effective_at_height(h) {
             if vd.body.contains_key(&v.id)
                 || vd
@@ -1848,6 +2828,10 @@ pub(crate) struct DelegationInfo {
     #[serde(rename = "addr_map")]
     pub(crate) global_delegation_records_map: BTreeMap<XfrPublicKey, Delegation>,
     pub(crate) end_height_map: BTreeMap<BlockHeight, BTreeSet<XfrPublicKey>>,
+    // delegator pubkey => height of its last redelegation,
+    // only the ones still in the cooldown period are kept
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) redelegation_height_map: BTreeMap<XfrPublicKey, BlockHeight>,
 }
 
 impl DelegationInfo {
@@ -1856,6 +2840,7 @@ impl DelegationInfo {
             global_amount: 0,
             global_delegation_records_map: BTreeMap::new(),
             end_height_map: BTreeMap::new(),
+            redelegation_height_map: BTreeMap::new(),
         }
     }
 }
@@ -1906,12 +2891,178 @@ pub struct Validator {
     pub signed_last_block: bool,
     /// how many blocks has the validator signed
     pub signed_cnt: u64,
+    /// the blocks missed by this validator in the sliding window
+    #[serde(default, skip_serializing_if = "MissedBlocks::is_empty")]
+    pub missed_blocks: MissedBlocks,
+    /// if jailed, the height since which it can be unjailed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jailed_until: Option<BlockHeight>,
+    /// if tombstoned for double-signing, the height at which it happened,
+    /// a tombstoned validator will never rejoin the validator set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tombstoned_at: Option<BlockHeight>,
+    /// the limits of the commission rate, declared when staking
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commission_policy: Option<CommissionPolicy>,
+    /// a new commission rate and the height at which it takes effect
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_commission_rate: Option<(BlockHeight, [u64; 2])>,
+    /// if set, the operations signed by `id` also need enough signatures
+    /// of these operators, declared when staking and can not be changed later
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operators: Option<SignatureRules>,
 
     /// delegator pubkey => amount
     ///   - delegator entries on current block height
     pub delegators: IndexMap<XfrPublicKey, Amount>,
 }
 
+// The bonded amounts of a validator,
+// all zero if it is not in the validator set.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+struct BondedAmounts {
+    td_power: Amount,
+    delegators: BTreeMap<XfrPublicKey, Amount>,
+}
+
+// The latest bonded amounts of a validator,
+// and the changes to roll them back to an earlier height.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct BondedHist {
+    // the first tracked height
+    since: BlockHeight,
+    cur: BondedAmounts,
+    // height => the amounts before that height,
+    // only the delegators that have changed are included, `0` if absent
+    undo: BTreeMap<BlockHeight, BondedAmounts>,
+}
+
+impl BondedHist {
+    fn new(since: BlockHeight) -> Self {
+        BondedHist {
+            since,
+            cur: BondedAmounts::default(),
+            undo: BTreeMap::new(),
+        }
+    }
+
+    fn record(&mut self, h: BlockHeight, new: BondedAmounts) {
+        if new == self.cur {
+            return;
+        }
+
+        let cur = &self.cur;
+        let changed = cur
+            .delegators
+            .keys()
+            .chain(new.delegators.keys())
+            .filter(|k| cur.delegators.get(*k) != new.delegators.get(*k))
+            .map(|k| (*k, cur.delegators.get(k).copied().unwrap_or(0)))
+            .collect();
+        self.undo.insert(
+            h,
+            BondedAmounts {
+                td_power: cur.td_power,
+                delegators: changed,
+            },
+        );
+        self.cur = new;
+    }
+
+    fn get(&self, h: BlockHeight) -> Option<BondedAmounts> {
+        if h < self.since {
+            return None;
+        }
+
+        let mut am = self.cur.clone();
+        self.undo
+            .range(h.saturating_add(1)..)
+            .rev()
+            .for_each(|(_, prev)| {
+                am.td_power = prev.td_power;
+                prev.delegators.iter().for_each(|(k, v)| {
+                    if 0 == *v {
+                        am.delegators.remove(k);
+                    } else {
+                        am.delegators.insert(*k, *v);
+                    }
+                });
+            });
+
+        alt!(0 == am.td_power, None, Some(am))
+    }
+
+    // Forget the changes before `expired`,
+    // return `false` if nothing within the window is left.
+    fn prune(&mut self, expired: BlockHeight) -> bool {
+        self.undo = self.undo.split_off(&(expired + 1));
+        self.since = self.since.max(expired);
+        !self.undo.is_empty() || 0 != self.cur.td_power
+    }
+}
+
+/// The blocks missed by a validator in the sliding window of `JAIL_WINDOW_BLOCK_CNT` blocks,
+/// one bit for each height, so the size is bounded by the window.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MissedBlocks {
+    // bit `h % JAIL_WINDOW_BLOCK_CNT` is set if height `h` was missed,
+    // empty if nothing was missed
+    bits: Vec<u8>,
+    cnt: u64,
+    // the latest height recorded
+    height: BlockHeight,
+}
+
+impl MissedBlocks {
+    /// Record whether the block at height `h` was missed,
+    /// the heights that slide out of the window are forgotten.
+    pub fn record(&mut self, h: BlockHeight, missed: bool) {
+        if self.height + JAIL_WINDOW_BLOCK_CNT <= h {
+            *self = MissedBlocks::default();
+        } else {
+            // the slots of the new heights are held by the ones out of the window
+            for x in (1 + self.height)..=h {
+                self.set(x, false);
+            }
+        }
+        self.height = cmp::max(self.height, h);
+
+        self.set(h, missed);
+    }
+
+    /// The number of blocks missed in the window.
+    #[inline(always)]
+    pub fn count(&self) -> u64 {
+        self.cnt
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn is_empty(&self) -> bool {
+        0 == self.cnt
+    }
+
+    fn set(&mut self, h: BlockHeight, missed: bool) {
+        let pos = (h % JAIL_WINDOW_BLOCK_CNT) as usize;
+        let (idx, mask) = (pos / 8, 1u8 << (pos % 8));
+        let was_missed = self.bits.get(idx).map_or(false, |b| 0 != b & mask);
+
+        if missed && !was_missed {
+            if self.bits.is_empty() {
+                self.bits = vec![0; JAIL_WINDOW_BLOCK_CNT.div_ceil(8) as usize];
+            }
+            self.bits[idx] |= mask;
+            self.cnt += 1;
+        } else if !missed && was_missed {
+            self.bits[idx] &= !mask;
+            self.cnt -= 1;
+            if 0 == self.cnt {
+                self.bits.clear();
+            }
+        }
+    }
+}
+
 impl Validator {
     #[allow(missing_docs)]
     pub fn new(
@@ -1936,6 +3087,12 @@ impl Validator {
             kind,
             signed_last_block: false,
             signed_cnt: 0,
+            missed_blocks: MissedBlocks::default(),
+            jailed_until: None,
+            tombstoned_at: None,
+            commission_policy: None,
+            pending_commission_rate: None,
+            operators: None,
             delegators: IndexMap::new(),
         })
     }
@@ -1970,15 +3127,113 @@ impl Validator {
         self.commission_rate
     }
 
+    /// The declared commission policy, or the default one.
+    #[inline(always)]
+    pub fn get_commission_policy(&self) -> CommissionPolicy {
+        self.commission_policy.unwrap_or(COMMISSION_POLICY_DEFAULT)
+    }
+
+    /// Check if the commission rate can be changed to `new` now.
+    pub fn check_commission_change(&self, new: [u64; 2]) -> Result<()> {
+        // only the memo is changed
+        if self.commission_rate == new {
+            return Ok(());
+        }
+        if self.pending_commission_rate.is_some() {
+            return Err(eg!("another commission rate change is pending"));
+        }
+        self.get_commission_policy()
+            .check_change(self.commission_rate, new)
+            .c(d!())
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn is_jailed(&self) -> bool {
+        self.jailed_until.is_some()
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn is_tombstoned(&self) -> bool {
+        self.tombstoned_at.is_some()
+    }
+
+    /// The vote power in the context of tendermint, zero if jailed or tombstoned.
+    #[inline(always)]
+    pub fn consensus_power(&self) -> Amount {
+        alt!(self.is_jailed() || self.is_tombstoned(), 0, self.td_power)
+    }
+
     #[inline(always)]
     #[allow(missing_docs)]
     pub fn staking_is_basic_valid(&self) -> bool {
         self.td_power == 0
+            && self.missed_blocks.is_empty()
+            && !self.is_jailed()
+            && !self.is_tombstoned()
+            && self.pending_commission_rate.is_none()
+            && self.commission_policy.map_or(true, |p| {
+                p.is_valid() && p.check_rate(self.commission_rate).is_ok()
+            })
+            && self
+                .operators
+                .as_ref()
+                .map_or(true, operator_rules_is_valid)
             && self.td_addr == td_pubkey_to_td_addr_bytes(&self.td_pubkey)
             && self.commission_rate[0] < self.commission_rate[1]
     }
 }
 
+/// The limits of the commission rate of a validator,
+/// declared when staking and can not be changed later.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CommissionPolicy {
+    /// the max commission rate
+    pub max_rate: [u64; 2],
+    /// the max change of the commission rate in one day
+    pub max_change_rate: [u64; 2],
+}
+
+impl CommissionPolicy {
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn is_valid(&self) -> bool {
+        let valid = |r: [u64; 2]| 0 < r[1] && r[0] <= r[1];
+        valid(self.max_rate) && valid(self.max_change_rate)
+    }
+
+    /// Check if a commission rate is not bigger than `max_rate`.
+    pub fn check_rate(&self, rate: [u64; 2]) -> Result<()> {
+        if 0 == rate[1] || rate[0] >= rate[1] {
+            return Err(eg!("invalid commission rate"));
+        }
+        if rate[0] as u128 * self.max_rate[1] as u128
+            > self.max_rate[0] as u128 * rate[1] as u128
+        {
+            return Err(eg!("commission rate exceeds the max rate"));
+        }
+        Ok(())
+    }
+
+    /// Check if the commission rate can be changed from `cur` to `new`.
+    pub fn check_change(&self, cur: [u64; 2], new: [u64; 2]) -> Result<()> {
+        self.check_rate(new).c(d!())?;
+
+        // |new - cur| = |n0 * c1 - c0 * n1| / (n1 * c1)
+        let a = new[0] as u128 * cur[1] as u128;
+        let b = cur[0] as u128 * new[1] as u128;
+        let diff = alt!(a > b, a - b, b - a);
+        let l = diff.checked_mul(self.max_change_rate[1] as u128);
+        let r = (new[1] as u128 * cur[1] as u128)
+            .checked_mul(self.max_change_rate[0] as u128);
+        match (l, r) {
+            (Some(l), Some(r)) if l <= r => Ok(()),
+            _ => Err(eg!("commission rate changes too much")),
+        }
+    }
+}
+
 /// FRA delegation, include:
 /// - user delegation
 /// - validator's self-delegation
@@ -2014,6 +3269,22 @@ pub struct Delegation {
     pub proposer_rwd_cnt: u64,
     /// how many times you get delegation rewards
     pub delegation_rwd_cnt: u64,
+    /// if set, the rewards will be moved into the bonded principal periodically
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_compound: Option<AutoCompound>,
+    /// the withdrawal address of a validator,
+    /// the rewards and the principal will be paid to it instead of id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reward_receiver: Option<XfrPublicKey>,
+}
+
+/// The settings of compounding the rewards of a delegation automatically.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AutoCompound {
+    /// compound the rewards every `interval` blocks
+    pub interval: u64,
+    /// the height of the next compounding
+    pub next_height: BlockHeight,
 }
 
 /// Detail of each reward entry.
@@ -2025,6 +3296,9 @@ pub struct DelegationRwdDetail {
     pub amount: Amount,
     /// total penalties
     pub penalty_amount: Amount,
+    /// rewards moved into the bonded principal
+    #[serde(default)]
+    pub compounded_amount: Amount,
     /// global reward rate
     pub return_rate: Option<[u128; 2]>,
     /// commission rate of the target validator
@@ -2148,6 +3422,7 @@ impl Delegation {
                         bond: self.amount(),
                         amount: n,
                         penalty_amount: 0,
+                        compounded_amount: 0,
                         return_rate: Some(return_rate),
                         commission_rate: Some(commission_rate),
                         global_delegation_percent: Some(global_delegation_percent),
@@ -2448,7 +3723,10 @@ pub fn deny_relative_inputs(x: &TransferAsset) -> Result<()> {
 #[cfg(test)]
 #[allow(missing_docs)]
 mod test {
-    use super::*;
+    use {
+        super::*,
+        crate::data_model::{gen_random_keypair, NoReplayToken},
+    };
 
     // **NOTE**
     //
@@ -2481,6 +3759,727 @@ mod test {
         });
     }
 
+    #[test]
+    fn staking_redelegation() {
+        let validators = gen_validators(7);
+        let v1 = validators[0].id;
+        let v2 = validators[1].id;
+        let v3 = validators[2].id;
+        let v4 = validators[3].id;
+        let v1_addr = td_addr_to_string(&validators[0].td_addr);
+        let v2_addr = td_addr_to_string(&validators[1].td_addr);
+        let v3_addr = td_addr_to_string(&validators[2].td_addr);
+        let v4_addr = td_addr_to_string(&validators[3].td_addr);
+
+        let mut staking = gen_staking_with_validators(&validators);
+        pnk!(staking.delegate(v1, &v1_addr, STAKING_VALIDATOR_MIN_POWER));
+        pnk!(staking.delegate(v2, &v2_addr, STAKING_VALIDATOR_MIN_POWER));
+        pnk!(staking.delegate(v3, &v3_addr, STAKING_VALIDATOR_MIN_POWER));
+        pnk!(staking.delegate(v4, &v4_addr, STAKING_VALIDATOR_MIN_POWER));
+
+        let delegator = gen_random_keypair().get_pk();
+        pnk!(staking.delegate(delegator, &v1_addr, 300 * FRA));
+
+        // same validator, self-delegation and excessive amount are rejected
+        assert!(staking
+            .redelegate(&delegator, &v1_addr, &v1_addr, FRA)
+            .is_err());
+        assert!(staking.redelegate(&v1, &v1_addr, &v2_addr, FRA).is_err());
+        assert!(staking
+            .redelegate(&delegator, &v1_addr, &v2_addr, 301 * FRA)
+            .is_err());
+
+        // jailed or tombstoned destinations are rejected
+        staking
+            .validator_get_current_mut_one_by_id(&v3)
+            .unwrap()
+            .jailed_until = Some(1 + JAIL_MIN_BLOCK_CNT);
+        staking
+            .validator_get_current_mut_one_by_id(&v4)
+            .unwrap()
+            .tombstoned_at = Some(1);
+        assert!(staking
+            .redelegate(&delegator, &v1_addr, &v3_addr, FRA)
+            .is_err());
+        assert!(staking
+            .redelegate(&delegator, &v1_addr, &v4_addr, FRA)
+            .is_err());
+
+        pnk!(staking.redelegate(&delegator, &v1_addr, &v2_addr, 100 * FRA));
+        let d = staking.delegation_get(&delegator).unwrap();
+        assert_eq!(d.validator_entry(&v1), Some(200 * FRA));
+        assert_eq!(d.validator_entry(&v2), Some(100 * FRA));
+        assert_eq!(
+            pnk!(staking.validator_get_power(&v1)),
+            STAKING_VALIDATOR_MIN_POWER + 200 * FRA
+        );
+        assert_eq!(
+            pnk!(staking.validator_get_power(&v2)),
+            STAKING_VALIDATOR_MIN_POWER + 100 * FRA
+        );
+        assert_eq!(
+            staking
+                .validator_get_current_one_by_id(&v2)
+                .unwrap()
+                .delegators[&delegator],
+            100 * FRA
+        );
+
+        // cooling down
+        assert!(staking
+            .redelegate(&delegator, &v2_addr, &v1_addr, 100 * FRA)
+            .is_err());
+
+        staking.set_custom_block_height(1 + REDELEGATION_COOLDOWN_BLOCK_CNT);
+        pnk!(staking.redelegate(&delegator, &v1_addr, &v2_addr, 200 * FRA));
+        let d = staking.delegation_get(&delegator).unwrap();
+        assert_eq!(d.validator_entry(&v1), None);
+        assert_eq!(d.validator_entry(&v2), Some(300 * FRA));
+        assert_eq!(
+            pnk!(staking.validator_get_power(&v1)),
+            STAKING_VALIDATOR_MIN_POWER
+        );
+        assert!(!staking
+            .validator_get_current_one_by_id(&v1)
+            .unwrap()
+            .delegators
+            .contains_key(&delegator));
+        assert_eq!(
+            staking.delegation_info_global_amount(),
+            4 * STAKING_VALIDATOR_MIN_POWER + 300 * FRA
+        );
+    }
+
+    #[test]
+    fn staking_params_update() {
+        let mut staking = Staking::new();
+        staking.set_custom_block_height(10);
+        assert_eq!(staking.params(), &*DEFAULT_STAKING_PARAMS);
+
+        let mut params = StakingParams::default();
+        params.unbond_block_cnt = 100;
+        assert!(staking.params_set_at_height(10, params.clone()).is_err());
+        pnk!(staking.params_set_at_height(20, params.clone()));
+        assert_eq!(staking.params_get_pending().len(), 1);
+
+        staking.set_custom_block_height(19);
+        assert_eq!(staking.params(), &*DEFAULT_STAKING_PARAMS);
+        staking.set_custom_block_height(20);
+        assert_eq!(staking.params().unbond_block_cnt, 100);
+        assert!(staking.params_get_pending().is_empty());
+
+        params.validator_min_power = 0;
+        assert!(staking.params_set_at_height(30, params).is_err());
+    }
+
+    #[test]
+    fn staking_proposal() {
+        let validators = gen_validators(7);
+        let voters = validators.iter().take(3).map(|v| v.id).collect::<Vec<_>>();
+        let v1_addr = td_addr_to_string(&validators[0].td_addr);
+
+        let mut staking = gen_staking_with_validators(&validators);
+        for v in voters.iter() {
+            let addr = pnk!(staking.validator_app_pk_to_td_addr(v));
+            pnk!(staking.delegate(*v, &addr, STAKING_VALIDATOR_MIN_POWER));
+        }
+        let delegator = gen_random_keypair().get_pk();
+        pnk!(staking.delegate(delegator, &v1_addr, 300 * FRA));
+
+        let proposer = gen_random_keypair().get_pk();
+        assert!(staking
+            .proposal_submit(
+                proposer,
+                "text".to_owned(),
+                String::new(),
+                ProposalContent::Text,
+                PROPOSAL_MIN_DEPOSIT - 1,
+            )
+            .is_err());
+        let id = pnk!(staking.proposal_submit(
+            proposer,
+            "text".to_owned(),
+            String::new(),
+            ProposalContent::Text,
+            PROPOSAL_MIN_DEPOSIT,
+        ));
+
+        // only bonded addresses can vote
+        assert!(staking
+            .proposal_vote(&proposer, id, VoteOption::Yes)
+            .is_err());
+        for v in voters.iter() {
+            pnk!(staking.proposal_vote(v, id, VoteOption::Yes));
+        }
+        // the delegator overrides the vote of its validator
+        pnk!(staking.proposal_vote(&delegator, id, VoteOption::No));
+
+        let power = voters
+            .iter()
+            .map(|v| pnk!(staking.validator_get_power(v)))
+            .sum::<Amount>();
+        let tally = staking.proposal_tally(pnk!(staking.proposal_get(id)));
+        assert_eq!(tally.yes, power - 300 * FRA);
+        assert_eq!(tally.no, 300 * FRA);
+        assert!(tally.is_quorum_reached() && tally.is_passed());
+
+        // votes are accepted until the end of the voting period
+        let end = pnk!(staking.proposal_get(id)).voting_end_height;
+        staking.set_custom_block_height(end);
+        staking.proposal_process();
+        assert_eq!(
+            pnk!(staking.proposal_get(id)).status,
+            ProposalStatus::Voting
+        );
+        pnk!(staking.proposal_vote(&voters[0], id, VoteOption::Yes));
+
+        staking.set_custom_block_height(end + 1);
+        staking.proposal_process();
+        let p = pnk!(staking.proposal_get(id));
+        assert_eq!(p.status, ProposalStatus::Passed);
+        assert!(p.deposit_refunded);
+        assert_eq!(
+            staking.fra_distribution_get_plan().get(&proposer),
+            Some(&PROPOSAL_MIN_DEPOSIT)
+        );
+
+        // voting period has ended
+        assert!(staking
+            .proposal_vote(&voters[0], id, VoteOption::No)
+            .is_err());
+
+        // vetoed proposals burn the deposit
+        let id = pnk!(staking.proposal_submit(
+            proposer,
+            "spend".to_owned(),
+            String::new(),
+            ProposalContent::TreasurySpend {
+                receiver: proposer,
+                amount: FRA,
+            },
+            PROPOSAL_MIN_DEPOSIT,
+        ));
+        for v in voters.iter() {
+            pnk!(staking.proposal_vote(v, id, VoteOption::NoWithVeto));
+        }
+        staking.set_custom_block_height(
+            1 + pnk!(staking.proposal_get(id)).voting_end_height,
+        );
+        staking.proposal_process();
+        let p = pnk!(staking.proposal_get(id));
+        assert_eq!(p.status, ProposalStatus::Rejected);
+        assert!(!p.deposit_refunded);
+        assert_eq!(
+            staking.fra_distribution_get_plan().get(&proposer),
+            Some(&PROPOSAL_MIN_DEPOSIT)
+        );
+    }
+
+    #[test]
+    fn staking_validator_jail() {
+        let validators = gen_validators(7);
+        let v1 = validators[0].id;
+        let v1_td_addr = validators[0].td_addr.clone();
+        let v1_addr = td_addr_to_string(&v1_td_addr);
+
+        let mut staking = gen_staking_with_validators(&validators);
+        pnk!(staking.delegate(v1, &v1_addr, STAKING_VALIDATOR_MIN_POWER));
+
+        // not jailed
+        assert!(staking.validator_unjail(&v1).is_err());
+
+        let offline = [&v1_td_addr].iter().copied().collect::<BTreeSet<_>>();
+        let mut h = 1;
+        for _ in 0..JAIL_MISSED_BLOCK_MAX {
+            staking.set_custom_block_height(h);
+            pnk!(staking.validator_handle_missed_blocks(&offline));
+            h += 1;
+        }
+        let v = staking.validator_get_current_one_by_id(&v1).unwrap();
+        assert!(!v.is_jailed());
+        assert_eq!(v.missed_blocks.count(), JAIL_MISSED_BLOCK_MAX);
+
+        // missed blocks out of the window are forgotten one by one
+        staking.set_custom_block_height(JAIL_WINDOW_BLOCK_CNT);
+        pnk!(staking.validator_handle_missed_blocks(&BTreeSet::new()));
+        let v = staking.validator_get_current_one_by_id(&v1).unwrap();
+        assert_eq!(v.missed_blocks.count(), JAIL_MISSED_BLOCK_MAX);
+        staking.set_custom_block_height(1 + JAIL_WINDOW_BLOCK_CNT);
+        pnk!(staking.validator_handle_missed_blocks(&BTreeSet::new()));
+        let v = staking.validator_get_current_one_by_id(&v1).unwrap();
+        assert_eq!(v.missed_blocks.count(), JAIL_MISSED_BLOCK_MAX - 1);
+
+        staking.set_custom_block_height(h + JAIL_WINDOW_BLOCK_CNT);
+        pnk!(staking.validator_handle_missed_blocks(&BTreeSet::new()));
+        let v = staking.validator_get_current_one_by_id(&v1).unwrap();
+        assert!(v.missed_blocks.is_empty());
+
+        h += JAIL_WINDOW_BLOCK_CNT;
+        for _ in 0..=JAIL_MISSED_BLOCK_MAX {
+            staking.set_custom_block_height(h);
+            pnk!(staking.validator_handle_missed_blocks(&offline));
+            h += 1;
+        }
+        let v = staking.validator_get_current_one_by_id(&v1).unwrap();
+        assert!(v.is_jailed());
+        assert_eq!(v.consensus_power(), 0);
+
+        // still in the jail period
+        assert!(staking.validator_unjail(&v1).is_err());
+
+        staking.set_custom_block_height(h - 1 + JAIL_MIN_BLOCK_CNT);
+        pnk!(staking.validator_unjail(&v1));
+        let v = staking.validator_get_current_one_by_id(&v1).unwrap();
+        assert!(!v.is_jailed());
+        assert_eq!(v.consensus_power(), v.td_power);
+    }
+
+    #[test]
+    fn staking_evidence_tombstone() {
+        let validators = gen_validators(7);
+        let v1 = validators[0].id;
+        let v1_addr = td_addr_to_string(&validators[0].td_addr);
+        let v2 = validators[1].id;
+        let v2_addr = td_addr_to_string(&validators[1].td_addr);
+        let delegator = gen_random_keypair().get_pk();
+        let late_delegator = gen_random_keypair().get_pk();
+
+        let mut staking = gen_staking_with_validators(&validators);
+        pnk!(staking.delegate(v1, &v1_addr, STAKING_VALIDATOR_MIN_POWER));
+        pnk!(staking.delegate(v2, &v2_addr, STAKING_VALIDATOR_MIN_POWER));
+        pnk!(staking.delegate(delegator, &v1_addr, 100 * FRA));
+        staking.set_custom_block_height(2);
+        staking.validator_apply_current();
+
+        // delegations after the infraction height will not be slashed
+        staking.set_custom_block_height(3);
+        pnk!(staking.delegate(late_delegator, &v1_addr, 100 * FRA));
+        staking.validator_apply_current();
+
+        pnk!(staking.governance_handle_evidence(
+            &v1_addr,
+            2,
+            ByzantineKind::DuplicateVote
+        ));
+        assert_eq!(
+            staking.delegation_get(&delegator).unwrap().amount(),
+            95 * FRA
+        );
+        assert_eq!(
+            staking.delegation_get(&late_delegator).unwrap().amount(),
+            100 * FRA
+        );
+        let v = staking.validator_get_current_one_by_id(&v1).unwrap();
+        assert_eq!(v.tombstoned_at, Some(3));
+        assert_eq!(v.consensus_power(), 0);
+
+        // duplicate evidences are ignored
+        assert!(staking
+            .governance_handle_evidence(&v1_addr, 2, ByzantineKind::DuplicateVote)
+            .is_err());
+
+        // a tombstoned validator will not be punished again
+        pnk!(staking.governance_handle_evidence(
+            &v1_addr,
+            3,
+            ByzantineKind::DuplicateVote
+        ));
+        assert_eq!(
+            staking.delegation_get(&delegator).unwrap().amount(),
+            95 * FRA
+        );
+
+        // and it will never come back
+        assert!(staking.delegate(late_delegator, &v1_addr, FRA).is_err());
+        assert!(staking.validator_unjail(&v1).is_err());
+
+        // not even after it has been removed from the validator set
+        let mut vd = staking.validator_get_current().unwrap().clone();
+        let v = vd.body.remove(&v1).unwrap();
+        vd.addr_td_to_app.remove(&v1_addr);
+        pnk!(staking.validator_set_at_height(4, vd));
+        staking.set_custom_block_height(4);
+        let revived = pnk!(Validator::new(
+            v.td_pubkey,
+            STAKING_VALIDATOR_MIN_POWER,
+            gen_random_keypair().get_pk(),
+            [1, 10],
+            StakerMemo::default(),
+            ValidatorKind::Initiator,
+        ));
+        assert!(staking.validator_add_staker(4, revived).is_err());
+        assert!(staking.delegate(late_delegator, &v1_addr, FRA).is_err());
+
+        // evidences are slashed from the bonded amounts at the infraction height,
+        // as long as it is within the tracked history
+        (5..10).for_each(|h| {
+            staking.set_custom_block_height(h);
+            staking.validator_apply_current();
+        });
+        assert!(staking
+            .governance_handle_evidence(&v2_addr, 1, ByzantineKind::DuplicateVote)
+            .is_err());
+        assert!(!staking
+            .validator_get_current_one_by_id(&v2)
+            .unwrap()
+            .is_tombstoned());
+        pnk!(staking.governance_handle_evidence(
+            &v2_addr,
+            2,
+            ByzantineKind::DuplicateVote
+        ));
+        assert!(staking
+            .validator_get_current_one_by_id(&v2)
+            .unwrap()
+            .is_tombstoned());
+        assert!(
+            staking.delegation_get(&v2).unwrap().amount() < STAKING_VALIDATOR_MIN_POWER
+        );
+
+        staking.set_custom_block_height(3 + EVIDENCE_MAX_AGE_BLOCK_CNT);
+        assert!(staking
+            .governance_handle_evidence(&v1_addr, 2, ByzantineKind::LightClientAttack)
+            .is_err());
+    }
+
+    #[test]
+    fn staking_auto_compound() {
+        let validators = gen_validators(7);
+        let v1 = validators[0].id;
+        let v1_addr = td_addr_to_string(&validators[0].td_addr);
+        let delegator = gen_random_keypair().get_pk();
+
+        let mut staking = gen_staking_with_validators(&validators);
+        pnk!(staking.delegate(v1, &v1_addr, STAKING_VALIDATOR_MIN_POWER));
+        pnk!(staking.delegate(delegator, &v1_addr, 100 * FRA));
+
+        assert!(staking
+            .delegation_set_auto_compound(
+                &delegator,
+                Some(AUTO_COMPOUND_MIN_BLOCK_CNT - 1)
+            )
+            .is_err());
+        pnk!(staking.delegation_set_auto_compound(
+            &delegator,
+            Some(AUTO_COMPOUND_MIN_BLOCK_CNT)
+        ));
+
+        staking.delegation_get_mut(&delegator).unwrap().rwd_amount = 10 * FRA;
+        staking.coinbase.balance = 100 * FRA;
+        let power = pnk!(staking.validator_get_power(&v1));
+        let global_amount = staking.get_global_delegation_amount();
+
+        // the interval has not arrived
+        staking.set_custom_block_height(AUTO_COMPOUND_MIN_BLOCK_CNT);
+        staking.delegation_auto_compound();
+        assert_eq!(
+            pnk!(staking.delegation_get_principal(&delegator)),
+            100 * FRA
+        );
+
+        staking.set_custom_block_height(1 + AUTO_COMPOUND_MIN_BLOCK_CNT);
+        staking.delegation_auto_compound();
+        let d = staking.delegation_get(&delegator).unwrap();
+        assert_eq!(d.amount(), 110 * FRA);
+        assert_eq!(d.rwd_amount, 0);
+        assert_eq!(
+            d.auto_compound.unwrap().next_height,
+            1 + 2 * AUTO_COMPOUND_MIN_BLOCK_CNT
+        );
+        assert_eq!(staking.coinbase_balance(), 90 * FRA);
+        assert_eq!(pnk!(staking.validator_get_power(&v1)), power + 10 * FRA);
+        assert_eq!(
+            staking.get_global_delegation_amount(),
+            global_amount + 10 * FRA
+        );
+        assert_eq!(
+            staking
+                .validator_get_current_one_by_id(&v1)
+                .unwrap()
+                .delegators
+                .get(&delegator),
+            Some(&(110 * FRA))
+        );
+
+        pnk!(staking.delegation_set_auto_compound(&delegator, None));
+        assert!(staking
+            .delegation_get(&delegator)
+            .unwrap()
+            .auto_compound
+            .is_none());
+
+        // turned off once the delegation leaves the bonded state
+        pnk!(staking.delegation_set_auto_compound(
+            &delegator,
+            Some(AUTO_COMPOUND_MIN_BLOCK_CNT)
+        ));
+        pnk!(staking.undelegate(&delegator, None));
+        assert!(staking
+            .delegation_get(&delegator)
+            .unwrap()
+            .auto_compound
+            .is_none());
+
+        // even if it is left behind by other paths
+        let d = staking.delegation_get_mut(&delegator).unwrap();
+        d.auto_compound = Some(AutoCompound {
+            interval: AUTO_COMPOUND_MIN_BLOCK_CNT,
+            next_height: 1 + AUTO_COMPOUND_MIN_BLOCK_CNT,
+        });
+        d.rwd_amount = 10 * FRA;
+        staking.delegation_auto_compound();
+        let d = staking.delegation_get(&delegator).unwrap();
+        assert!(d.auto_compound.is_none());
+        assert_eq!(d.rwd_amount, 10 * FRA);
+    }
+
+    #[test]
+    fn staking_reward_receiver() {
+        let validators = gen_validators(7);
+        let v1 = validators[0].id;
+        let v1_td_addr = validators[0].td_addr.clone();
+        let v1_addr = td_addr_to_string(&v1_td_addr);
+        let delegator = gen_random_keypair().get_pk();
+        let receiver = gen_random_keypair().get_pk();
+
+        let mut staking = gen_staking_with_validators(&validators);
+        pnk!(staking.delegate(v1, &v1_addr, 2 * STAKING_VALIDATOR_MIN_POWER));
+        pnk!(staking.delegate(delegator, &v1_addr, 100 * FRA));
+
+        // only validators can set a reward receiver
+        assert!(staking
+            .validator_set_reward_receiver(&delegator, Some(receiver))
+            .is_err());
+        assert!(staking.delegation_get_reward_receiver(&delegator).is_none());
+
+        pnk!(staking.validator_set_reward_receiver(&v1, Some(receiver)));
+        assert_eq!(staking.delegation_get_reward_receiver(&v1), Some(receiver));
+
+        // the pending partial undelegations follow the validator
+        let id = gen_random_keypair().get_pk();
+        let pu = PartialUnDelegation::new(STAKING_VALIDATOR_MIN_POWER, id, v1_td_addr);
+        pnk!(staking.undelegate(&v1, Some(&pu)));
+        assert_eq!(staking.delegation_get_reward_receiver(&id), Some(receiver));
+
+        let receiver = gen_random_keypair().get_pk();
+        pnk!(staking.validator_set_reward_receiver(&v1, Some(receiver)));
+        assert_eq!(staking.delegation_get_reward_receiver(&v1), Some(receiver));
+        assert_eq!(staking.delegation_get_reward_receiver(&id), Some(receiver));
+
+        // paying to the validator key itself means no receiver
+        pnk!(staking.validator_set_reward_receiver(&v1, Some(v1)));
+        assert!(staking.delegation_get_reward_receiver(&v1).is_none());
+        assert!(staking.delegation_get_reward_receiver(&id).is_none());
+    }
+
+    #[test]
+    fn staking_validator_operators() {
+        let operators = (0..3).map(|_| gen_random_keypair()).collect::<Vec<_>>();
+        let rules = SignatureRules {
+            threshold: 2,
+            weights: operators.iter().map(|kp| (kp.get_pk(), 1)).collect(),
+        };
+
+        let mut v = pnk!(Validator::new_staker(
+            random::<[u8; 32]>().to_vec(),
+            gen_random_keypair().get_pk(),
+            [1, 10],
+            StakerMemo::default(),
+        ));
+        v.operators = Some(rules.clone());
+        assert!(v.staking_is_basic_valid());
+        v.operators = Some(SignatureRules {
+            threshold: 4,
+            weights: rules.weights.clone(),
+        });
+        assert!(!v.staking_is_basic_valid());
+        v.operators = Some(SignatureRules {
+            threshold: 1,
+            weights: vec![rules.weights[0]; 2],
+        });
+        assert!(!v.staking_is_basic_valid());
+
+        let mut validators = gen_validators(7);
+        let v1_kp = gen_random_keypair();
+        validators[0].id = v1_kp.get_pk();
+        validators[0].operators = Some(rules);
+        let v1 = validators[0].id;
+        let v1_addr = td_addr_to_string(&validators[0].td_addr);
+        let v2 = validators[1].id;
+
+        let mut staking = gen_staking_with_validators(&validators);
+
+        let msg = b"operation body";
+        let mut sigs = OperatorCoSigs::default();
+        assert!(staking.validator_check_operator_sigs(&v1, &sigs).is_err());
+        sigs.sign(&operators[0], msg);
+        assert!(staking.validator_check_operator_sigs(&v1, &sigs).is_err());
+        sigs.sign(&operators[1], msg);
+        pnk!(sigs.verify(msg));
+        assert!(sigs.verify(b"another body").is_err());
+        pnk!(staking.validator_check_operator_sigs(&v1, &sigs));
+
+        // keys outside of the rules are not allowed
+        let mut sigs_x = sigs.clone();
+        sigs_x.sign(&gen_random_keypair(), msg);
+        assert!(staking.validator_check_operator_sigs(&v1, &sigs_x).is_err());
+
+        // validators without operators are controlled by their own keys
+        pnk!(staking.validator_check_operator_sigs(&v2, &OperatorCoSigs::default()));
+        assert!(staking.validator_check_operator_sigs(&v2, &sigs).is_err());
+
+        // so are the operations signed by the key of a multisig-controlled validator
+        pnk!(staking.delegate(v1, &v1_addr, STAKING_VALIDATOR_MIN_POWER));
+        let mut op = ops::replace_staker::ReplaceStakerOps::new(
+            &v1_kp,
+            gen_random_keypair().get_pk(),
+            None,
+            NoReplayToken::unsafe_new(1, 1),
+        );
+        let tx = Transaction::from_operation(Operation::ReplaceStaker(op.clone()), 1);
+        assert!(op.check_run(&mut staking.clone(), &tx).is_err());
+        op.sign_as_operator(&operators[0]);
+        assert!(op.check_run(&mut staking.clone(), &tx).is_err());
+        op.sign_as_operator(&operators[1]);
+        pnk!(op.check_run(&mut staking.clone(), &tx));
+    }
+
+    #[test]
+    fn staking_cancel_unbonding() {
+        let validators = gen_validators(7);
+        let v1 = validators[0].id;
+        let v1_td_addr = validators[0].td_addr.clone();
+        let v1_addr = td_addr_to_string(&v1_td_addr);
+        let delegator = gen_random_keypair().get_pk();
+
+        let mut staking = gen_staking_with_validators(&validators);
+        pnk!(staking.delegate(v1, &v1_addr, STAKING_VALIDATOR_MIN_POWER));
+        pnk!(staking.delegate(delegator, &v1_addr, 100 * FRA));
+        let power = pnk!(staking.validator_get_power(&v1));
+
+        let id = gen_random_keypair().get_pk();
+        let pu = PartialUnDelegation::new(40 * FRA, id, v1_td_addr.clone());
+        pnk!(staking.undelegate(&delegator, Some(&pu)));
+
+        let entries = staking.delegation_get_unbonding_entries(&delegator);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0, id);
+        assert_eq!(entries[0].1.amount(), 40 * FRA);
+        assert_eq!(
+            entries[0].1.end_height(),
+            1 + staking.params().unbond_block_cnt
+        );
+
+        // only the original delegator can cancel it
+        assert!(staking.cancel_unbonding(&v1, &id).is_err());
+
+        pnk!(staking.cancel_unbonding(&delegator, &id));
+        assert!(staking
+            .delegation_get_unbonding_entries(&delegator)
+            .is_empty());
+        assert!(staking.delegation_get(&id).is_none());
+        assert_eq!(
+            pnk!(staking.delegation_get_principal(&delegator)),
+            100 * FRA
+        );
+        assert_eq!(pnk!(staking.validator_get_power(&v1)), power);
+        let v = staking.validator_get_current_one_by_id(&v1).unwrap();
+        assert_eq!(v.delegators.get(&delegator), Some(&(100 * FRA)));
+        assert!(v.delegators.get(&id).is_none());
+
+        // matured unbondings can not be cancelled
+        let id = gen_random_keypair().get_pk();
+        let pu = PartialUnDelegation::new(40 * FRA, id, v1_td_addr);
+        pnk!(staking.undelegate(&delegator, Some(&pu)));
+        staking.set_custom_block_height(1 + staking.params().unbond_block_cnt);
+        assert!(staking.cancel_unbonding(&delegator, &id).is_err());
+    }
+
+    #[test]
+    fn staking_commission_policy() {
+        let mut validators = gen_validators(7);
+        validators[0].commission_rate = [10, 100];
+        validators[1].commission_rate = [10, 100];
+        let policy = CommissionPolicy {
+            max_rate: [20, 100],
+            max_change_rate: [5, 100],
+        };
+        validators[0].commission_policy = Some(policy);
+        let v1 = validators[0].id;
+        let v2 = validators[1].id;
+
+        let mut staking = gen_staking_with_validators(&validators);
+
+        let mut new = staking
+            .validator_get_current_one_by_id(&v1)
+            .unwrap()
+            .clone();
+        let v = staking.validator_get_current_one_by_id(&v1).unwrap();
+        // exceeds the max rate
+        assert!(v.check_commission_change([21, 100]).is_err());
+        // changes too much
+        assert!(v.check_commission_change([16, 100]).is_err());
+        pnk!(v.check_commission_change([15, 100]));
+        pnk!(v.check_commission_change([5, 100]));
+
+        // the default policy does not cap the rate
+        let v = staking.validator_get_current_one_by_id(&v2).unwrap();
+        pnk!(v.check_commission_change([15, 100]));
+        assert!(v.check_commission_change([16, 100]).is_err());
+
+        new.commission_rate = [15, 100];
+        new.memo.name = "v1".to_owned();
+        pnk!(staking.update_staker_delayed(&new));
+        let v = staking.validator_get_current_one_by_id(&v1).unwrap();
+        assert_eq!(v.memo.name, "v1");
+        assert_eq!(v.get_commission_rate(), [10, 100]);
+        assert_eq!(
+            v.pending_commission_rate,
+            Some((1 + COMMISSION_CHANGE_DELAY_BLOCK_CNT, [15, 100]))
+        );
+        // only one change at a time
+        assert!(v.check_commission_change([12, 100]).is_err());
+
+        staking.set_custom_block_height(COMMISSION_CHANGE_DELAY_BLOCK_CNT);
+        staking.validator_apply_commission_changes();
+        let v = staking.validator_get_current_one_by_id(&v1).unwrap();
+        assert_eq!(v.get_commission_rate(), [10, 100]);
+
+        staking.set_custom_block_height(1 + COMMISSION_CHANGE_DELAY_BLOCK_CNT);
+        staking.validator_apply_commission_changes();
+        let v = staking.validator_get_current_one_by_id(&v1).unwrap();
+        assert_eq!(v.get_commission_rate(), [15, 100]);
+        assert!(v.pending_commission_rate.is_none());
+    }
+
+    // `n` validators with the minimum power
+    fn gen_validators(n: usize) -> Vec<Validator> {
+        (0..n)
+            .map(|_| {
+                let td_pubkey = random::<[u8; 32]>().to_vec();
+                pnk!(Validator::new(
+                    td_pubkey,
+                    STAKING_VALIDATOR_MIN_POWER,
+                    gen_random_keypair().get_pk(),
+                    [1, 10],
+                    StakerMemo::default(),
+                    ValidatorKind::Initiator,
+                ))
+            })
+            .collect()
+    }
+
+    // a staking state with `validators` set at height 1
+    fn gen_staking_with_validators(validators: &[Validator]) -> Staking {
+        let mut staking = Staking::new();
+        staking.set_custom_block_height(1);
+        pnk!(staking.validator_set_at_height(
+            1,
+            pnk!(ValidatorData::new(1, validators.to_vec()))
+        ));
+        staking
+    }
+
     fn gen_round_vote_percent(lower_bound: u64, upper_bound: u64) -> [u64; 2] {
         let itv = upper_bound - lower_bound;
         let lb = if 0 == itv {
@@ -5,7 +5,10 @@
 //!
 
 use {
-    crate::{data_model::NoReplayToken, staking::Staking},
+    crate::{
+        data_model::NoReplayToken,
+        staking::{cosig::OperatorCoSigs, Staking},
+    },
     ruc::*,
     serde::{Deserialize, Serialize},
     zei::xfr::sig::{XfrKeyPair, XfrPublicKey, XfrSignature},
@@ -17,6 +20,8 @@ pub struct ClaimOps {
     pub(crate) body: Data,
     pub(crate) pubkey: XfrPublicKey,
     signature: XfrSignature,
+    #[serde(default, skip_serializing_if = "OperatorCoSigs::is_empty")]
+    operator_sigs: OperatorCoSigs,
 }
 
 impl ClaimOps {
@@ -30,6 +35,11 @@ impl ClaimOps {
     pub fn apply(&self, staking: &mut Staking) -> Result<()> {
         self.verify()
             .c(d!())
+            .and_then(|_| {
+                staking
+                    .validator_check_operator_sigs(&self.pubkey, &self.operator_sigs)
+                    .c(d!())
+            })
             .and_then(|_| staking.claim(self.pubkey, self.body.amount).c(d!()))
     }
 
@@ -39,6 +49,13 @@ impl ClaimOps {
         self.pubkey
             .verify(&self.body.to_bytes(), &self.signature)
             .c(d!())
+            .and_then(|_| self.operator_sigs.verify(&self.body.to_bytes()).c(d!()))
+    }
+
+    /// Attach a signature of an operator of the multisig-controlled validator.
+    #[inline(always)]
+    pub fn sign_as_operator(&mut self, keypair: &XfrKeyPair) {
+        self.operator_sigs.sign(keypair, &self.body.to_bytes());
     }
 
     #[inline(always)]
@@ -62,6 +79,7 @@ impl ClaimOps {
             body,
             pubkey: keypair.get_pk(),
             signature,
+            operator_sigs: OperatorCoSigs::default(),
         }
     }
 
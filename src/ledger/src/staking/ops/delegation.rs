@@ -11,10 +11,11 @@ use {
             BLACK_HOLE_PUBKEY_STAKING,
         },
         staking::{
-            deny_relative_inputs, td_addr_to_string, Amount, Staking, TendermintAddr,
-            Validator, STAKING_VALIDATOR_MIN_POWER,
+            cosig::OperatorCoSigs, deny_relative_inputs, td_addr_to_string, Amount,
+            Staking, TendermintAddr, Validator,
         },
     },
+    config::abci::global_cfg::CFG,
     ed25519_dalek::Signer,
     ruc::*,
     serde::{Deserialize, Serialize},
@@ -33,6 +34,8 @@ pub struct DelegationOps {
     pub(crate) pubkey: XfrPublicKey,
     signature: XfrSignature,
     v_signature: Option<Ed25519Signature>,
+    #[serde(default, skip_serializing_if = "OperatorCoSigs::is_empty")]
+    operator_sigs: OperatorCoSigs,
 }
 
 impl DelegationOps {
@@ -78,6 +81,13 @@ impl DelegationOps {
         self.pubkey
             .verify(&self.body.to_bytes(), &self.signature)
             .c(d!())
+            .and_then(|_| self.operator_sigs.verify(&self.body.to_bytes()).c(d!()))
+    }
+
+    /// Attach a signature of an operator of the multisig-controlled validator.
+    #[inline(always)]
+    pub fn sign_as_operator(&mut self, keypair: &XfrKeyPair) {
+        self.operator_sigs.sign(keypair, &self.body.to_bytes());
     }
 
     #[inline(always)]
@@ -93,16 +103,36 @@ impl DelegationOps {
             let h = staking.cur_height;
 
             if !v.staking_is_basic_valid()
-                || am < STAKING_VALIDATOR_MIN_POWER
+                || am < staking.params().validator_min_power
                 || self.body.validator != td_addr_to_string(&v.td_addr)
             {
                 return Err(eg!("invalid"));
             }
 
+            if v.commission_policy.is_some()
+                && (h as i64) < CFG.checkpoint.enable_commission_policy_height
+            {
+                return Err(eg!("commission policy is not enabled"));
+            }
+
+            // the operators must agree to take control of the new validator
+            if let Some(rules) = v.operators.as_ref() {
+                if (h as i64) < CFG.checkpoint.enable_validator_multisig_height {
+                    return Err(eg!("validator multisig is not enabled"));
+                }
+                self.operator_sigs.check_rules(rules).c(d!())?;
+            } else if !self.operator_sigs.is_empty() {
+                return Err(eg!("not a multisig-controlled validator"));
+            }
+
             staking
                 .validator_check_power_x(am, 0)
                 .c(d!())
                 .and_then(|_| staking.validator_add_staker(h, v.clone()).c(d!()))?;
+        } else {
+            staking
+                .validator_check_operator_sigs(&self.pubkey, &self.operator_sigs)
+                .c(d!())?;
         }
 
         Ok(am)
@@ -133,6 +163,7 @@ impl DelegationOps {
             pubkey: keypair.get_pk(),
             signature,
             v_signature,
+            operator_sigs: OperatorCoSigs::default(),
         }
     }
 
@@ -216,7 +247,9 @@ fn check_delegation_context(tx: &Transaction) -> Result<Amount> {
         .c(d!("delegation amount is not paid correctly"))
 }
 
-fn check_delegation_context_principal(
+// the FRA paid to `BLACK_HOLE_PUBKEY_STAKING` by the owner,
+// also used to check the deposit of a proposal
+pub(crate) fn check_delegation_context_principal(
     tx: &Transaction,
     owner: (XfrPublicKey, Amount),
 ) -> Result<Amount> {
@@ -12,28 +12,12 @@ use {
         data_model::NoReplayToken,
         staking::{cosig::CoSigOp, Staking, TendermintAddrRef, BLOCK_HEIGHT_MAX},
     },
-    lazy_static::lazy_static,
     ruc::*,
     serde::{Deserialize, Serialize},
     std::collections::BTreeMap,
     zei::xfr::sig::{XfrKeyPair, XfrPublicKey},
 };
 
-lazy_static! {
-    // The current MVP version is a fixed rule,
-    // and it will be upgraded to a mechanism
-    // that can update rules by sending a specific transaction.
-    static ref RULES: BTreeMap<ByzantineKind, Rule> = {
-        map! { B
-            ByzantineKind::DuplicateVote => Rule::new([5, 100]),
-            ByzantineKind::LightClientAttack => Rule::new([1, 100]),
-            ByzantineKind::Unknown => Rule::new([30, 100]),
-            // we should set this percent to a very small value
-            ByzantineKind::OffLine => Rule::new([1, 1000_0000]),
-        }
-    };
-}
-
 /// Used as the inner object of a `Governance Operation`.
 pub type GovernanceOps = CoSigOp<Data>;
 
@@ -48,14 +32,19 @@ impl GovernanceOps {
     pub fn apply(&self, staking: &mut Staking) -> Result<()> {
         self.verify(staking)
             .c(d!())
-            .and_then(|_| RULES.get(&self.data.kind).ok_or(eg!()))
-            .and_then(|rule| {
+            .and_then(|_| {
+                staking
+                    .params()
+                    .penalty_rules
+                    .get(&self.data.kind)
+                    .map(|rule| rule.gen_penalty_percent())
+                    .ok_or(eg!())
+            })
+            .and_then(|percent| {
                 staking
                     .governance_penalty_by_pubkey(
                         &self.data.byzantine_id,
-                        self.data
-                            .custom_percent
-                            .unwrap_or_else(|| rule.gen_penalty_percent()),
+                        self.data.custom_percent.unwrap_or(percent),
                     )
                     .c(d!())
             })
@@ -139,7 +128,9 @@ pub struct Rule {
 }
 
 impl Rule {
-    fn new(penalty_percent: [u64; 2]) -> Self {
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn new(penalty_percent: [u64; 2]) -> Self {
         Rule { penalty_percent }
     }
 
@@ -160,7 +151,12 @@ pub fn governance_penalty_tendermint_auto(
     addr: TendermintAddrRef,
     bz_kind: &ByzantineKind,
 ) -> Result<()> {
-    let rule = RULES.get(bz_kind).ok_or(eg!())?;
+    let percent = staking
+        .params()
+        .penalty_rules
+        .get(bz_kind)
+        .map(|rule| rule.gen_penalty_percent())
+        .ok_or(eg!())?;
     staking
         .validator_td_addr_to_app_pk(addr)
         .c(d!())
@@ -173,9 +169,7 @@ pub fn governance_penalty_tendermint_auto(
                     if BLOCK_HEIGHT_MAX != h {
                         return Ok(());
                     }
-                    staking
-                        .governance_penalty_by_pubkey(&pk, rule.gen_penalty_percent())
-                        .c(d!())
+                    staking.governance_penalty_by_pubkey(&pk, percent).c(d!())
                 })
         })
 }
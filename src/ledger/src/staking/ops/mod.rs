@@ -7,12 +7,20 @@
 //! In the current implementation, the first operation must be a `TransferAsset`.
 //!
 
+pub mod auto_compound;
+pub mod cancel_unbonding;
 pub mod claim;
 pub mod delegation;
 pub mod fra_distribution;
 pub mod governance;
 pub mod mint_fra;
+pub mod proposal;
+pub mod redelegation;
 pub mod replace_staker;
+pub mod reward_receiver;
 pub mod undelegation;
+pub mod unjail;
+pub mod update_params;
 pub mod update_staker;
 pub mod update_validator;
+pub mod vote;
@@ -0,0 +1,150 @@
+//!
+//! # Proposal Operation
+//!
+//! Data representation required when users submit a governance proposal,
+//! the deposit is paid by a `TransferAsset` to `BLACK_HOLE_PUBKEY_STAKING`
+//! in the same transaction.
+//!
+
+use {
+    crate::{
+        data_model::{NoReplayToken, Operation, Transaction},
+        staking::{
+            ops::delegation::check_delegation_context_principal,
+            proposal::ProposalContent, Amount, Staking,
+        },
+    },
+    config::abci::global_cfg::CFG,
+    ruc::*,
+    serde::{Deserialize, Serialize},
+    zei::xfr::sig::{XfrKeyPair, XfrPublicKey, XfrSignature},
+};
+
+/// Used as the inner object of a `Proposal Operation`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProposalOps {
+    body: Box<Data>,
+    pub(crate) pubkey: XfrPublicKey,
+    signature: XfrSignature,
+}
+
+impl ProposalOps {
+    /// Check the validity of an operation by running it in a staking simulator.
+    #[inline(always)]
+    pub fn check_run(
+        &self,
+        staking_simulator: &mut Staking,
+        tx: &Transaction,
+    ) -> Result<()> {
+        self.apply(staking_simulator, tx).c(d!())
+    }
+
+    /// Add the new proposal to the target `Staking` instance.
+    pub fn apply(&self, staking: &mut Staking, tx: &Transaction) -> Result<()> {
+        if (staking.cur_height() as i64) < CFG.checkpoint.enable_proposal_height {
+            return Err(eg!("governance proposal is not enabled"));
+        }
+
+        self.verify()
+            .c(d!())
+            .and_then(|_| self.check_context(tx).c(d!()))
+            .and_then(|_| {
+                staking
+                    .proposal_submit(
+                        self.pubkey,
+                        self.body.title.clone(),
+                        self.body.description.clone(),
+                        self.body.content.clone(),
+                        self.body.deposit,
+                    )
+                    .c(d!())
+            })
+            .map(|_| ())
+    }
+
+    /// Verify signature.
+    #[inline(always)]
+    pub fn verify(&self) -> Result<()> {
+        self.pubkey
+            .verify(&self.body.to_bytes(), &self.signature)
+            .c(d!())
+    }
+
+    // Only one proposal is allowed in a transaction,
+    // and it can not be mixed with a delegation.
+    fn check_context(&self, tx: &Transaction) -> Result<()> {
+        let cnt = tx
+            .body
+            .operations
+            .iter()
+            .filter(|op| matches!(op, Operation::Proposal(_) | Operation::Delegation(_)))
+            .count();
+        if 1 != cnt {
+            return Err(eg!());
+        }
+
+        check_delegation_context_principal(tx, (self.pubkey, self.body.deposit))
+            .c(d!("deposit is not paid correctly"))
+            .map(|_| ())
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn get_related_pubkeys(&self) -> Vec<XfrPublicKey> {
+        vec![self.pubkey]
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn new(
+        keypair: &XfrKeyPair,
+        title: String,
+        description: String,
+        content: ProposalContent,
+        deposit: Amount,
+        nonce: NoReplayToken,
+    ) -> Self {
+        let body = Box::new(Data {
+            title,
+            description,
+            content,
+            deposit,
+            nonce,
+        });
+        let signature = keypair.sign(&body.to_bytes());
+        ProposalOps {
+            body,
+            pubkey: keypair.get_pk(),
+            signature,
+        }
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn set_nonce(&mut self, nonce: NoReplayToken) {
+        self.body.nonce = nonce;
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn get_nonce(&self) -> NoReplayToken {
+        self.body.nonce
+    }
+}
+
+// The body of a proposal operation.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct Data {
+    title: String,
+    description: String,
+    content: ProposalContent,
+    deposit: Amount,
+    nonce: NoReplayToken,
+}
+
+impl Data {
+    #[inline(always)]
+    fn to_bytes(&self) -> Vec<u8> {
+        pnk!(bincode::serialize(self))
+    }
+}
@@ -0,0 +1,180 @@
+//!
+//! # Redelegation Operation
+//!
+//! Data representation required when users move a bonded amount
+//! from a validator to another one.
+//!
+
+use {
+    crate::{
+        data_model::{NoReplayToken, Operation, Transaction},
+        staking::{cosig::OperatorCoSigs, Amount, Staking, TendermintAddr},
+    },
+    config::abci::global_cfg::CFG,
+    ruc::*,
+    serde::{Deserialize, Serialize},
+    zei::xfr::sig::{XfrKeyPair, XfrPublicKey, XfrSignature},
+};
+
+/// Used as the inner object of a `Redelegation Operation`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RedelegationOps {
+    body: Data,
+    pub(crate) pubkey: XfrPublicKey,
+    signature: XfrSignature,
+    #[serde(default, skip_serializing_if = "OperatorCoSigs::is_empty")]
+    operator_sigs: OperatorCoSigs,
+}
+
+impl RedelegationOps {
+    /// Check the validity of an operation by running it in a staking simulator.
+    #[inline(always)]
+    pub fn check_run(
+        &self,
+        staking_simulator: &mut Staking,
+        tx: &Transaction,
+    ) -> Result<()> {
+        self.apply(staking_simulator, tx).c(d!())
+    }
+
+    /// Apply new redelegation to the target `Staking` instance.
+    pub fn apply(&self, staking: &mut Staking, tx: &Transaction) -> Result<()> {
+        if (staking.cur_height() as i64) < CFG.checkpoint.enable_redelegation_height {
+            return Err(eg!("redelegation is not enabled"));
+        }
+
+        self.verify()
+            .c(d!())
+            .and_then(|_| Self::check_context(tx).c(d!()))
+            .and_then(|_| {
+                staking
+                    .validator_check_operator_sigs(&self.pubkey, &self.operator_sigs)
+                    .c(d!())
+            })
+            .and_then(|_| {
+                staking
+                    .redelegate(
+                        &self.pubkey,
+                        &self.body.src_validator,
+                        &self.body.dst_validator,
+                        self.body.amount,
+                    )
+                    .c(d!())
+            })
+    }
+
+    /// Verify signature.
+    #[inline(always)]
+    pub fn verify(&self) -> Result<()> {
+        self.pubkey
+            .verify(&self.body.to_bytes(), &self.signature)
+            .c(d!())
+            .and_then(|_| self.operator_sigs.verify(&self.body.to_bytes()).c(d!()))
+    }
+
+    /// Attach a signature of an operator of the multisig-controlled validator.
+    #[inline(always)]
+    pub fn sign_as_operator(&mut self, keypair: &XfrKeyPair) {
+        self.operator_sigs.sign(keypair, &self.body.to_bytes());
+    }
+
+    #[inline(always)]
+    fn check_context(tx: &Transaction) -> Result<()> {
+        check_redelegation_context(tx).c(d!())
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn get_related_pubkeys(&self) -> Vec<XfrPublicKey> {
+        vec![self.pubkey]
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn new(
+        keypair: &XfrKeyPair,
+        nonce: NoReplayToken,
+        src_validator: TendermintAddr,
+        dst_validator: TendermintAddr,
+        amount: Amount,
+    ) -> Self {
+        let body = Data::new(nonce, src_validator, dst_validator, amount);
+        let signature = keypair.sign(&body.to_bytes());
+        RedelegationOps {
+            body,
+            pubkey: keypair.get_pk(),
+            signature,
+            operator_sigs: OperatorCoSigs::default(),
+        }
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn set_nonce(&mut self, nonce: NoReplayToken) {
+        self.body.set_nonce(nonce);
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn get_nonce(&self) -> NoReplayToken {
+        self.body.get_nonce()
+    }
+}
+
+// The body of a redelegation operation.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct Data {
+    src_validator: TendermintAddr,
+    dst_validator: TendermintAddr,
+    amount: Amount,
+    nonce: NoReplayToken,
+}
+
+impl Data {
+    #[inline(always)]
+    fn new(
+        nonce: NoReplayToken,
+        src_validator: TendermintAddr,
+        dst_validator: TendermintAddr,
+        amount: Amount,
+    ) -> Self {
+        Data {
+            src_validator,
+            dst_validator,
+            amount,
+            nonce,
+        }
+    }
+
+    #[inline(always)]
+    fn to_bytes(&self) -> Vec<u8> {
+        pnk!(bincode::serialize(self))
+    }
+
+    #[inline(always)]
+    fn set_nonce(&mut self, nonce: NoReplayToken) {
+        self.nonce = nonce;
+    }
+
+    #[inline(always)]
+    fn get_nonce(&self) -> NoReplayToken {
+        self.nonce
+    }
+}
+
+// Only one redelegation is allowed in a transaction.
+#[inline(always)]
+fn check_redelegation_context(tx: &Transaction) -> Result<()> {
+    let cnt = tx
+        .body
+        .operations
+        .iter()
+        .filter(|op| matches!(op, Operation::Redelegation(_)))
+        .count();
+
+    if 1 == cnt {
+        Ok(())
+    } else {
+        Err(eg!())
+    }
+}
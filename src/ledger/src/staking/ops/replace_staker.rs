@@ -5,7 +5,7 @@
 
 use {
     crate::data_model::{NoReplayToken, Transaction},
-    crate::staking::Staking,
+    crate::staking::{cosig::OperatorCoSigs, Staking},
     ruc::*,
     serde::{Deserialize, Serialize},
     zei::xfr::sig::{XfrKeyPair, XfrPublicKey, XfrSignature},
@@ -17,6 +17,8 @@ pub struct ReplaceStakerOps {
     body: Data,
     pubkey: XfrPublicKey,
     signature: XfrSignature,
+    #[serde(default, skip_serializing_if = "OperatorCoSigs::is_empty")]
+    operator_sigs: OperatorCoSigs,
 }
 
 impl ReplaceStakerOps {
@@ -44,6 +46,7 @@ impl ReplaceStakerOps {
             body,
             pubkey: keypair.get_pk(),
             signature,
+            operator_sigs: OperatorCoSigs::default(),
         }
     }
 
@@ -60,7 +63,13 @@ impl ReplaceStakerOps {
         self.pubkey
             .verify(&self.body.to_bytes(), &self.signature)
             .c(d!("Verification failed."))?;
-        Ok(())
+        self.operator_sigs.verify(&self.body.to_bytes()).c(d!())
+    }
+
+    /// Attach a signature of an operator of the multisig-controlled validator.
+    #[inline(always)]
+    pub fn sign_as_operator(&mut self, keypair: &XfrKeyPair) {
+        self.operator_sigs.sign(keypair, &self.body.to_bytes());
     }
 
     #[allow(missing_docs)]
@@ -70,6 +79,9 @@ impl ReplaceStakerOps {
         _tx: &Transaction,
     ) -> Result<()> {
         self.verify()?;
+        staking_simulator
+            .validator_check_operator_sigs(&self.pubkey, &self.operator_sigs)
+            .c(d!())?;
         dbg!(staking_simulator.check_and_replace_staker(
             &self.pubkey,
             self.body.new_public_key,
@@ -0,0 +1,122 @@
+//!
+//! # Reward Receiver Operation
+//!
+//! Data representation required when a validator wants its rewards
+//! and self-delegation to be paid to a withdrawal address.
+//!
+
+use {
+    crate::{
+        data_model::NoReplayToken,
+        staking::{cosig::OperatorCoSigs, Staking},
+    },
+    config::abci::global_cfg::CFG,
+    ruc::*,
+    serde::{Deserialize, Serialize},
+    zei::xfr::sig::{XfrKeyPair, XfrPublicKey, XfrSignature},
+};
+
+/// Used as the inner object of a `RewardReceiver Operation`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RewardReceiverOps {
+    body: Data,
+    pub(crate) pubkey: XfrPublicKey,
+    signature: XfrSignature,
+    #[serde(default, skip_serializing_if = "OperatorCoSigs::is_empty")]
+    operator_sigs: OperatorCoSigs,
+}
+
+impl RewardReceiverOps {
+    /// Check the validity of an operation by running it in a staking simulator.
+    #[inline(always)]
+    pub fn check_run(&self, staking_simulator: &mut Staking) -> Result<()> {
+        self.apply(staking_simulator).c(d!())
+    }
+
+    /// Set the reward receiver in the target `Staking` instance.
+    pub fn apply(&self, staking: &mut Staking) -> Result<()> {
+        if (staking.cur_height() as i64) < CFG.checkpoint.enable_reward_receiver_height {
+            return Err(eg!("reward receiver is not enabled"));
+        }
+
+        self.verify()
+            .c(d!())
+            .and_then(|_| {
+                staking
+                    .validator_check_operator_sigs(&self.pubkey, &self.operator_sigs)
+                    .c(d!())
+            })
+            .and_then(|_| {
+                staking
+                    .validator_set_reward_receiver(&self.pubkey, self.body.receiver)
+                    .c(d!())
+            })
+    }
+
+    /// Verify signature.
+    #[inline(always)]
+    pub fn verify(&self) -> Result<()> {
+        self.pubkey
+            .verify(&self.body.to_bytes(), &self.signature)
+            .c(d!())
+            .and_then(|_| self.operator_sigs.verify(&self.body.to_bytes()).c(d!()))
+    }
+
+    /// Attach a signature of an operator of the multisig-controlled validator.
+    #[inline(always)]
+    pub fn sign_as_operator(&mut self, keypair: &XfrKeyPair) {
+        self.operator_sigs.sign(keypair, &self.body.to_bytes());
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn get_related_pubkeys(&self) -> Vec<XfrPublicKey> {
+        let mut pks = vec![self.pubkey];
+        pks.extend(self.body.receiver);
+        pks
+    }
+
+    /// Use `None` as the receiver to pay to the validator key itself.
+    #[inline(always)]
+    pub fn new(
+        keypair: &XfrKeyPair,
+        receiver: Option<XfrPublicKey>,
+        nonce: NoReplayToken,
+    ) -> Self {
+        let body = Data { receiver, nonce };
+        let signature = keypair.sign(&body.to_bytes());
+        RewardReceiverOps {
+            body,
+            pubkey: keypair.get_pk(),
+            signature,
+            operator_sigs: OperatorCoSigs::default(),
+        }
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn set_nonce(&mut self, nonce: NoReplayToken) {
+        self.body.nonce = nonce;
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn get_nonce(&self) -> NoReplayToken {
+        self.body.nonce
+    }
+}
+
+// The body of a reward receiver operation.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct Data {
+    // `None` means paying to the validator key itself
+    receiver: Option<XfrPublicKey>,
+    nonce: NoReplayToken,
+}
+
+impl Data {
+    #[inline(always)]
+    fn to_bytes(&self) -> Vec<u8> {
+        pnk!(bincode::serialize(self))
+    }
+}
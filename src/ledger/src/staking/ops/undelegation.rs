@@ -7,7 +7,7 @@
 use {
     crate::{
         data_model::{NoReplayToken, Operation, Transaction},
-        staking::{PartialUnDelegation, Staking},
+        staking::{cosig::OperatorCoSigs, PartialUnDelegation, Staking},
     },
     ruc::*,
     serde::{Deserialize, Serialize},
@@ -20,6 +20,8 @@ pub struct UnDelegationOps {
     body: Data,
     pub(crate) pubkey: XfrPublicKey,
     signature: XfrSignature,
+    #[serde(default, skip_serializing_if = "OperatorCoSigs::is_empty")]
+    operator_sigs: OperatorCoSigs,
 }
 
 impl UnDelegationOps {
@@ -37,6 +39,11 @@ impl UnDelegationOps {
     pub fn apply(&self, staking: &mut Staking, tx: &Transaction) -> Result<()> {
         self.verify()
             .c(d!())
+            .and_then(|_| {
+                staking
+                    .validator_check_operator_sigs(&self.pubkey, &self.operator_sigs)
+                    .c(d!())
+            })
             .and_then(|_| Self::check_context(tx).c(d!()))
             .and_then(|pu| staking.undelegate(&self.pubkey, pu).c(d!()))
     }
@@ -47,6 +54,13 @@ impl UnDelegationOps {
         self.pubkey
             .verify(&self.body.to_bytes(), &self.signature)
             .c(d!())
+            .and_then(|_| self.operator_sigs.verify(&self.body.to_bytes()).c(d!()))
+    }
+
+    /// Attach a signature of an operator of the multisig-controlled validator.
+    #[inline(always)]
+    pub fn sign_as_operator(&mut self, keypair: &XfrKeyPair) {
+        self.operator_sigs.sign(keypair, &self.body.to_bytes());
     }
 
     #[inline(always)]
@@ -73,6 +87,7 @@ impl UnDelegationOps {
             body,
             pubkey: keypair.get_pk(),
             signature,
+            operator_sigs: OperatorCoSigs::default(),
         }
     }
 
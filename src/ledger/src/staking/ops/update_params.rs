@@ -0,0 +1,79 @@
+//!
+//! # Update Staking Parameters
+//!
+//! update the staking parameters at a certain future block height
+//! by using a multi-signature transaction.
+//!
+//! **NOTE**: always use the same multi-signature rules as `UpdateValidator`.
+//!
+
+use {
+    crate::{
+        data_model::NoReplayToken,
+        staking::{cosig::CoSigOp, params::StakingParams, BlockHeight, Staking},
+    },
+    config::abci::global_cfg::CFG,
+    ruc::*,
+    serde::{Deserialize, Serialize},
+    zei::xfr::sig::{XfrKeyPair, XfrPublicKey},
+};
+
+/// Used as the inner object of a `UpdateParams Operation`.
+pub type UpdateParamsOps = CoSigOp<Data>;
+
+impl UpdateParamsOps {
+    /// Check the validity of an operation by running it in a staking simulator.
+    #[inline(always)]
+    pub fn check_run(&self, staking_simulator: &mut Staking) -> Result<()> {
+        self.apply(staking_simulator).c(d!())
+    }
+
+    /// Apply new parameters to the target `Staking` instance,
+    /// pending parameters at the same height will be replaced.
+    pub fn apply(&self, staking: &mut Staking) -> Result<()> {
+        if (staking.cur_height() as i64) < CFG.checkpoint.enable_staking_params_height {
+            return Err(eg!("staking parameters governance is not enabled"));
+        }
+
+        self.verify(staking).c(d!()).and_then(|_| {
+            staking
+                .params_set_at_height(self.data.height, self.data.params.clone())
+                .c(d!())
+        })
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn get_related_pubkeys(&self) -> Vec<XfrPublicKey> {
+        self.cosigs.keys().copied().collect()
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn new(
+        kps: &[&XfrKeyPair],
+        h: BlockHeight,
+        params: StakingParams,
+        nonce: NoReplayToken,
+    ) -> Result<Self> {
+        params.check().c(d!())?;
+
+        let mut op = CoSigOp::create(Data::new(h, params), nonce);
+        op.batch_sign(kps).c(d!()).map(|_| op)
+    }
+}
+
+/// The body of a `UpdateParams Operation`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    /// the height at which the parameters take effect
+    height: BlockHeight,
+    params: StakingParams,
+}
+
+impl Data {
+    #[inline(always)]
+    fn new(height: BlockHeight, params: StakingParams) -> Self {
+        Data { height, params }
+    }
+}
@@ -7,8 +7,11 @@
 use {
     crate::{
         data_model::{NoReplayToken, Transaction},
-        staking::{td_addr_to_string, Staking, TendermintAddr, Validator},
+        staking::{
+            cosig::OperatorCoSigs, td_addr_to_string, Staking, TendermintAddr, Validator,
+        },
     },
+    config::abci::global_cfg::CFG,
     ed25519_dalek::Signer,
     ruc::*,
     serde::{Deserialize, Serialize},
@@ -23,6 +26,8 @@ pub struct UpdateStakerOps {
     pub(crate) pubkey: XfrPublicKey,
     signature: XfrSignature,
     v_signature: Option<Ed25519Signature>,
+    #[serde(default, skip_serializing_if = "OperatorCoSigs::is_empty")]
+    operator_sigs: OperatorCoSigs,
 }
 
 impl UpdateStakerOps {
@@ -40,7 +45,17 @@ impl UpdateStakerOps {
         self.verify()
             .c(d!())
             .and_then(|_| self.check_update_context(staking).c(d!()))
-            .and_then(|_| staking.update_staker(&self.body.new_validator).c(d!()))
+            .and_then(|_| {
+                if (staking.cur_height() as i64)
+                    < CFG.checkpoint.enable_commission_policy_height
+                {
+                    staking.update_staker(&self.body.new_validator).c(d!())
+                } else {
+                    staking
+                        .update_staker_delayed(&self.body.new_validator)
+                        .c(d!())
+                }
+            })
     }
 
     /// verify signature
@@ -62,14 +77,39 @@ impl UpdateStakerOps {
         self.pubkey
             .verify(&self.body.to_bytes(), &self.signature)
             .c(d!())
+            .and_then(|_| self.operator_sigs.verify(&self.body.to_bytes()).c(d!()))
+    }
+
+    /// Attach a signature of an operator of the multisig-controlled validator.
+    #[inline(always)]
+    pub fn sign_as_operator(&mut self, keypair: &XfrKeyPair) {
+        self.operator_sigs.sign(keypair, &self.body.to_bytes());
     }
 
     #[inline(always)]
     fn check_update_context(&self, staking: &mut Staking) -> Result<()> {
         let v = &self.body.new_validator;
+        if self.pubkey != v.id {
+            return Err(eg!("a staker can only be updated by itself"));
+        }
         staking
             .validator_td_addr_to_app_pk(&td_addr_to_string(&v.td_addr))
             .c(d!())?;
+        staking
+            .validator_check_operator_sigs(&v.id, &self.operator_sigs)
+            .c(d!())?;
+
+        // the commission rate can only be changed within the declared policy
+        if (staking.cur_height() as i64)
+            >= CFG.checkpoint.enable_commission_policy_height
+        {
+            staking
+                .validator_get_current_one_by_id(&v.id)
+                .c(d!("validator not found"))?
+                .check_commission_change(v.commission_rate)
+                .c(d!())?;
+        }
+
         Ok(())
     }
 
@@ -98,6 +138,7 @@ impl UpdateStakerOps {
             pubkey: keypair.get_pk(),
             signature,
             v_signature,
+            operator_sigs: OperatorCoSigs::default(),
         }
     }
 
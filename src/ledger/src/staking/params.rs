@@ -0,0 +1,90 @@
+//!
+//! # Staking Parameters
+//!
+//! The tunable rules of staking and penalties,
+//! they can be changed by an `UpdateParams` operation without a hard fork.
+//!
+
+use {
+    crate::staking::{
+        ops::governance::{ByzantineKind, Rule, RuleSet},
+        Amount, MAX_POWER_PERCENT_PER_VALIDATOR, MIN_DELEGATION_AMOUNT,
+        STAKING_VALIDATOR_MIN_POWER,
+    },
+    config::abci::global_cfg::CFG,
+    lazy_static::lazy_static,
+    ruc::*,
+    serde::{Deserialize, Serialize},
+};
+
+lazy_static! {
+    /// The parameters used before any update.
+    pub static ref DEFAULT_STAKING_PARAMS: StakingParams = StakingParams::default();
+}
+
+/// A version of the staking parameters.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StakingParams {
+    /// The max vote power of any validator, as a percent of global power.
+    pub max_power_percent_per_validator: [u128; 2],
+    /// The minimum investment to become a validator through staking.
+    pub validator_min_power: Amount,
+    /// The lock time after the delegation expires, in blocks.
+    pub unbond_block_cnt: u64,
+    /// Kinds of byzantine behavior and corresponding punishment mechanism.
+    pub penalty_rules: RuleSet,
+}
+
+impl Default for StakingParams {
+    fn default() -> Self {
+        StakingParams {
+            max_power_percent_per_validator: MAX_POWER_PERCENT_PER_VALIDATOR,
+            validator_min_power: STAKING_VALIDATOR_MIN_POWER,
+            unbond_block_cnt: CFG.checkpoint.unbond_block_cnt,
+            penalty_rules: map! { B
+                ByzantineKind::DuplicateVote => Rule::new([5, 100]),
+                ByzantineKind::LightClientAttack => Rule::new([1, 100]),
+                ByzantineKind::Unknown => Rule::new([30, 100]),
+                // we should set this percent to a very small value
+                ByzantineKind::OffLine => Rule::new([1, 1000_0000]),
+            },
+        }
+    }
+}
+
+impl StakingParams {
+    /// Check if all the parameters are in a reasonable range.
+    pub fn check(&self) -> Result<()> {
+        let p = self.max_power_percent_per_validator;
+        if 0 == p[0] || p[0] > p[1] {
+            return Err(eg!("invalid max power percent per validator"));
+        }
+
+        if MIN_DELEGATION_AMOUNT > self.validator_min_power {
+            return Err(eg!("invalid validator min power"));
+        }
+
+        if 0 == self.unbond_block_cnt {
+            return Err(eg!("invalid unbond block count"));
+        }
+
+        let kinds = [
+            ByzantineKind::DuplicateVote,
+            ByzantineKind::LightClientAttack,
+            ByzantineKind::OffLine,
+            ByzantineKind::Unknown,
+        ];
+        for kind in kinds.iter() {
+            let p = self
+                .penalty_rules
+                .get(kind)
+                .c(d!(format!("missing penalty rule of {:?}", kind)))?
+                .gen_penalty_percent();
+            if 0 == p[1] || p[1] > i64::MAX as u64 || p[0] > p[1] {
+                return Err(eg!(format!("invalid penalty rule of {:?}", kind)));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+//!
+//! # Governance Proposals
+//!
+//! Stake-weighted on-chain governance,
+//! validators and delegators vote on proposals with their bonded power,
+//! and the vote of a delegator overrides the one of its validator.
+//!
+
+use {
+    crate::staking::{params::StakingParams, Amount, BlockHeight, BLOCK_INTERVAL, FRA},
+    serde::{Deserialize, Serialize},
+    std::collections::{BTreeMap, BTreeSet},
+    zei::xfr::sig::XfrPublicKey,
+};
+
+/// The sequence number of a proposal.
+pub type ProposalId = u64;
+
+/// The minimum deposit of a new proposal.
+pub const PROPOSAL_MIN_DEPOSIT: Amount = 1000 * FRA;
+
+/// The voting period of a proposal, about 7 days.
+pub const PROPOSAL_VOTING_BLOCK_CNT: u64 = 3600 * 24 * 7 / BLOCK_INTERVAL;
+
+/// The minimum percent of the global power that must vote, 1/3.
+pub const PROPOSAL_QUORUM: [u128; 2] = [1, 3];
+
+/// The minimum percent of `Yes` in the non-abstaining votes, 1/2.
+pub const PROPOSAL_PASS_THRESHOLD: [u128; 2] = [1, 2];
+
+/// The percent of `NoWithVeto` in all votes that rejects a proposal
+/// and burns its deposit, 1/3.
+pub const PROPOSAL_VETO_THRESHOLD: [u128; 2] = [1, 3];
+
+/// The max length of the title of a proposal, in bytes.
+pub const PROPOSAL_TITLE_LEN_MAX: usize = 128;
+
+/// The max length of the description of a proposal, in bytes.
+pub const PROPOSAL_DESC_LEN_MAX: usize = 4096;
+
+/// What will be done if a proposal is passed.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ProposalContent {
+    /// A signaling proposal, nothing will be executed.
+    Text,
+    /// Replace the staking parameters from the next block.
+    ParamChange(StakingParams),
+    /// Pay some FRA to the receiver from the coinbase.
+    TreasurySpend {
+        #[allow(missing_docs)]
+        receiver: XfrPublicKey,
+        #[allow(missing_docs)]
+        amount: Amount,
+    },
+}
+
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum VoteOption {
+    Yes,
+    No,
+    Abstain,
+    NoWithVeto,
+}
+
+/// The life cycle of a proposal.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ProposalStatus {
+    /// In the voting period.
+    Voting,
+    /// Passed and executed successfully.
+    Passed,
+    /// Not passed, or vetoed.
+    Rejected,
+    /// Passed, but failed to execute.
+    Failed,
+}
+
+/// A governance proposal and its votes.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Proposal {
+    #[allow(missing_docs)]
+    pub id: ProposalId,
+    #[allow(missing_docs)]
+    pub proposer: XfrPublicKey,
+    #[allow(missing_docs)]
+    pub title: String,
+    #[allow(missing_docs)]
+    pub description: String,
+    #[allow(missing_docs)]
+    pub content: ProposalContent,
+    /// FRA paid by the proposer,
+    /// it will be refunded unless the proposal is vetoed or without quorum.
+    pub deposit: Amount,
+    /// Whether the deposit has been refunded.
+    pub deposit_refunded: bool,
+    #[allow(missing_docs)]
+    pub submit_height: BlockHeight,
+    /// The last height at which votes are accepted.
+    pub voting_end_height: BlockHeight,
+    #[allow(missing_docs)]
+    pub status: ProposalStatus,
+    /// voter => the latest vote of it
+    pub votes: BTreeMap<XfrPublicKey, VoteOption>,
+    /// Set when the voting period ends.
+    pub tally: Option<TallyResult>,
+}
+
+/// The bonded power of each vote option.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TallyResult {
+    #[allow(missing_docs)]
+    pub yes: Amount,
+    #[allow(missing_docs)]
+    pub no: Amount,
+    #[allow(missing_docs)]
+    pub abstain: Amount,
+    #[allow(missing_docs)]
+    pub no_with_veto: Amount,
+    /// The power of all validators when tallying.
+    pub global_power: Amount,
+}
+
+impl TallyResult {
+    #[inline(always)]
+    pub(crate) fn add(&mut self, option: VoteOption, power: Amount) {
+        let v = match option {
+            VoteOption::Yes => &mut self.yes,
+            VoteOption::No => &mut self.no,
+            VoteOption::Abstain => &mut self.abstain,
+            VoteOption::NoWithVeto => &mut self.no_with_veto,
+        };
+        *v = v.saturating_add(power);
+    }
+
+    #[inline(always)]
+    fn voted_power(&self) -> u128 {
+        self.yes as u128
+            + self.no as u128
+            + self.abstain as u128
+            + self.no_with_veto as u128
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn is_quorum_reached(&self) -> bool {
+        0 < self.global_power
+            && self.voted_power() * PROPOSAL_QUORUM[1]
+                >= self.global_power as u128 * PROPOSAL_QUORUM[0]
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn is_vetoed(&self) -> bool {
+        self.no_with_veto as u128 * PROPOSAL_VETO_THRESHOLD[1]
+            > self.voted_power() * PROPOSAL_VETO_THRESHOLD[0]
+    }
+
+    #[inline(always)]
+    #[allow(missing_docs)]
+    pub fn is_passed(&self) -> bool {
+        let non_abstain = self.voted_power() - self.abstain as u128;
+        0 < non_abstain
+            && self.yes as u128 * PROPOSAL_PASS_THRESHOLD[1]
+                > non_abstain * PROPOSAL_PASS_THRESHOLD[0]
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ProposalInfo {
+    pub(crate) next_id: ProposalId,
+    pub(crate) proposals: BTreeMap<ProposalId, Proposal>,
+    // voting end height => proposals to be tallied at that height
+    pub(crate) end_height_map: BTreeMap<BlockHeight, BTreeSet<ProposalId>>,
+}
+
+impl ProposalInfo {
+    #[inline(always)]
+    pub(crate) fn is_empty(&self) -> bool {
+        0 == self.next_id
+    }
+}
@@ -6,7 +6,7 @@ use {
     crate::{
         data_model::{
             AssetTypeCode, DefineAsset, IssueAsset, IssuerPublicKey, Operation,
-            Transaction, TxOutput, TxnIDHash, TxnSID, TxoSID, XfrAddress,
+            Transaction, TxOutput, TxnIDHash, TxnSID, TxoRef, TxoSID, XfrAddress,
         },
         staking::{
             ops::mint_fra::MintEntry, Amount, BlockHeight, DelegationRwdDetail,
@@ -18,7 +18,10 @@ use {
     globutils::wallet,
     ruc::*,
     serde::{Deserialize, Serialize},
-    std::collections::HashSet,
+    std::{
+        cmp,
+        collections::{BTreeSet, HashSet},
+    },
     zei::xfr::{sig::XfrPublicKey, structs::OwnerMemo},
 };
 
@@ -46,12 +49,16 @@ pub struct ApiCache {
     pub owner_memos: Mapxnk<TxoSID, OwnerMemo>,
     /// ownship of txo
     pub utxos_to_map_index: Mapxnk<TxoSID, XfrAddress>,
+    /// unspent txos of a ledger address, ordered by sid, used to page them
+    pub owned_utxos: Mapx<XfrAddress, Mapxnk<TxoSID, bool>>,
     /// txo(spent, unspent) to authenticated txn (sid, hash)
     pub txo_to_txnid: Mapxnk<TxoSID, TxnIDHash>,
     /// txn sid to txn hash
     pub txn_sid_to_hash: Mapxnk<TxnSID, String>,
     /// txn hash to txn sid
     pub txn_hash_to_sid: Mapx<String, TxnSID>,
+    /// txn sid to its operation kinds and assets, used to filter the history
+    pub txn_tags: Mapxnk<TxnSID, TxnTags>,
     /// global rate history
     pub staking_global_rate_hist: Mapxnk<BlockHeight, [u128; 2]>,
     /// - self-delegation amount history
@@ -96,9 +103,11 @@ impl ApiCache {
                 "api_cache/{}utxos_to_map_index",
                 prefix
             )),
+            owned_utxos: new_mapx!(format!("api_cache/{}owned_utxos", prefix)),
             txo_to_txnid: new_mapxnk!(format!("api_cache/{}txo_to_txnid", prefix)),
             txn_sid_to_hash: new_mapxnk!(format!("api_cache/{}txn_sid_to_hash", prefix)),
             txn_hash_to_sid: new_mapx!(format!("api_cache/{}txn_hash_to_sid", prefix)),
+            txn_tags: new_mapxnk!(format!("api_cache/{}txn_tags", prefix)),
             staking_global_rate_hist: new_mapxnk!(format!(
                 "api_cache/{}staking_global_rate_hist",
                 prefix
@@ -222,6 +231,176 @@ impl ApiCache {
     }
 }
 
+/// Operation kinds and nonconfidential assets of a transaction
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TxnTags {
+    /// names of the operations, such as `TransferAsset`
+    pub op_kinds: BTreeSet<String>,
+    /// nonconfidential assets defined, issued or transferred
+    pub assets: BTreeSet<AssetTypeCode>,
+}
+
+impl TxnTags {
+    /// Whether the transaction involves `asset` and contains an operation of `op_kind`,
+    /// a missing condition always matches.
+    pub fn matches(&self, asset: Option<&AssetTypeCode>, op_kind: Option<&str>) -> bool {
+        asset.map_or(true, |a| self.assets.contains(a))
+            && op_kind.map_or(true, |k| self.op_kinds.contains(k))
+    }
+}
+
+/// An ordered index of numeric keys, such as `TxnSID`s or `TxoSID`s
+pub trait PagedIndex {
+    /// Whether there is an entry at `pos`.
+    fn contains_pos(&self, pos: u64) -> bool;
+    /// The closest entry after `pos` if `desc` is false, or before it otherwise.
+    fn closest_pos(&self, pos: u64, desc: bool) -> Option<u64>;
+}
+
+impl PagedIndex for Mapxnk<TxnSID, bool> {
+    fn contains_pos(&self, pos: u64) -> bool {
+        self.contains_key(&TxnSID(pos as usize))
+    }
+
+    fn closest_pos(&self, pos: u64, desc: bool) -> Option<u64> {
+        let key = TxnSID(pos as usize);
+        alt!(
+            desc,
+            self.get_closest_smaller(&key),
+            self.get_closest_larger(&key)
+        )
+        .map(|(k, _)| k.0 as u64)
+    }
+}
+
+impl PagedIndex for Mapxnk<TxoSID, bool> {
+    fn contains_pos(&self, pos: u64) -> bool {
+        self.contains_key(&TxoSID(pos))
+    }
+
+    fn closest_pos(&self, pos: u64, desc: bool) -> Option<u64> {
+        let key = TxoSID(pos);
+        alt!(
+            desc,
+            self.get_closest_smaller(&key),
+            self.get_closest_larger(&key)
+        )
+        .map(|(k, _)| k.0)
+    }
+}
+
+/// Walk through an ordered index within `[lo, hi]` from the cursor,
+/// the cursor itself is excluded, entries rejected by `filter` are skipped.
+///
+/// Returns at most `limit` entries,
+/// along with the cursor of the next page if there are more.
+pub fn page_index(
+    index: &impl PagedIndex,
+    (lo, hi): (u64, u64),
+    cursor: Option<u64>,
+    limit: usize,
+    order_desc: bool,
+    mut filter: impl FnMut(u64) -> bool,
+) -> (Vec<u64>, Option<u64>) {
+    let mut items = vec![];
+
+    let mut pos = match (cursor, order_desc) {
+        (None, false) => Some(lo),
+        (None, true) => Some(hi),
+        (Some(c), false) => c.checked_add(1).map(|c| cmp::max(lo, c)),
+        (Some(c), true) => c.checked_sub(1).map(|c| cmp::min(hi, c)),
+    };
+
+    while let Some(p) = pos {
+        // `pos` itself is checked first, so the walk does not
+        // depend on whether the closest lookups include it
+        let p = if index.contains_pos(p) {
+            p
+        } else {
+            match index.closest_pos(p, order_desc) {
+                Some(p) => p,
+                None => break,
+            }
+        };
+        if p < lo || hi < p {
+            break;
+        }
+
+        if filter(p) {
+            if items.len() == limit {
+                let next_cursor = items.last().copied();
+                return (items, next_cursor);
+            }
+            items.push(p);
+        }
+
+        pos = alt!(order_desc, p.checked_sub(1), p.checked_add(1));
+    }
+
+    (items, None)
+}
+
+/// Returns the name of an operation, it is the same as the variant name
+pub fn get_operation_kind(op: &Operation) -> &'static str {
+    match op {
+        Operation::TransferAsset(_) => "TransferAsset",
+        Operation::IssueAsset(_) => "IssueAsset",
+        Operation::DefineAsset(_) => "DefineAsset",
+        Operation::UpdateMemo(_) => "UpdateMemo",
+        Operation::UpdateStaker(_) => "UpdateStaker",
+        Operation::ReplaceStaker(_) => "ReplaceStaker",
+        Operation::Delegation(_) => "Delegation",
+        Operation::UnDelegation(_) => "UnDelegation",
+        Operation::Redelegation(_) => "Redelegation",
+        Operation::Claim(_) => "Claim",
+        Operation::UpdateValidator(_) => "UpdateValidator",
+        Operation::Governance(_) => "Governance",
+        Operation::UpdateParams(_) => "UpdateParams",
+        Operation::Proposal(_) => "Proposal",
+        Operation::Vote(_) => "Vote",
+        Operation::Unjail(_) => "Unjail",
+        Operation::AutoCompound(_) => "AutoCompound",
+        Operation::CancelUnbonding(_) => "CancelUnbonding",
+        Operation::RewardReceiver(_) => "RewardReceiver",
+        Operation::FraDistribution(_) => "FraDistribution",
+        Operation::MintFra(_) => "MintFra",
+        Operation::ConvertAccount(_) => "ConvertAccount",
+    }
+}
+
+/// Collect the operation kinds and nonconfidential assets of a transaction
+pub fn get_txn_tags(txn: &Transaction) -> TxnTags {
+    let mut tags = TxnTags::default();
+
+    for op in &txn.body.operations {
+        tags.op_kinds.insert(get_operation_kind(op).to_owned());
+        match op {
+            Operation::TransferAsset(transfer) => {
+                let xfr = &transfer.body.transfer;
+                xfr.inputs
+                    .iter()
+                    .chain(xfr.outputs.iter())
+                    .filter_map(|r| r.asset_type.get_asset_type())
+                    .for_each(|val| {
+                        tags.assets.insert(AssetTypeCode { val });
+                    });
+            }
+            Operation::IssueAsset(issue_asset) => {
+                tags.assets.insert(issue_asset.body.code);
+            }
+            Operation::DefineAsset(define_asset) => {
+                tags.assets.insert(define_asset.body.asset.code);
+            }
+            Operation::UpdateMemo(update_memo) => {
+                tags.assets.insert(update_memo.body.asset_type);
+            }
+            _ => {}
+        }
+    }
+
+    tags
+}
+
 /// An xfr address is related to a transaction if it is one of the following:
 /// 1. Owner of a transfer output
 /// 2. Transfer signer (owner of input or co-signer)
@@ -252,9 +431,17 @@ where
             Operation::ReplaceStaker(i) => staking_gen!(i),
             Operation::Delegation(i) => staking_gen!(i),
             Operation::UnDelegation(i) => staking_gen!(i),
+            Operation::Redelegation(i) => staking_gen!(i),
             Operation::Claim(i) => staking_gen!(i),
             Operation::UpdateValidator(i) => staking_gen!(i),
             Operation::Governance(i) => staking_gen!(i),
+            Operation::UpdateParams(i) => staking_gen!(i),
+            Operation::Proposal(i) => staking_gen!(i),
+            Operation::Vote(i) => staking_gen!(i),
+            Operation::Unjail(i) => staking_gen!(i),
+            Operation::AutoCompound(i) => staking_gen!(i),
+            Operation::CancelUnbonding(i) => staking_gen!(i),
+            Operation::RewardReceiver(i) => staking_gen!(i),
             Operation::FraDistribution(i) => staking_gen!(i),
             Operation::MintFra(i) => staking_gen!(i),
 
@@ -455,6 +642,46 @@ pub fn check_lost_data(ledger: &mut LedgerState) -> Result<()> {
                 .insert("last_txo_sid".to_string(), index as u64);
         }
     }
+
+    // index the utxos committed before the owner index existed
+    let next_owned_txo_sid = ledger
+        .api_cache
+        .as_mut()
+        .unwrap()
+        .last_sid
+        .get(&"next_owned_txo_sid".to_string())
+        .unwrap_or(0);
+    if next_owned_txo_sid < cur_txo_sid {
+        let prefix = ledger.api_cache.as_mut().unwrap().prefix.clone();
+        for index in next_owned_txo_sid..cur_txo_sid {
+            if let Some(utxo) = ledger.get_utxo_light(TxoSID(index)) {
+                let address = XfrAddress {
+                    key: utxo.utxo.0.record.public_key,
+                };
+                ledger
+                    .api_cache
+                    .as_mut()
+                    .unwrap()
+                    .owned_utxos
+                    .entry(address)
+                    .or_insert_with(|| {
+                        new_mapxnk!(format!(
+                            "api_cache/{}owned_utxos/{}",
+                            &prefix,
+                            address.to_base64()
+                        ))
+                    })
+                    .insert(TxoSID(index), Default::default());
+            }
+        }
+        ledger
+            .api_cache
+            .as_mut()
+            .unwrap()
+            .last_sid
+            .insert("next_owned_txo_sid".to_string(), cur_txo_sid);
+    }
+
     Ok(())
 }
 
@@ -566,6 +793,13 @@ pub fn update_api_cache(ledger: &mut LedgerState) -> Result<()> {
                 .insert(txn_sid, Default::default());
         }
 
+        ledger
+            .api_cache
+            .as_mut()
+            .unwrap()
+            .txn_tags
+            .insert(txn_sid, get_txn_tags(&curr_txn));
+
         // Update transferred nonconfidential assets
         let transferred_assets = get_transferred_nonconfidential_assets(&curr_txn);
         for asset in &transferred_assets {
@@ -644,6 +878,41 @@ pub fn update_api_cache(ledger: &mut LedgerState) -> Result<()> {
                     .owner_memos
                     .insert(*txo_sid, (*owner_memo).clone());
             }
+            // outputs spent within the same block are never indexed
+            if ledger.get_utxo_light(*txo_sid).is_some() {
+                ledger
+                    .api_cache
+                    .as_mut()
+                    .unwrap()
+                    .owned_utxos
+                    .entry(*address)
+                    .or_insert_with(|| {
+                        new_mapxnk!(format!(
+                            "api_cache/{}owned_utxos/{}",
+                            &prefix,
+                            address.to_base64()
+                        ))
+                    })
+                    .insert(*txo_sid, Default::default());
+            }
+        }
+
+        // Remove spent utxos from the index
+        for op in &curr_txn.body.operations {
+            if let Operation::TransferAsset(transfer) = op {
+                for input in transfer.body.inputs.iter() {
+                    if let TxoRef::Absolute(sid) = input {
+                        let api_cache = ledger.api_cache.as_mut().unwrap();
+                        if let Some(address) = api_cache.utxos_to_map_index.get(sid) {
+                            if let Some(mut utxos) =
+                                api_cache.owned_utxos.get_mut(&address)
+                            {
+                                utxos.remove(sid);
+                            }
+                        }
+                    }
+                }
+            }
         }
     }
 
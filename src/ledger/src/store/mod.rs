@@ -20,8 +20,8 @@ use {
             BLACK_HOLE_PUBKEY,
         },
         staking::{
-            Amount, Power, Staking, TendermintAddrRef, FF_PK_EXTRA_120_0000, FF_PK_LIST,
-            FRA_TOTAL_AMOUNT, KEEP_HIST,
+            Amount, BlockHeight, Power, Staking, TendermintAddrRef,
+            FF_PK_EXTRA_120_0000, FF_PK_LIST, FRA_TOTAL_AMOUNT, KEEP_HIST,
         },
         LSSED_VAR, SNAPSHOT_ENTRIES_DIR,
     },
@@ -39,7 +39,8 @@ use {
     serde::{Deserialize, Serialize},
     sliding_set::SlidingSet,
     std::{
-        collections::{BTreeMap, HashMap, HashSet},
+        cmp,
+        collections::{BTreeMap, BTreeSet, HashMap, HashSet},
         env,
         fs::{self, OpenOptions},
         io::ErrorKind,
@@ -56,6 +57,9 @@ use {
 
 const TRANSACTION_WINDOW_WIDTH: u64 = 128;
 
+// the max number of blocks newly pruned by one call of `LedgerState::prune`
+const PRUNE_BLOCKS_PER_CALL: usize = 100;
+
 type TmpSidMap = HashMap<TxnTempSID, (TxnSID, Vec<TxoSID>)>;
 
 /// findora ledger
@@ -434,6 +438,48 @@ impl LedgerState {
         Ok(ledger)
     }
 
+    /// Open an existing ledger for inspection, used by offline tools
+    /// on the data of a stopped node.
+    ///
+    /// Unlike `load_or_init`, nothing is created, repaired or truncated,
+    /// the Merkle logs and the utxo bitmap are opened without write access,
+    /// and any missing or corrupted file is returned as an error.
+    pub fn load_existing(basedir: &str) -> Result<LedgerState> {
+        let status_path = format!("{}/ledger_status", basedir);
+        let block_merkle_path = format!("{}/block_merkle", basedir);
+        let txn_merkle_path = format!("{}/txn_merkle", basedir);
+        let utxo_map_path = format!("{}/utxo_map", basedir);
+
+        env::set_var(LSSED_VAR, "ledger_status_subdata");
+        let status = fs::read_to_string(&status_path)
+            .c(d!(status_path))
+            .and_then(|s| serde_json::from_str::<LedgerStatus>(&s).c(d!()))?;
+
+        let utxo_map = OpenOptions::new()
+            .read(true)
+            .open(&utxo_map_path)
+            .c(d!(utxo_map_path))
+            .and_then(|f| BitMap::open(f).c(d!()))?;
+
+        let ledger = LedgerState {
+            status,
+            block_merkle: Arc::new(RwLock::new(
+                AppendOnlyMerkle::open_read_only(&block_merkle_path).c(d!())?,
+            )),
+            txn_merkle: Arc::new(RwLock::new(
+                AppendOnlyMerkle::open_read_only(&txn_merkle_path).c(d!())?,
+            )),
+            blocks: new_vecx!("blocks"),
+            tx_to_block_location: new_mapxnk!("tx_to_block_location"),
+            utxo_map: Arc::new(RwLock::new(utxo_map)),
+            block_ctx: None,
+            api_cache: None,
+        };
+        ledger.fast_invariant_check().c(d!())?;
+
+        Ok(ledger)
+    }
+
     /// Perform checkpoint of current ledger state
     pub fn checkpoint(&mut self, block: &BlockEffect) -> Result<u64> {
         let merkle_id = self.compute_and_append_txns_hash(&block);
@@ -900,6 +946,11 @@ impl LedgerState {
 
     /// Query the Block by a BlockSID along with its proof data
     pub fn get_block(&self, addr: BlockSID) -> Option<AuthenticatedBlock> {
+        // the transactions of a pruned block are incomplete
+        if addr.0 < self.status.pruned_block_count {
+            return None;
+        }
+
         match self.blocks.get(addr.0) {
             None => None,
             Some(finalized_block) => {
@@ -921,6 +972,193 @@ impl LedgerState {
         }
     }
 
+    /// Check the block and transaction Merkle logs for consistency,
+    /// and their root hashes, along with the checksum of the utxo bitmap,
+    /// against the latest state commitment data.
+    pub fn check_merkle_logs(&self) -> Result<()> {
+        let block_merkle = self.block_merkle.read();
+        let txn_merkle = self.txn_merkle.read();
+
+        check_commitment_data(
+            self.status.state_commitment_data.as_ref(),
+            &block_merkle,
+            &txn_merkle,
+            &mut self.utxo_map.write(),
+        )
+        .c(d!())
+    }
+
+    /// Check the ledger files in `basedir`, such as those restored from a snapshot,
+    /// and return the state commitment of their latest height.
+    ///
+    /// The Merkle roots and the utxo bitmap checksum are recomputed from the files,
+    /// then compared with the `StateCommitmentData` of the `LedgerStatus`.
+    ///
+    /// NOTE: the fbnc data of the `LedgerStatus` is bound to the current
+    /// `BNC_DATA_DIR`, so it must not be accessed through the returned value.
+    pub fn check_ledger_files(
+        basedir: &str,
+    ) -> Result<HashOf<Option<StateCommitmentData>>> {
+        let status_path = format!("{}/ledger_status", basedir);
+        let block_merkle_path = format!("{}/block_merkle", basedir);
+        let txn_merkle_path = format!("{}/txn_merkle", basedir);
+        let utxo_map_path = format!("{}/utxo_map", basedir);
+
+        let scd = fs::read_to_string(&status_path)
+            .c(d!(status_path))
+            .and_then(|s| serde_json::from_str::<LedgerStatus>(&s).c(d!()))?
+            .state_commitment_data;
+
+        let block_merkle =
+            AppendOnlyMerkle::open_read_only(&block_merkle_path).c(d!())?;
+        let txn_merkle = AppendOnlyMerkle::open_read_only(&txn_merkle_path).c(d!())?;
+        let mut utxo_map = OpenOptions::new()
+            .read(true)
+            .open(&utxo_map_path)
+            .c(d!(utxo_map_path))
+            .and_then(|f| BitMap::open(f).c(d!()))?;
+
+        check_commitment_data(scd.as_ref(), &block_merkle, &txn_merkle, &mut utxo_map)
+            .c(d!())?;
+
+        Ok(HashOf::new(&scd))
+    }
+
+    /// Verify the block at `block_height` against the Merkle logs
+    /// and the recorded commitment history, returns its state commitment.
+    ///
+    /// The following are checked:
+    /// - the commitment links to the one of the previous height
+    /// - the transactions in the block match `txns_in_block_hash`
+    /// - the leaf of the block Merkle log is `txns_in_block_hash`
+    /// - the leaves of the transaction Merkle log are the transaction hashes
+    /// - the commitment matches the recorded one
+    ///
+    /// NOTE: the Merkle logs have no versions and the utxo bitmap is not logged,
+    /// so the roots and the bitmap checksum of a past height are NOT recomputed,
+    /// those of the latest height are checked by `check_merkle_logs`.
+    pub fn verify_block_commitment(
+        &self,
+        block_height: u64,
+    ) -> Result<HashOf<Option<StateCommitmentData>>> {
+        let idx = (block_height as usize).checked_sub(1).c(d!())?;
+        let block = self.blocks.get(idx).c(d!("block not found"))?;
+
+        if self.block_merkle.read().get_leaf(block.merkle_id)
+            != Some(block.state.txns_in_block_hash.0.hash.into())
+        {
+            return Err(eg!("block merkle leaf mismatch"));
+        }
+
+        // pruned transactions are not in the block any more,
+        // but the kept ones can still be checked against their leaves
+        let txn_merkle = self.txn_merkle.read();
+        for tx in block.txns.iter() {
+            if txn_merkle.get_leaf(tx.merkle_id) != Some(tx.hash().0.hash.into()) {
+                return Err(eg!(format!(
+                    "transaction merkle leaf mismatch: {}",
+                    tx.tx_id.0
+                )));
+            }
+        }
+        drop(txn_merkle);
+
+        let previous = if 0 == idx {
+            HashOf::new(&None)
+        } else {
+            self.status.state_commitment_versions.get(idx - 1).c(d!())?
+        };
+        if block.state.previous_state_commitment != previous {
+            return Err(eg!("previous state commitment mismatch"));
+        }
+
+        // the transactions of a pruned block are incomplete
+        if self.status.pruned_block_count <= idx {
+            let txns = block
+                .txns
+                .iter()
+                .map(|tx| tx.txn.clone())
+                .collect::<Vec<_>>();
+            if HashOf::new(&txns) != block.state.txns_in_block_hash {
+                return Err(eg!("transactions hash mismatch"));
+            }
+        }
+
+        let commitment = block.state.compute_commitment();
+        if Some(&commitment) != self.status.state_commitment_versions.get(idx).as_ref() {
+            return Err(eg!("state commitment mismatch"));
+        }
+
+        Ok(commitment)
+    }
+
+    /// Iterate over all registered asset types.
+    #[inline(always)]
+    pub fn iter_asset_types(
+        &self,
+    ) -> impl Iterator<Item = (AssetTypeCode, AssetType)> + '_ {
+        self.status.asset_types.iter()
+    }
+
+    /// Discard the transactions and spent utxos of the blocks
+    /// older than the latest `keep_blocks` ones.
+    ///
+    /// Transactions that still have unspent outputs are kept,
+    /// so that these utxos can be queried along with their proofs,
+    /// they are discarded by a later pruning once all their outputs are spent,
+    /// the Merkle trees and state commitments are never touched.
+    ///
+    /// At most `PRUNE_BLOCKS_PER_CALL` blocks are newly pruned by one call,
+    /// the rest are left to the following calls.
+    pub fn prune(&mut self, keep_blocks: usize) -> Result<()> {
+        let end = cmp::min(
+            self.blocks.len().saturating_sub(keep_blocks),
+            self.status.pruned_block_count + PRUNE_BLOCKS_PER_CALL,
+        );
+
+        // revisit the pruned blocks whose kept transactions have outputs spent since
+        let revisited = mem::take(&mut self.status.spent_since_pruning)
+            .into_iter()
+            .filter_map(|sid| self.tx_to_block_location.get(&sid))
+            .map(|[block_idx, _]| block_idx)
+            .filter(|block_idx| *block_idx < self.status.pruned_block_count)
+            .collect::<BTreeSet<_>>();
+
+        for block_idx in revisited
+            .into_iter()
+            .chain(self.status.pruned_block_count..end)
+        {
+            let mut block = self.blocks.get_mut(block_idx).c(d!())?;
+
+            let (kept, pruned): (Vec<_>, Vec<_>) =
+                mem::take(&mut block.txns).into_iter().partition(|tx| {
+                    tx.txo_ids
+                        .iter()
+                        .any(|sid| self.status.utxos.contains_key(sid))
+                });
+
+            for tx in pruned.iter() {
+                self.tx_to_block_location.remove(&tx.tx_id);
+                for sid in tx.txo_ids.iter() {
+                    self.status.spent_utxos.remove(sid);
+                    self.status.txo_to_txn_location.remove(sid);
+                }
+            }
+
+            // the positions of the kept transactions have changed
+            for (tx_idx, tx) in kept.iter().enumerate() {
+                self.tx_to_block_location
+                    .insert(tx.tx_id, [block_idx, tx_idx]);
+            }
+
+            block.txns = kept;
+        }
+
+        self.status.pruned_block_count = cmp::max(self.status.pruned_block_count, end);
+
+        Ok(())
+    }
+
     #[inline(always)]
     #[allow(missing_docs)]
     pub fn get_block_count(&self) -> usize {
@@ -933,6 +1171,47 @@ impl LedgerState {
         self.get_next_txn().0
     }
 
+    /// Returns the height of the block containing a given transaction.
+    #[inline(always)]
+    pub fn get_transaction_height(&self, txn_sid: TxnSID) -> Option<BlockHeight> {
+        self.tx_to_block_location
+            .get(&txn_sid)
+            .map(|[block_idx, _]| 1 + block_idx as BlockHeight)
+    }
+
+    /// The range of txn sids committed within the given heights, both ends inclusive.
+    ///
+    /// Txn sids are assigned in the order of block heights,
+    /// so the bounds can be found by binary searching.
+    pub fn get_txn_sid_range(
+        &self,
+        from_height: Option<BlockHeight>,
+        to_height: Option<BlockHeight>,
+    ) -> Option<(usize, usize)> {
+        let cnt = self.get_transaction_count();
+
+        // the first txn sid whose height is not lower than `h`
+        let lower_bound = |h: BlockHeight| {
+            let (mut l, mut r) = (0, cnt);
+            while l < r {
+                let m = l + (r - l) / 2;
+                if self.get_transaction_height(TxnSID(m)).unwrap_or(0) < h {
+                    l = m + 1;
+                } else {
+                    r = m;
+                }
+            }
+            l
+        };
+
+        let lo = from_height.map(lower_bound).unwrap_or(0);
+        let hi = to_height
+            .map(|h| lower_bound(h.saturating_add(1)))
+            .unwrap_or(cnt);
+
+        alt!(lo < hi, Some((lo, hi - 1)), None)
+    }
+
     #[inline(always)]
     #[allow(missing_docs)]
     pub fn get_state_commitment_at_block_height(
@@ -945,6 +1224,31 @@ impl LedgerState {
     }
 }
 
+// Check the Merkle logs and the utxo bitmap against the state commitment data.
+fn check_commitment_data(
+    scd: Option<&StateCommitmentData>,
+    block_merkle: &AppendOnlyMerkle,
+    txn_merkle: &AppendOnlyMerkle,
+    utxo_map: &mut BitMap,
+) -> Result<()> {
+    block_merkle.check().c(d!("invalid block merkle log"))?;
+    txn_merkle.check().c(d!("invalid transaction merkle log"))?;
+
+    if let Some(scd) = scd {
+        if scd.block_merkle != block_merkle.get_root_hash() {
+            return Err(eg!("block merkle root mismatch"));
+        }
+        if scd.transaction_merkle_commitment != txn_merkle.get_root_hash() {
+            return Err(eg!("transaction merkle root mismatch"));
+        }
+        if scd.bitmap != utxo_map.compute_checksum() {
+            return Err(eg!("utxo bitmap checksum mismatch"));
+        }
+    }
+
+    Ok(())
+}
+
 /// The main LedgerStatus of findora ledger
 #[derive(Deserialize, Serialize, Clone, PartialEq, Debug)]
 pub struct LedgerStatus {
@@ -983,6 +1287,13 @@ pub struct LedgerStatus {
     staking: Staking,
     // tendermint commit height
     td_commit_height: u64,
+    // number of blocks whose transactions have been pruned
+    #[serde(default)]
+    pruned_block_count: usize,
+    // transactions whose outputs have been spent since the last pruning,
+    // the kept ones among them are revisited by the next pruning
+    #[serde(default)]
+    spent_since_pruning: BTreeSet<TxnSID>,
 
     // An obsolete feature, ignore it!
     tracing_policies: HashMap<AssetTypeCode, TracingPolicy>,
@@ -1093,6 +1404,8 @@ impl LedgerStatus {
             block_commit_count: 0,
             staking: Staking::new(),
             td_commit_height: 0,
+            pruned_block_count: 0,
+            spent_since_pruning: BTreeSet::new(),
         };
 
         Ok(ledger)
@@ -1338,6 +1651,11 @@ impl LedgerStatus {
                     *bl -= v.get_nonconfidential_balance();
                 }
                 self.spent_utxos.insert(inp_sid, v);
+                if 0 < self.pruned_block_count {
+                    if let Some((txn_sid, _)) = self.txo_to_txn_location.get(&inp_sid) {
+                        self.spent_since_pruning.insert(txn_sid);
+                    }
+                }
             }
         }
 
@@ -787,3 +787,266 @@ fn test_check_fee_with_ledger() {
     let mut block = ledger.start_block().unwrap();
     assert!(ledger.apply_transaction(&mut block, effect).is_err());
 }
+
+#[test]
+fn test_prune() {
+    let mut ledger = LedgerState::tmp_ledger();
+    let fra_owner_kp = XfrKeyPair::generate(&mut ChaChaRng::from_entropy());
+
+    let apply = |ledger: &mut LedgerState, tx: Transaction| {
+        let effect = TxnEffect::compute_effect(tx).unwrap();
+        let mut block = ledger.start_block().unwrap();
+        let tmp_sid = ledger.apply_transaction(&mut block, effect).unwrap();
+        ledger
+            .finish_block(block)
+            .unwrap()
+            .remove(&tmp_sid)
+            .unwrap()
+    };
+
+    // block 0, issue two utxos
+    let (txn_sid0, txos0) = apply(&mut ledger, utils::fra_gen_initial_tx(&fra_owner_kp));
+
+    // block 1, spend one of them
+    let op = gen_fee_operation(&mut ledger, txos0[0], &fra_owner_kp);
+    let (txn_sid1, txos1) = apply(&mut ledger, Transaction::from_operation(op, 1));
+
+    // the transaction in block 0 is kept for its unspent output
+    ledger.prune(1).unwrap();
+    assert!(ledger.get_transaction(txn_sid0).is_ok());
+    assert!(ledger.get_block(BlockSID(0)).is_none());
+
+    // block 2, spend the other one
+    let op = gen_fee_operation(&mut ledger, txos0[1], &fra_owner_kp);
+    let (txn_sid2, _) = apply(&mut ledger, Transaction::from_operation(op, 2));
+
+    let state_commitment = ledger.get_state_commitment();
+
+    // block 0 is revisited
+    ledger.prune(1).unwrap();
+
+    // nothing about the state commitment is touched
+    assert_eq!(state_commitment, ledger.get_state_commitment());
+    ledger.fast_invariant_check().unwrap();
+
+    // all outputs of the transaction in block 0 are spent
+    assert!(ledger.get_transaction(txn_sid0).is_err());
+    assert!(ledger.get_spent_utxo(txos0[0]).is_none());
+    assert!(ledger.get_spent_utxo(txos0[1]).is_none());
+    assert!(ledger.get_block(BlockSID(0)).is_none());
+
+    // the transaction in block 1 has unspent outputs
+    let txn = ledger.get_transaction(txn_sid1).unwrap();
+    assert!(txn.is_valid(state_commitment.0.clone()));
+    let utxo = ledger.get_utxo(txos1[0]).unwrap();
+    assert!(utxo.is_valid(state_commitment.0.clone()));
+    assert!(ledger.get_block(BlockSID(1)).is_none());
+
+    // block 2 is in the retention window
+    let txn = ledger.get_transaction(txn_sid2).unwrap();
+    assert!(txn.is_valid(state_commitment.0.clone()));
+    let block = ledger.get_block(BlockSID(2)).unwrap();
+    assert!(block.is_valid(state_commitment.0));
+}
+
+#[test]
+fn test_verify_block_commitment() {
+    let mut ledger = LedgerState::tmp_ledger();
+    let fra_owner_kp = XfrKeyPair::generate(&mut ChaChaRng::from_entropy());
+
+    let tx = utils::fra_gen_initial_tx(&fra_owner_kp);
+    let effect = TxnEffect::compute_effect(tx).unwrap();
+    let mut block = ledger.start_block().unwrap();
+    let tmp_sid = ledger.apply_transaction(&mut block, effect).unwrap();
+    let txo_sid = ledger
+        .finish_block(block)
+        .unwrap()
+        .remove(&tmp_sid)
+        .unwrap()
+        .1[0];
+
+    let tx = Transaction::from_operation(
+        gen_fee_operation(&mut ledger, txo_sid, &fra_owner_kp),
+        1,
+    );
+    let effect = TxnEffect::compute_effect(tx).unwrap();
+    let mut block = ledger.start_block().unwrap();
+    ledger.apply_transaction(&mut block, effect).unwrap();
+    ledger.finish_block(block).unwrap();
+
+    ledger.check_merkle_logs().unwrap();
+
+    for h in 1..=ledger.get_block_commit_count() {
+        assert_eq!(
+            ledger.verify_block_commitment(h).unwrap(),
+            ledger.get_state_commitment_at_block_height(h).unwrap()
+        );
+    }
+    assert!(ledger.verify_block_commitment(0).is_err());
+    assert!(ledger
+        .verify_block_commitment(1 + ledger.get_block_commit_count())
+        .is_err());
+}
+
+#[test]
+fn test_load_existing_creates_nothing() {
+    let tmp_dir = globutils::fresh_tmp_dir();
+    let path = tmp_dir.to_string_lossy().into_owned();
+
+    assert!(LedgerState::load_existing(&path).is_err());
+    assert_eq!(0, std::fs::read_dir(&tmp_dir).unwrap().count());
+}
+
+#[test]
+fn test_check_ledger_files() {
+    fbnc::clear();
+    let tmp_dir = globutils::fresh_tmp_dir();
+    let path = tmp_dir.to_string_lossy().into_owned();
+    let mut ledger = LedgerState::new(&path, None).unwrap();
+    let fra_owner_kp = XfrKeyPair::generate(&mut ChaChaRng::from_entropy());
+
+    let tx = utils::fra_gen_initial_tx(&fra_owner_kp);
+    let effect = TxnEffect::compute_effect(tx).unwrap();
+    let mut block = ledger.start_block().unwrap();
+    ledger.apply_transaction(&mut block, effect).unwrap();
+    ledger.finish_block(block).unwrap();
+
+    let status_path = format!("{}/{}", &path, &ledger.get_status().snapshot_file);
+    std::fs::write(
+        &status_path,
+        serde_json::to_vec(&ledger.get_status()).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        LedgerState::check_ledger_files(&path).unwrap(),
+        ledger.get_state_commitment().0
+    );
+
+    // a broken bitmap
+    std::fs::write(tmp_dir.join("utxo_map"), vec![]).unwrap();
+    assert!(LedgerState::check_ledger_files(&path).is_err());
+}
+
+#[test]
+fn test_page_index() {
+    let name = globutils::fresh_tmp_dir()
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .into_owned();
+    let mut index: Mapxnk<TxoSID, bool> =
+        new_mapxnk!(format!("test_page_index/{}", name));
+    for sid in [1, 3, 4, 7, 9, 10] {
+        index.insert(TxoSID(sid), true);
+    }
+
+    let page = |range, cursor, limit, desc| {
+        api_cache::page_index(&index, range, cursor, limit, desc, |_| true)
+    };
+
+    // both ends are inclusive
+    assert_eq!(
+        page((1, 10), None, 10, false),
+        (vec![1, 3, 4, 7, 9, 10], None)
+    );
+    assert_eq!(
+        page((1, 10), None, 10, true),
+        (vec![10, 9, 7, 4, 3, 1], None)
+    );
+    assert_eq!(page((3, 9), None, 10, false), (vec![3, 4, 7, 9], None));
+    assert_eq!(page((3, 9), None, 10, true), (vec![9, 7, 4, 3], None));
+    assert_eq!(page((5, 6), None, 10, false), (vec![], None));
+
+    // the cursor is excluded, its neighbours are not
+    assert_eq!(page((0, 10), Some(3), 2, false), (vec![4, 7], Some(7)));
+    assert_eq!(page((0, 10), Some(7), 2, false), (vec![9, 10], None));
+    assert_eq!(page((0, 10), Some(4), 2, true), (vec![3, 1], None));
+    assert_eq!(page((0, 10), Some(10), 2, true), (vec![9, 7], Some(7)));
+    assert_eq!(page((0, 10), Some(5), 10, false), (vec![7, 9, 10], None));
+    assert_eq!(page((0, 10), Some(0), 10, true), (vec![], None));
+    assert_eq!(
+        page((0, u64::MAX), Some(u64::MAX), 10, false),
+        (vec![], None)
+    );
+
+    // no next page if exactly `limit` entries remain
+    assert_eq!(
+        page((0, 10), None, 6, false),
+        (vec![1, 3, 4, 7, 9, 10], None)
+    );
+    assert_eq!(
+        page((0, 10), None, 5, false),
+        (vec![1, 3, 4, 7, 9], Some(9))
+    );
+    assert_eq!(page((0, 9), None, 5, false), (vec![1, 3, 4, 7, 9], None));
+
+    // filtered out entries neither fill the page nor make a next page
+    let odd = |sid: u64| 1 == sid % 2;
+    assert_eq!(
+        api_cache::page_index(&index, (0, 10), None, 2, false, odd),
+        (vec![1, 3], Some(3))
+    );
+    assert_eq!(
+        api_cache::page_index(&index, (0, 10), Some(3), 2, false, odd),
+        (vec![7, 9], None)
+    );
+}
+
+#[test]
+fn test_get_txn_sid_range() {
+    let mut ledger = LedgerState::tmp_ledger();
+    let keypair = XfrKeyPair::generate(&mut ledger.get_prng());
+
+    assert!(ledger.get_txn_sid_range(None, None).is_none());
+
+    // block 1 has txn 0 and 1, block 2 is empty, block 3 has txn 2
+    for n in [2, 0, 1] {
+        let mut block = ledger.start_block().unwrap();
+        for _ in 0..n {
+            let tx = create_definition_transaction(
+                &AssetTypeCode::gen_random(),
+                &keypair,
+                AssetRules::default(),
+                None,
+                ledger.get_block_commit_count(),
+            )
+            .unwrap();
+            let effect = TxnEffect::compute_effect(tx).unwrap();
+            ledger.apply_transaction(&mut block, effect).unwrap();
+        }
+        ledger.finish_block(block).unwrap();
+    }
+
+    assert_eq!(Some(1), ledger.get_transaction_height(TxnSID(1)));
+    assert_eq!(Some(3), ledger.get_transaction_height(TxnSID(2)));
+    assert!(ledger.get_transaction_height(TxnSID(3)).is_none());
+
+    assert_eq!(Some((0, 2)), ledger.get_txn_sid_range(None, None));
+    assert_eq!(Some((0, 1)), ledger.get_txn_sid_range(Some(1), Some(1)));
+    assert_eq!(Some((0, 1)), ledger.get_txn_sid_range(None, Some(2)));
+    assert_eq!(Some((2, 2)), ledger.get_txn_sid_range(Some(2), None));
+    assert_eq!(Some((2, 2)), ledger.get_txn_sid_range(Some(3), Some(9)));
+    assert!(ledger.get_txn_sid_range(Some(2), Some(2)).is_none());
+    assert!(ledger.get_txn_sid_range(Some(4), None).is_none());
+    assert!(ledger.get_txn_sid_range(Some(3), Some(1)).is_none());
+}
+
+#[test]
+fn test_txn_tags_matches() {
+    let mut prng = ChaChaRng::from_entropy();
+    let keypair = build_keys(&mut prng);
+    let code = AssetTypeCode::gen_random();
+    let tx =
+        create_definition_transaction(&code, &keypair, AssetRules::default(), None, 0)
+            .unwrap();
+    let tags = api_cache::get_txn_tags(&tx);
+
+    assert!(tags.matches(None, None));
+    assert!(tags.matches(Some(&code), None));
+    assert!(tags.matches(None, Some("DefineAsset")));
+    assert!(tags.matches(Some(&code), Some("DefineAsset")));
+    assert!(!tags.matches(Some(&AssetTypeCode::gen_random()), None));
+    assert!(!tags.matches(None, Some("TransferAsset")));
+    assert!(!tags.matches(Some(&code), Some("TransferAsset")));
+}
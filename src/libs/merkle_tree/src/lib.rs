@@ -576,12 +576,18 @@ pub struct AppendOnlyMerkle {
 
     #[serde(skip_serializing, skip_deserializing)]
     blocks_on_disk: Vec<u64>, // the number of entries on stable store
+
+    #[serde(skip_serializing, skip_deserializing)]
+    read_only: bool, // opened by open_read_only, the disk is never touched
 }
 
 // When a tree is dropped, write it to disk.
 impl Drop for AppendOnlyMerkle {
     #[inline(always)]
     fn drop(&mut self) {
+        if self.read_only {
+            return;
+        }
         if let Err(e) = self.write() {
             panic!("AppendOnlyMerkle: drop failed, err = {}", e);
         }
@@ -600,6 +606,7 @@ impl AppendOnlyMerkle {
             blocks: vec![Vec::new()],
             files: vec![file],
             blocks_on_disk: vec![0],
+            read_only: false,
         }
     }
 
@@ -625,6 +632,24 @@ impl AppendOnlyMerkle {
         }
     }
 
+    /// Open an existing Merkle tree without write access, used to inspect
+    /// the data of a stopped node. Nothing on disk is created, repaired or
+    /// removed, any inconsistency is returned as an error, and the tree
+    /// refuses to append or write.
+    ///
+    /// # Argument
+    ///
+    /// * `path` - a string specifying the path to the base file
+    pub fn open_read_only(path: &str) -> Result<AppendOnlyMerkle> {
+        let file = OpenOptions::new().read(true).open(path).c(d!(path))?;
+        let mut result = AppendOnlyMerkle::new(path, file);
+        result.read_only = true;
+
+        result.open_files().c(d!())?;
+        result.read_files(true).c(d!())?;
+        Ok(result)
+    }
+
     /// Create a new Merkle tree at the given path. This routine returns
     /// an error if the tree exists.
     pub fn create(path: &str) -> Result<AppendOnlyMerkle> {
@@ -834,6 +859,18 @@ impl AppendOnlyMerkle {
         self.blocks[0][block_id].hashes[block_index]
     }
 
+    /// Returns the leaf hash of a given transaction id, if it exists.
+    pub fn get_leaf(&self, transaction_id: u64) -> Option<HashValue> {
+        if transaction_id >= self.entry_count {
+            return None;
+        }
+
+        let index = transaction_id as usize;
+        self.blocks[0]
+            .get(index / LEAVES_IN_BLOCK)
+            .map(|b| b.hashes[index % LEAVES_IN_BLOCK])
+    }
+
     /// Make a deserialized tree ready for use. The derived
     /// deserializer doesn't open files or set up a correct
     /// vector for the blocks_on_disk field. Do that here,
@@ -873,7 +910,10 @@ impl AppendOnlyMerkle {
 
         loop {
             let path = self.file_path(i);
-            let result = OpenOptions::new().read(true).write(true).open(&path);
+            let result = OpenOptions::new()
+                .read(true)
+                .write(!self.read_only)
+                .open(&path);
 
             match result {
                 Err(x) => {
@@ -942,6 +982,9 @@ impl AppendOnlyMerkle {
 
         // Remove any leftover files. open_files() stops at the first missing
         // file, but there might be others there at higher levels.
+        if self.read_only {
+            return Ok(());
+        }
         for i in self.files.len()..MAX_BLOCK_LEVELS {
             let path = self.file_path(i);
             let _ = fs::remove_file(&path);
@@ -1005,8 +1048,9 @@ impl AppendOnlyMerkle {
                     self.blocks[level].push(block);
                 }
                 Err(x) => {
-                    // Loss of a level zero block is irrecoverable.
-                    if level == 0 {
+                    // Loss of a level zero block is irrecoverable,
+                    // and nothing can be rebuilt without write access.
+                    if level == 0 || self.read_only {
                         return Err(eg!(x));
                     }
 
@@ -1065,6 +1109,10 @@ impl AppendOnlyMerkle {
             return Err(eg!("The level 0 file is corrupted."));
         }
 
+        if self.read_only {
+            return Err(eg!(format!("The level {} file is corrupted.", level)));
+        }
+
         let path = self.file_path(level);
 
         let _ = fs::remove_file(&path);
@@ -1183,6 +1231,10 @@ impl AppendOnlyMerkle {
     ///
     /// * `hash_value` - a HashValue structure for the new transaction
     pub fn append_hash(&mut self, hash_value: &HashValue) -> Result<u64> {
+        if self.read_only {
+            return Err(eg!("The tree is read-only."));
+        }
+
         if self.entry_count == 0 {
             if !self.blocks[0].is_empty() {
                 return Err(eg!(format!(
@@ -1658,6 +1710,10 @@ impl AppendOnlyMerkle {
     /// Save the tree to disk.
     /// At some point, flushes for transactional semantics might be important.
     pub fn write(&mut self) -> Result<()> {
+        if self.read_only {
+            return Err(eg!("The tree is read-only."));
+        }
+
         let mut entries_at_this_level = self.entry_count;
 
         // Write each block level of the tree to its file.
@@ -3120,6 +3176,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_open_read_only() {
+        let path = "test_open_read_only";
+        let _ = fs::remove_file(&path);
+
+        if AppendOnlyMerkle::open_read_only(&path).is_ok() {
+            panic!("Read-only open worked without a tree.");
+        }
+        assert!(fs::metadata(&path).is_err());
+
+        let mut tree = AppendOnlyMerkle::create(&path).unwrap();
+        for i in 0..2 * LEAVES_IN_BLOCK {
+            test_append(&mut tree, i as u64, false);
+        }
+        write_tree(&mut tree);
+        let root = tree.get_root_hash();
+        drop(tree);
+
+        let mut tree = AppendOnlyMerkle::open_read_only(&path).unwrap();
+        assert_eq!(tree.get_root_hash(), root);
+        assert_eq!(tree.get_leaf(3), Some(create_test_hash(3, false)));
+        assert!(tree.get_leaf(2 * LEAVES_IN_BLOCK as u64).is_none());
+        check_tree(&tree);
+        assert!(tree.append_hash(&create_test_hash(0, false)).is_err());
+        assert!(tree.write().is_err());
+        drop(tree);
+
+        // a corrupted upper level is reported instead of being rebuilt
+        let level1 = path.to_owned() + ".1";
+        let size = fs::metadata(&level1).unwrap().len();
+        OpenOptions::new()
+            .write(true)
+            .open(&level1)
+            .unwrap()
+            .set_len(size - 1)
+            .unwrap();
+
+        if AppendOnlyMerkle::open_read_only(&path).is_ok() {
+            panic!("Read-only open worked with a corrupt tree.");
+        }
+        assert_eq!(fs::metadata(&level1).unwrap().len(), size - 1);
+
+        let _ = fs::remove_file(&path);
+        for i in 1..MAX_BLOCK_LEVELS {
+            let _ = fs::remove_file(path.to_owned() + "." + &i.to_string());
+        }
+    }
+
     #[test]
     fn test_corrupt_level0() {
         let path = "test_corrupt_level0";